@@ -0,0 +1,215 @@
+//! Forward-mode automatic differentiation for model Jacobians. A model writes its
+//! derivative once, generic over [`Scalar`], in
+//! [`AutoDiffDynamics::derivative_generic`], and its `f64` instance serves as
+//! [`Dynamics::derivative`]. With the `autodiff` feature, evaluating it on `Dual`
+//! numbers yields exact Jacobian columns through `jacobian`, with no hand-derived
+//! partials.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[cfg(feature = "autodiff")]
+use nalgebra::DMatrix;
+
+use crate::{traits::Dynamics, types::StateVector};
+
+/// Number type a generic derivative is written in: `f64` for plain evaluation, `Dual`
+/// for differentiation.
+pub trait Scalar:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// A value that does not depend on the state, e.g. a model parameter.
+    fn constant(value: f64) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+}
+
+impl Scalar for f64 {
+    fn constant(value: f64) -> Self {
+        value
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+}
+
+/// Dual number `re + eps ε` with `ε² = 0`: carrying `eps = 1` on one input makes every
+/// result carry its exact partial derivative with respect to that input.
+#[cfg(feature = "autodiff")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual {
+    pub re: f64,
+    pub eps: f64,
+}
+
+#[cfg(feature = "autodiff")]
+impl Dual {
+    pub fn new(re: f64, eps: f64) -> Self {
+        Self { re, eps }
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.re * rhs.re, self.re * rhs.eps + self.eps * rhs.re)
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::new(
+            self.re / rhs.re,
+            (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        )
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.eps)
+    }
+}
+
+#[cfg(feature = "autodiff")]
+impl Scalar for Dual {
+    fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+
+    fn sin(self) -> Self {
+        Self::new(self.re.sin(), self.eps * self.re.cos())
+    }
+
+    fn cos(self) -> Self {
+        Self::new(self.re.cos(), -self.eps * self.re.sin())
+    }
+
+    fn tan(self) -> Self {
+        let t = self.re.tan();
+        Self::new(t, self.eps * (1.0 + t * t))
+    }
+
+    fn sqrt(self) -> Self {
+        let r = self.re.sqrt();
+        Self::new(r, self.eps / (2.0 * r))
+    }
+
+    fn exp(self) -> Self {
+        let e = self.re.exp();
+        Self::new(e, self.eps * e)
+    }
+}
+
+/// Models whose derivative is written generically over [`Scalar`], in [`StateVector`]
+/// component order. The model's [`Dynamics::derivative`] should call the `f64`
+/// instance, so there is one formula; with the `autodiff` feature, `jacobian` then
+/// gives the state Jacobian for free, and a model's
+/// [`LinearizableDynamics::jacobian`](crate::traits::LinearizableDynamics::jacobian) or
+/// [`Dynamics::dfdx`] can simply delegate to it.
+pub trait AutoDiffDynamics: Dynamics
+where
+    Self::State: StateVector,
+{
+    /// `f(t, x, u)` for the state components `x`, one rate per component written into
+    /// `dx` (of the same length), so the `f64` instance does not allocate.
+    fn derivative_generic<T: Scalar>(&self, t: f64, x: &[T], control: &Self::Control, dx: &mut [T]);
+}
+
+/// State Jacobian `∂f/∂x` of `model` at `(t, state, control)` by forward-mode autodiff:
+/// one dual evaluation of the derivative per state component.
+#[cfg(feature = "autodiff")]
+pub fn jacobian<M>(model: &M, t: f64, state: &M::State, control: &M::Control) -> DMatrix<f64>
+where
+    M: AutoDiffDynamics,
+    M::State: StateVector,
+{
+    let x = state.to_dvector();
+    let n = x.len();
+    let mut jac = DMatrix::zeros(n, n);
+    let mut column = vec![Dual::constant(0.0); n];
+    for k in 0..n {
+        let seeded: Vec<Dual> = x
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Dual::new(v, if i == k { 1.0 } else { 0.0 }))
+            .collect();
+        model.derivative_generic(t, &seeded, control, &mut column);
+        for (i, d) in column.iter().enumerate() {
+            jac[(i, k)] = d.eps;
+        }
+    }
+    jac
+}
+
+#[cfg(all(test, feature = "autodiff"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dual_arithmetic_carries_exact_derivatives() {
+        let x = Dual::new(0.7, 1.0);
+        let two = Dual::constant(2.0);
+
+        // d/dx [x² sin x / (2 + x)] by the product and quotient rules
+        let f = x * x * x.sin() / (two + x);
+        let (v, s, c) = (0.7_f64, 0.7_f64.sin(), 0.7_f64.cos());
+        let expected = ((2.0 * v * s + v * v * c) * (2.0 + v) - v * v * s) / (2.0 + v).powi(2);
+        assert_eq!(f.re, v * v * s / (2.0 + v));
+        assert!((f.eps - expected).abs() < 1e-15);
+
+        let g = (-x).exp().sqrt() - x.tan() * x.cos();
+        let expected = -0.5 * (-0.5 * v).exp() - c;
+        assert!((g.eps - expected).abs() < 1e-15);
+    }
+}
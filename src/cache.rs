@@ -0,0 +1,125 @@
+// Memoization support: hashable keys describing a prediction request.
+
+use std::fmt::Debug;
+
+use crate::{
+    traits::Dynamics,
+    types::{DroneInput, StateVector},
+};
+
+/// Hashable identity of a constant-input prediction request, for memoizing
+/// [`predict`](crate::predict::predict) results. Floating-point values are quantized to
+/// multiples of `resolution`, so requests that differ by less than about that much share
+/// a key.
+///
+/// The model contributes its `Debug` representation (i.e. its exact parameters) and the
+/// solver its type name; solver options such as
+/// [`NewtonOpts`](crate::solvers::NewtonOpts) are not part of the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PredictionKey {
+    state: Vec<i64>,
+    input: [i64; 4],
+    model: String,
+    solver: &'static str,
+    t0: i64,
+    t_final: i64,
+    steps: usize,
+}
+
+impl PredictionKey {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<M, S>(
+        input: &DroneInput,
+        initial_state: &M::State,
+        model: &M,
+        _solver: &S,
+        t0: f64,
+        t_final: f64,
+        steps: usize,
+        resolution: f64,
+    ) -> Self
+    where
+        M: Dynamics + Debug,
+        M::State: StateVector,
+    {
+        assert!(
+            resolution.is_finite() && resolution > 0.0,
+            "resolution must be finite and > 0"
+        );
+        let q = |v: f64| {
+            assert!(v.is_finite(), "cannot key a non-finite value");
+            (v / resolution).round() as i64
+        };
+
+        Self {
+            state: initial_state.to_dvector().iter().map(|v| q(*v)).collect(),
+            input: [
+                q(input.roll_rad),
+                q(input.pitch_rad),
+                q(input.yaw_rate_rps),
+                q(input.thrust_norm),
+            ],
+            model: format!("{model:?}"),
+            solver: std::any::type_name::<S>(),
+            t0: q(t0),
+            t_final: q(t_final),
+            steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::{ForwardEuler, Rk4},
+    };
+
+    const INPUT: DroneInput = DroneInput {
+        roll_rad: 0.1,
+        pitch_rad: 0.2,
+        yaw_rate_rps: 0.3,
+        thrust_norm: 0.5,
+    };
+
+    fn key(input: &DroneInput, state: &SimpleQuadState, model: &SimpleQuadcopter) -> PredictionKey {
+        PredictionKey::new(input, state, model, &Rk4, 0.0, 5.0, 100, 1e-6)
+    }
+
+    #[test]
+    fn equivalent_requests_share_a_key() {
+        let model = SimpleQuadcopter::new(0.2);
+        let state = SimpleQuadState::new(1.0, 2.0, 3.0, 0.0, 0.5);
+        let nudged = SimpleQuadState {
+            north_m: 1.0 + 1e-9,
+            ..state
+        };
+        assert_eq!(key(&INPUT, &state, &model), key(&INPUT, &nudged, &model));
+
+        let perturbed = DroneInput {
+            pitch_rad: 0.2 + 1e-3,
+            ..INPUT
+        };
+        assert_ne!(key(&INPUT, &state, &model), key(&perturbed, &state, &model));
+        assert_ne!(
+            key(&INPUT, &state, &model),
+            key(&INPUT, &state, &SimpleQuadcopter::new(0.3))
+        );
+        assert_ne!(
+            key(&INPUT, &state, &model),
+            PredictionKey::new(&INPUT, &state, &model, &ForwardEuler, 0.0, 5.0, 100, 1e-6)
+        );
+
+        let mut cache = HashMap::new();
+        for _ in 0..2 {
+            cache
+                .entry(key(&INPUT, &state, &model))
+                .or_insert_with(|| predict(&INPUT, state, &model, &mut Rk4, 0.0, 5.0, 100));
+        }
+        assert_eq!(cache.len(), 1);
+    }
+}
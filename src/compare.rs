@@ -0,0 +1,555 @@
+use std::time::Duration;
+
+use crate::{
+    predict::{Prediction, predict, predict_backward},
+    solvers::{BackwardEuler, ForwardEuler, Rk4},
+    traits::{Dynamics, LinearizableDynamics, Stepper},
+    types::{DroneInput, Position2D, StateVector},
+};
+
+/// Outcome of one solver in [`compare_solvers`].
+#[derive(Debug, Clone)]
+pub struct SolverReport<S> {
+    pub solver: &'static str,
+    pub endpoint: S,
+    /// XY path length [m], see [`Prediction::arc_length`](crate::predict::Prediction::arc_length).
+    pub arc_length: f64,
+    pub cpu_time: Duration,
+}
+
+impl<S: StateVector> SolverReport<S> {
+    /// Max absolute component difference between the endpoint and `reference`, e.g. an
+    /// analytic solution or a fine-grid run.
+    pub fn endpoint_error(&self, reference: &S) -> f64 {
+        (self.endpoint.to_dvector() - reference.to_dvector()).amax()
+    }
+}
+
+/// Run ForwardEuler, RK4 and BackwardEuler on the same scenario (from `t = 0`) and
+/// report each solver's endpoint, arc length and CPU time, in that order.
+pub fn compare_solvers<M>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    t_final: f64,
+    steps: usize,
+) -> Vec<SolverReport<M::State>>
+where
+    M: LinearizableDynamics,
+    M::State: StateVector + Position2D,
+{
+    vec![
+        report(
+            "ForwardEuler",
+            &mut ForwardEuler,
+            input,
+            &initial_state,
+            model,
+            t_final,
+            steps,
+        ),
+        report(
+            "Rk4",
+            &mut Rk4,
+            input,
+            &initial_state,
+            model,
+            t_final,
+            steps,
+        ),
+        report(
+            "BackwardEuler",
+            &mut BackwardEuler::default(),
+            input,
+            &initial_state,
+            model,
+            t_final,
+            steps,
+        ),
+    ]
+}
+
+/// Estimated endpoint error of a `steps`-step prediction (from `t = 0`), without a
+/// reference solution: runs `steps` and `2 * steps` and Richardson-extrapolates with the
+/// solver's [`Stepper::order`] `p`, i.e. `|x_n - x_2n| / (1 - 2^-p)` in the max norm.
+pub fn estimate_endpoint_error<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t_final: f64,
+    steps: usize,
+) -> f64
+where
+    M: Dynamics,
+    M::State: StateVector,
+    S: Stepper<M>,
+{
+    let p = solver.order();
+    assert!(p > 0, "solver order must be > 0");
+
+    let coarse = predict(
+        input,
+        initial_state.clone(),
+        model,
+        solver,
+        0.0,
+        t_final,
+        steps,
+    );
+    let fine = predict(input, initial_state, model, solver, 0.0, t_final, 2 * steps);
+    let diff = (coarse.final_state().to_dvector() - fine.final_state().to_dvector()).amax();
+
+    diff / (1.0 - 0.5f64.powi(p as i32))
+}
+
+/// Reversibility check: integrate `steps` steps forward over `t_final` (from `t = 0`),
+/// then the same number back from the endpoint with negative `dt`, and return the
+/// Euclidean norm of the difference from `initial_state`. An exact integrator of a
+/// reversible model returns 0; the result otherwise shrinks like `dt^p` for a solver
+/// of order `p`. Dissipation (e.g. drag) makes the backward pass amplify errors, so
+/// compare values across solvers on the same scenario rather than to a fixed scale.
+/// The solver must accept negative `dt`, see
+/// [`predict_backward`](crate::predict::predict_backward).
+pub fn round_trip_error<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t_final: f64,
+    steps: usize,
+) -> f64
+where
+    M: Dynamics,
+    M::State: StateVector,
+    S: Stepper<M>,
+{
+    let start = initial_state.to_dvector();
+    let forward = predict(input, initial_state, model, solver, 0.0, t_final, steps);
+    let back = predict_backward(
+        input,
+        forward.final_state().clone(),
+        model,
+        solver,
+        t_final,
+        t_final,
+        steps,
+    );
+    (back.final_state().to_dvector() - start).norm()
+}
+
+/// One prediction per entry of `values`, each from a clone of `base_model` with the
+/// scalar parameter set by `set_param` (e.g. `|m, v| m.drag = v`), for checking how
+/// sensitive a prediction is to a model estimate. Overlay the result with
+/// [`plot_xy_multi`](crate::plot::plot_xy_multi).
+#[allow(clippy::too_many_arguments)]
+pub fn parameter_sweep<M, S, F>(
+    base_model: &M,
+    mut set_param: F,
+    values: &[f64],
+    input: &DroneInput,
+    initial_state: M::State,
+    solver: &mut S,
+    t_final: f64,
+    steps: usize,
+) -> Vec<Prediction<M::State, M::Control>>
+where
+    M: Dynamics + Clone,
+    S: Stepper<M>,
+    F: FnMut(&mut M, f64),
+{
+    values
+        .iter()
+        .map(|&value| {
+            let mut model = base_model.clone();
+            set_param(&mut model, value);
+            predict(
+                input,
+                initial_state.clone(),
+                &model,
+                solver,
+                0.0,
+                t_final,
+                steps,
+            )
+        })
+        .collect()
+}
+
+/// Smallest power-of-two step count (from 8) whose Richardson endpoint error estimate,
+/// as in [`estimate_endpoint_error`], is below `target_error`. Returns the chosen
+/// `steps` and the estimated error. No run takes more than `2^20` steps, so the search
+/// gives up at `steps = 2^19` (checked against its `2^20`-step fine run), in which case
+/// the returned error is still above the target.
+pub fn choose_steps<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t_final: f64,
+    target_error: f64,
+) -> (usize, f64)
+where
+    M: Dynamics,
+    M::State: StateVector,
+    S: Stepper<M>,
+{
+    const MIN_STEPS: usize = 8;
+    // Cap on any single run, the fine one included
+    const MAX_STEPS: usize = 1 << 20;
+    assert!(target_error > 0.0, "target_error must be > 0");
+    let p = solver.order();
+    assert!(p > 0, "solver order must be > 0");
+
+    let mut endpoint = |steps| {
+        predict(
+            input,
+            initial_state.clone(),
+            model,
+            solver,
+            0.0,
+            t_final,
+            steps,
+        )
+        .final_state()
+        .to_dvector()
+    };
+
+    // Each doubling reuses the previous fine run as the new coarse one
+    let mut steps = MIN_STEPS;
+    let mut coarse = endpoint(steps);
+    loop {
+        let fine = endpoint(2 * steps);
+        let error = (&coarse - &fine).amax() / (1.0 - 0.5f64.powi(p as i32));
+        if error < target_error || 2 * steps >= MAX_STEPS {
+            return (steps, error);
+        }
+        steps *= 2;
+        coarse = fine;
+    }
+}
+
+fn report<M, S>(
+    solver_name: &'static str,
+    solver: &mut S,
+    input: &DroneInput,
+    initial_state: &M::State,
+    model: &M,
+    t_final: f64,
+    steps: usize,
+) -> SolverReport<M::State>
+where
+    M: LinearizableDynamics,
+    M::State: Position2D,
+    S: Stepper<M>,
+{
+    let prediction = predict(
+        input,
+        initial_state.clone(),
+        model,
+        solver,
+        0.0,
+        t_final,
+        steps,
+    );
+    SolverReport {
+        solver: solver_name,
+        endpoint: prediction.final_state().clone(),
+        arc_length: prediction.arc_length(),
+        cpu_time: prediction.cpu_time(),
+    }
+}
+
+/// Position fit scores between two trajectories, see [`compare_trajectories`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectoryMetrics {
+    /// Root-mean-square XY distance [m] between time-aligned samples.
+    pub rmse_m: f64,
+    /// Largest XY distance [m] between time-aligned samples.
+    pub max_deviation_m: f64,
+    /// Dynamic-time-warping distance [m]: the summed XY distance along the cheapest
+    /// monotone alignment of the raw samples. Only computed on request (`O(n m)`).
+    pub dtw_m: Option<f64>,
+}
+
+/// Compare the XY paths of `a` and `b`, assumed to span the same time interval with
+/// uniform sampling. When the lengths differ, `b` is linearly resampled onto `a`'s
+/// sample times for the RMSE and max deviation; the DTW distance, computed when
+/// `with_dtw` is set, aligns the raw samples instead.
+pub fn compare_trajectories<S: Position2D>(a: &[S], b: &[S], with_dtw: bool) -> TrajectoryMetrics {
+    assert!(
+        !a.is_empty() && !b.is_empty(),
+        "trajectories must not be empty"
+    );
+    let pa: Vec<(f64, f64)> = a.iter().map(Position2D::position).collect();
+    let pb: Vec<(f64, f64)> = b.iter().map(Position2D::position).collect();
+    let pb_aligned = resample_positions(&pb, pa.len());
+
+    let distances: Vec<f64> = pa
+        .iter()
+        .zip(&pb_aligned)
+        .map(|(p, q)| distance(*p, *q))
+        .collect();
+
+    TrajectoryMetrics {
+        rmse_m: (distances.iter().map(|d| d * d).sum::<f64>() / distances.len() as f64).sqrt(),
+        max_deviation_m: distances.iter().copied().fold(0.0, f64::max),
+        dtw_m: with_dtw.then(|| dtw_distance(&pa, &pb)),
+    }
+}
+
+fn distance(p: (f64, f64), q: (f64, f64)) -> f64 {
+    (p.0 - q.0).hypot(p.1 - q.1)
+}
+
+/// Linear interpolation of a uniformly sampled path onto `n` uniform samples.
+fn resample_positions(points: &[(f64, f64)], n: usize) -> Vec<(f64, f64)> {
+    if points.len() == n {
+        return points.to_vec();
+    }
+    if points.len() == 1 || n == 1 {
+        return vec![points[0]; n];
+    }
+
+    let last = (points.len() - 1) as f64;
+    (0..n)
+        .map(|i| {
+            let x = i as f64 * last / (n - 1) as f64;
+            let k = (x.floor() as usize).min(points.len() - 2);
+            let f = x - k as f64;
+            let (p, q) = (points[k], points[k + 1]);
+            (p.0 + f * (q.0 - p.0), p.1 + f * (q.1 - p.1))
+        })
+        .collect()
+}
+
+/// Classic DTW with Euclidean cost, keeping one row of the cost table.
+fn dtw_distance(a: &[(f64, f64)], b: &[(f64, f64)]) -> f64 {
+    let mut prev = vec![f64::INFINITY; b.len() + 1];
+    let mut row = vec![f64::INFINITY; b.len() + 1];
+    prev[0] = 0.0;
+
+    for p in a {
+        row[0] = f64::INFINITY;
+        for (j, q) in b.iter().enumerate() {
+            let best = prev[j].min(prev[j + 1]).min(row[j]);
+            row[j + 1] = distance(*p, *q) + best;
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_models::{SimpleQuadState, SimpleQuadcopter};
+
+    #[test]
+    fn more_drag_means_a_shorter_path() {
+        let input = DroneInput::try_new(0.05, 0.2, 0.1).unwrap();
+        let drags = [0.05, 0.1, 0.2, 0.4];
+        let predictions = parameter_sweep(
+            &SimpleQuadcopter::new(0.0),
+            |m, v| m.drag = v,
+            &drags,
+            &input,
+            SimpleQuadState::zero(),
+            &mut Rk4,
+            10.0,
+            100,
+        );
+
+        assert_eq!(predictions.len(), drags.len());
+        let lengths: Vec<f64> = predictions.iter().map(|p| p.arc_length()).collect();
+        assert!(
+            lengths.windows(2).all(|w| w[1] < w[0]),
+            "arc lengths {lengths:?}"
+        );
+    }
+
+    #[test]
+    fn rk4_round_trip_is_far_tighter_than_forward_euler() {
+        let input = DroneInput {
+            roll_rad: 0.05,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.4,
+            thrust_norm: 0.5,
+        };
+        let model = SimpleQuadcopter::new(0.2);
+        let start = SimpleQuadState::new(1.0, -1.0, 2.0, 0.5, 0.3);
+
+        let rk4 = round_trip_error(&input, start, &model, &mut Rk4, 5.0, 100);
+        let fe = round_trip_error(&input, start, &model, &mut ForwardEuler, 5.0, 100);
+        // FE loses O(dt) on each pass; RK4's O(dt^4) is near round-off at dt = 0.05
+        assert!(rk4 < 1e-6, "RK4 round trip {rk4}");
+        assert!(fe > 1e-2, "FE round trip {fe}");
+
+        let fe_fine = round_trip_error(&input, start, &model, &mut ForwardEuler, 5.0, 200);
+        assert!(
+            (fe / fe_fine - 2.0).abs() < 0.2,
+            "FE ratio {}",
+            fe / fe_fine
+        );
+    }
+
+    #[test]
+    fn rk4_beats_forward_euler_at_equal_steps() {
+        let model = SimpleQuadcopter::new(0.2);
+        let input = DroneInput {
+            roll_rad: 5f64.to_radians(),
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        };
+
+        let reports = compare_solvers(&input, SimpleQuadState::zero(), &model, 8.0, 200);
+        let names: Vec<_> = reports.iter().map(|r| r.solver).collect();
+        assert_eq!(names, ["ForwardEuler", "Rk4", "BackwardEuler"]);
+
+        let reference = predict(
+            &input,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            8.0,
+            20_000,
+        );
+        let reference = reference.final_state();
+        let fe = reports[0].endpoint_error(reference);
+        let rk4 = reports[1].endpoint_error(reference);
+        let be = reports[2].endpoint_error(reference);
+        assert!(rk4 < fe && rk4 < be, "rk4 {rk4}, fe {fe}, be {be}");
+        assert!(reports.iter().all(|r| r.arc_length > 0.0));
+    }
+
+    #[test]
+    fn richardson_estimate_tracks_true_error() {
+        let model = SimpleQuadcopter::new(0.3);
+        let input = DroneInput {
+            roll_rad: 3f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.25,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let reference = predict(&input, start, &model, &mut Rk4, 0.0, 6.0, 50_000);
+        let reference = reference.final_state();
+
+        let mut last = f64::INFINITY;
+        for steps in [25, 50, 100, 200] {
+            let estimate =
+                estimate_endpoint_error(&input, start, &model, &mut ForwardEuler, 6.0, steps);
+            let truth = report("fe", &mut ForwardEuler, &input, &start, &model, 6.0, steps)
+                .endpoint_error(reference);
+
+            assert!(
+                (estimate / truth - 1.0).abs() < 0.2,
+                "steps {steps}: {estimate} vs {truth}"
+            );
+            assert!(estimate < last);
+            last = estimate;
+        }
+
+        let rk4 = estimate_endpoint_error(&input, start, &model, &mut Rk4, 6.0, 100);
+        let rk4_truth =
+            report("rk4", &mut Rk4, &input, &start, &model, 6.0, 100).endpoint_error(reference);
+        assert!((rk4 / rk4_truth - 1.0).abs() < 0.2, "{rk4} vs {rk4_truth}");
+    }
+
+    #[test]
+    fn choose_steps_meets_the_target() {
+        let model = SimpleQuadcopter::new(0.3);
+        let input = DroneInput {
+            roll_rad: 3f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.25,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+
+        let (tight, tight_err) = choose_steps(&input, start, &model, &mut Rk4, 6.0, 1e-8);
+        let (loose, loose_err) = choose_steps(&input, start, &model, &mut Rk4, 6.0, 1e-3);
+        assert!(tight_err < 1e-8 && loose_err < 1e-3);
+        assert!(loose < tight, "{loose} vs {tight}");
+        assert_eq!(
+            estimate_endpoint_error(&input, start, &model, &mut Rk4, 6.0, tight),
+            tight_err
+        );
+
+        let (fe, _) = choose_steps(&input, start, &model, &mut ForwardEuler, 6.0, 1e-3);
+        assert!(fe > loose);
+    }
+
+    #[test]
+    fn choose_steps_gives_up_with_the_fine_run_at_the_cap() {
+        let model = SimpleQuadcopter::new(0.3);
+        let input = DroneInput::try_new(0.1, 0.2, 0.3).unwrap();
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+
+        // Euler at 2^19 steps is still far from any representable target
+        let (steps, error) = choose_steps(
+            &input,
+            start,
+            &model,
+            &mut ForwardEuler,
+            1.0,
+            f64::MIN_POSITIVE,
+        );
+        assert_eq!(steps, 1 << 19);
+        assert!(error > f64::MIN_POSITIVE);
+    }
+
+    #[test]
+    fn trajectory_metrics_for_identical_and_offset_paths() {
+        let model = SimpleQuadcopter::new(0.2);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let path = predict(&input, start, &model, &mut Rk4, 0.0, 5.0, 100).states;
+
+        let same = compare_trajectories(&path, &path, true);
+        assert_eq!(same.rmse_m, 0.0);
+        assert_eq!(same.max_deviation_m, 0.0);
+        assert_eq!(same.dtw_m, Some(0.0));
+
+        // Constant 3-4-5 offset: every aligned sample is 5 m away
+        let mut shifted = path.clone();
+        for s in &mut shifted {
+            s.north_m += 3.0;
+            s.east_m -= 4.0;
+        }
+        let offset = compare_trajectories(&path, &shifted, false);
+        assert!((offset.rmse_m - 5.0).abs() < 1e-12);
+        assert!((offset.max_deviation_m - 5.0).abs() < 1e-12);
+        assert_eq!(offset.dtw_m, None);
+    }
+
+    #[test]
+    fn differently_sampled_straight_paths_agree() {
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 1.0, 0.0);
+        let hover = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let coarse = predict(&hover, start, &model, &mut Rk4, 0.0, 4.0, 40).states;
+        let fine = predict(&hover, start, &model, &mut Rk4, 0.0, 4.0, 400).states;
+
+        // Constant velocity: linear resampling is exact
+        let metrics = compare_trajectories(&coarse, &fine, true);
+        assert!(metrics.rmse_m < 1e-12);
+        assert!(metrics.max_deviation_m < 1e-12);
+        // DTW matches each fine sample to its nearest coarse one, which is never more
+        // than half a coarse step (0.5 * 0.1 s * |v|) away
+        let half_step = 0.05 * 5f64.sqrt();
+        assert!(metrics.dtw_m.unwrap() < 401.0 * half_step);
+    }
+}
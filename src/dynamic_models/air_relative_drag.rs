@@ -0,0 +1,205 @@
+use nalgebra::{DMatrix, Matrix2, Vector2};
+
+use crate::{
+    dynamic_models::{Composed, SimpleQuadControl, SimpleQuadState, SimpleQuadcopter},
+    math::{rotation_ned_from_body, rotation_ned_from_body_dyaw},
+    traits::{DisturbanceModel, Dynamics, LinearizableDisturbance},
+};
+
+/// Per-axis drag on the velocity relative to a steady wind, for the
+/// [`SimpleQuadcopter`](crate::dynamic_models::SimpleQuadcopter). The air-relative
+/// velocity `v - wind` is rotated into the body frame, each axis is damped with its own
+/// coefficient, and the result is rotated back to NED, so a crosswind loads the side
+/// of the airframe rather than its nose. At rest in the air mass the vehicle drifts
+/// with the wind.
+///
+/// This replaces the base model's world-frame drag rather than adding to it, so build
+/// the composition with [`compose`](Self::compose), which zeroes the base drag.
+#[derive(Debug, Clone)]
+pub struct AirRelativeDrag {
+    /// Velocity of the air mass `(north, east)` [m/s], the direction it blows *towards*.
+    pub wind_ned_mps: (f64, f64),
+    /// Drag [1/s] along the body x-axis (nose).
+    pub drag_forward: f64,
+    /// Drag [1/s] along the body y-axis (right wing).
+    pub drag_right: f64,
+    /// Drag [1/s] on the vertical channel, which sees no wind.
+    pub drag_down: f64,
+}
+
+impl AirRelativeDrag {
+    pub fn new(
+        wind_ned_mps: (f64, f64),
+        drag_forward: f64,
+        drag_right: f64,
+        drag_down: f64,
+    ) -> Self {
+        assert!(
+            drag_forward >= 0.0 && drag_right >= 0.0 && drag_down >= 0.0,
+            "drag coefficients must be >= 0"
+        );
+        Self {
+            wind_ned_mps,
+            drag_forward,
+            drag_right,
+            drag_down,
+        }
+    }
+
+    /// `base` with its world-frame drag replaced by this one.
+    pub fn compose(self, base: SimpleQuadcopter) -> Composed<SimpleQuadcopter, Self> {
+        Composed::new(SimpleQuadcopter { drag: 0.0, ..base }, self)
+    }
+
+    /// Air-relative horizontal velocity in NED.
+    fn air_velocity(&self, state: &SimpleQuadState) -> Vector2<f64> {
+        let (wn, we) = self.wind_ned_mps;
+        Vector2::new(state.v_north_mps - wn, state.v_east_mps - we)
+    }
+
+    fn body_drag(&self) -> Matrix2<f64> {
+        Matrix2::new(self.drag_forward, 0.0, 0.0, self.drag_right)
+    }
+}
+
+impl<M> DisturbanceModel<M> for AirRelativeDrag
+where
+    M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+{
+    fn extra_derivative(
+        &self,
+        _t: f64,
+        state: &SimpleQuadState,
+        _control: &SimpleQuadControl,
+    ) -> SimpleQuadState {
+        let r = rotation_ned_from_body(state.yaw_rad);
+        let a = -(r * self.body_drag() * r.transpose() * self.air_velocity(state));
+        SimpleQuadState::new(0.0, 0.0, a.x, a.y, 0.0)
+            .with_vertical(0.0, -self.drag_down * state.v_down_mps)
+    }
+}
+
+impl<M> LinearizableDisturbance<M> for AirRelativeDrag
+where
+    M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+{
+    fn jacobian(
+        &self,
+        _t: f64,
+        state: &SimpleQuadState,
+        _control: &SimpleQuadControl,
+    ) -> DMatrix<f64> {
+        let r = rotation_ned_from_body(state.yaw_rad);
+        let dr = rotation_ned_from_body_dyaw(state.yaw_rad);
+        let c = self.body_drag();
+        let dv = -(r * c * r.transpose());
+        let dyaw = -((dr * c * r.transpose() + r * c * dr.transpose()) * self.air_velocity(state));
+
+        let mut j = DMatrix::zeros(7, 7);
+        // Rows and columns 2, 3 are v_north, v_east; column 4 is yaw
+        j.view_mut((2, 2), (2, 2)).copy_from(&dv);
+        j[(2, 4)] = dyaw.x;
+        j[(3, 4)] = dyaw.y;
+        j[(6, 6)] = -self.drag_down;
+        j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use crate::{
+        dynamic_models::assert_jacobian_matches_fd,
+        frames::wind_triangle,
+        predict::predict,
+        solvers::Rk4,
+        types::{DroneInput, GroundVelocity},
+    };
+
+    const HOVER: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    fn model(wind: (f64, f64)) -> Composed<SimpleQuadcopter, AirRelativeDrag> {
+        AirRelativeDrag::new(wind, 0.2, 0.8, 0.5).compose(SimpleQuadcopter::new(0.0))
+    }
+
+    #[test]
+    fn composing_drops_the_base_drag() {
+        let wind = (1.0, -2.0);
+        let composed = AirRelativeDrag::new(wind, 0.2, 0.8, 0.5)
+            .compose(SimpleQuadcopter::new(0.7).with_max_ground_speed(9.0));
+        assert_eq!(composed.base.drag, 0.0);
+        assert_eq!(composed.base.max_ground_speed_mps, Some(9.0));
+
+        // Moving with the air mass meets no drag at all
+        let state = SimpleQuadState::new(0.0, 0.0, wind.0, wind.1, 0.3);
+        let hover = composed.input_to_control(&HOVER);
+        let derivative = composed.derivative(0.0, &state, &hover);
+        assert_eq!((derivative.v_north_mps, derivative.v_east_mps), (0.0, 0.0));
+    }
+
+    #[test]
+    fn crosswind_drift_settles_on_the_wind_triangle() {
+        let wind = (0.0, 4.0);
+        let model = model(wind);
+        let t_final = 15.0;
+        let prediction = predict(
+            &HOVER,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            t_final,
+            1500,
+        );
+
+        // Facing North, the crosswind acts on the side: v_e = w (1 - exp(-c_right t))
+        let c = model.disturbance.drag_right;
+        let at = |t: f64| &prediction.states[(t / prediction.dt()).round() as usize];
+        assert!((at(1.0 / c).v_east_mps - 4.0 * (1.0 - (-1.0f64).exp())).abs() < 1e-6);
+        let drift = 4.0 * (t_final - (1.0 - (-c * t_final).exp()) / c);
+        let end = prediction.final_state();
+        assert!((end.east_m - drift).abs() < 1e-6);
+        assert_eq!(end.north_m, 0.0);
+
+        // At rest in the air mass the ground velocity is the zero-airspeed wind triangle
+        let (gs, track) = wind_triangle(0.0, end.yaw_rad, wind.0, wind.1);
+        assert!((end.ground_speed() - gs).abs() < 1e-3);
+        assert!((end.v_east_mps.atan2(end.v_north_mps) - track).abs() < 1e-12);
+    }
+
+    #[test]
+    fn crosswind_response_follows_the_body_axis_it_loads() {
+        let settle = |yaw: f64| {
+            let start = SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, yaw);
+            let prediction = predict(&HOVER, start, &model((0.0, 4.0)), &mut Rk4, 0.0, 1.0, 100);
+            prediction.final_state().v_east_mps
+        };
+
+        // Facing East the same wind arrives on the nose, whose drag is weaker
+        let side_on = settle(0.0);
+        let head_on = settle(FRAC_PI_2);
+        assert!((side_on - 4.0 * (1.0 - (-0.8f64).exp())).abs() < 1e-6);
+        assert!((head_on - 4.0 * (1.0 - (-0.2f64).exp())).abs() < 1e-6);
+    }
+
+    #[test]
+    fn jacobian_matches_central_differences() {
+        let model = model((1.5, -3.0));
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.7,
+        });
+        let state = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.4).with_vertical(0.6, -0.5);
+
+        assert_jacobian_matches_fd(&model, &state, &control, 1e-6);
+    }
+}
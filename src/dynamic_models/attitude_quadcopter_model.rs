@@ -0,0 +1,332 @@
+use nalgebra::Vector3;
+
+use crate::{
+    math::rotation_ned_from_body_3d,
+    traits::{Dynamics, GRAVITY},
+    types::{
+        ControlChannels, DroneInput, GroundVelocity, Heading, IntegrableState, Position2D,
+        Saturation, StateVector,
+    },
+};
+
+/// Planar NED quadcopter carrying a full roll/pitch/yaw attitude instead of yaw only.
+/// Roll and pitch follow the stick through a first-order lag, and the collective
+/// thrust is scaled to hold altitude, so body thrust is rotated into NED through the
+/// full attitude matrix rather than the small-angle `g * tan` shortcut per axis.
+/// Angles follow the [`DroneInput`] conventions.
+#[derive(Debug, Clone)]
+pub struct AttitudeQuadcopter {
+    pub drag: f64,
+    /// Time constant [s] of the roll/pitch response to the stick.
+    pub attitude_tau_s: f64,
+}
+
+impl AttitudeQuadcopter {
+    pub fn new(drag: f64, attitude_tau_s: f64) -> Self {
+        assert!(attitude_tau_s > 0.0, "attitude_tau_s must be > 0");
+        Self {
+            drag,
+            attitude_tau_s,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttitudeQuadState {
+    pub north_m: f64,
+    pub east_m: f64,
+    pub v_north_mps: f64,
+    pub v_east_mps: f64,
+    pub roll_rad: f64,
+    pub pitch_rad: f64,
+    pub yaw_rad: f64,
+}
+
+impl AttitudeQuadState {
+    pub fn zero() -> Self {
+        Self {
+            north_m: 0.0,
+            east_m: 0.0,
+            v_north_mps: 0.0,
+            v_east_mps: 0.0,
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rad: 0.0,
+        }
+    }
+
+    pub fn ensure_finite(&self) {
+        if let Some(field) = self.non_finite_field() {
+            panic!("{field} must be finite");
+        }
+    }
+}
+
+impl IntegrableState for AttitudeQuadState {
+    fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+        let mut next = *self;
+        next.add_scaled_assign(derivative, scale);
+        next
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.north_m += scale * derivative.north_m;
+        self.east_m += scale * derivative.east_m;
+        self.v_north_mps += scale * derivative.v_north_mps;
+        self.v_east_mps += scale * derivative.v_east_mps;
+        self.roll_rad += scale * derivative.roll_rad;
+        self.pitch_rad += scale * derivative.pitch_rad;
+        self.yaw_rad += scale * derivative.yaw_rad;
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("north_m", self.north_m),
+            ("east_m", self.east_m),
+            ("v_north_mps", self.v_north_mps),
+            ("v_east_mps", self.v_east_mps),
+            ("roll_rad", self.roll_rad),
+            ("pitch_rad", self.pitch_rad),
+            ("yaw_rad", self.yaw_rad),
+        ]
+        .into_iter()
+        .find(|(_, v)| !v.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+impl Position2D for AttitudeQuadState {
+    fn position(&self) -> (f64, f64) {
+        (self.north_m, self.east_m)
+    }
+}
+
+impl GroundVelocity for AttitudeQuadState {
+    fn ground_velocity(&self) -> (f64, f64) {
+        (self.v_north_mps, self.v_east_mps)
+    }
+}
+
+impl Heading for AttitudeQuadState {
+    fn yaw_rad(&self) -> f64 {
+        self.yaw_rad
+    }
+
+    fn with_yaw(&self, yaw_rad: f64) -> Self {
+        Self { yaw_rad, ..*self }
+    }
+}
+
+impl StateVector for AttitudeQuadState {
+    fn to_dvector(&self) -> nalgebra::DVector<f64> {
+        nalgebra::DVector::from_vec(vec![
+            self.north_m,
+            self.east_m,
+            self.v_north_mps,
+            self.v_east_mps,
+            self.roll_rad,
+            self.pitch_rad,
+            self.yaw_rad,
+        ])
+    }
+
+    fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
+        assert!(
+            v.len() == 7,
+            "AttitudeQuadState expects 7 elements (north, east, v_north, v_east, roll, pitch, yaw)"
+        );
+        Self {
+            north_m: v[0],
+            east_m: v[1],
+            v_north_mps: v[2],
+            v_east_mps: v[3],
+            roll_rad: v[4],
+            pitch_rad: v[5],
+            yaw_rad: v[6],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttitudeQuadControl {
+    pub roll_cmd_rad: f64,
+    pub pitch_cmd_rad: f64,
+    pub yaw_rate_rps: f64,
+}
+
+impl ControlChannels for AttitudeQuadControl {
+    fn channel_names() -> &'static [&'static str] {
+        &["roll_cmd [rad]", "pitch_cmd [rad]", "yaw_rate [rad/s]"]
+    }
+
+    fn channels(&self) -> Vec<f64> {
+        vec![self.roll_cmd_rad, self.pitch_cmd_rad, self.yaw_rate_rps]
+    }
+
+    fn from_channels(values: &[f64]) -> Self {
+        assert!(values.len() == 3, "AttitudeQuadControl expects 3 channels");
+        Self {
+            roll_cmd_rad: values[0],
+            pitch_cmd_rad: values[1],
+            yaw_rate_rps: values[2],
+        }
+    }
+}
+
+/// Limit tilt to keep the altitude-holding thrust finite.
+const MAX_TILT_RAD: f64 = std::f64::consts::FRAC_PI_2 * 0.95;
+
+impl Dynamics for AttitudeQuadcopter {
+    type State = AttitudeQuadState;
+    type Control = AttitudeQuadControl;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        self.input_to_control_checked(input).0
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        let control = AttitudeQuadControl {
+            roll_cmd_rad: input.roll_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD),
+            pitch_cmd_rad: input.pitch_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD),
+            yaw_rate_rps: input.yaw_rate_rps,
+        };
+        let saturation = Saturation {
+            roll: control.roll_cmd_rad != input.roll_rad,
+            pitch: control.pitch_cmd_rad != input.pitch_rad,
+            ..Saturation::default()
+        };
+        (control, saturation)
+    }
+
+    fn derivative(&self, _t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        let roll = state.roll_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD);
+        let pitch = state.pitch_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD);
+
+        // Thrust along body -z, sized so its vertical NED component cancels gravity
+        let thrust = GRAVITY / (roll.cos() * pitch.cos());
+        let r = rotation_ned_from_body_3d(roll, pitch, state.yaw_rad);
+        let a_ned = r * Vector3::new(0.0, 0.0, -thrust);
+
+        // First-order attitude response to the stick
+        let roll_dot = (control.roll_cmd_rad - state.roll_rad) / self.attitude_tau_s;
+        let pitch_dot = (control.pitch_cmd_rad - state.pitch_rad) / self.attitude_tau_s;
+
+        AttitudeQuadState {
+            north_m: state.v_north_mps,                           // north_dot
+            east_m: state.v_east_mps,                             // east_dot
+            v_north_mps: a_ned.x - self.drag * state.v_north_mps, // v_north_dot
+            v_east_mps: a_ned.y - self.drag * state.v_east_mps,   // v_east_dot
+            roll_rad: roll_dot,                                   // roll_dot
+            pitch_rad: pitch_dot,                                 // pitch_dot
+            yaw_rad: control.yaw_rate_rps,                        // yaw_dot
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::Rk4,
+    };
+
+    fn model() -> AttitudeQuadcopter {
+        AttitudeQuadcopter::new(0.1, 0.2)
+    }
+
+    #[test]
+    fn level_attitude_reduces_to_yaw_only_model() {
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.4,
+            thrust_norm: 0.5,
+        };
+        let start = AttitudeQuadState {
+            v_north_mps: 3.0,
+            v_east_mps: -1.0,
+            yaw_rad: 0.3,
+            ..AttitudeQuadState::zero()
+        };
+
+        let full = predict(&input, start, &model(), &mut Rk4, 0.0, 5.0, 500);
+        let yaw_only = predict(
+            &input,
+            SimpleQuadState::new(0.0, 0.0, 3.0, -1.0, 0.3),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            5.0,
+            500,
+        );
+
+        for (a, b) in full.states.iter().zip(&yaw_only.states) {
+            assert!((a.north_m - b.north_m).abs() < 1e-12);
+            assert!((a.east_m - b.east_m).abs() < 1e-12);
+            assert!((a.v_north_mps - b.v_north_mps).abs() < 1e-12);
+            assert!((a.v_east_mps - b.v_east_mps).abs() < 1e-12);
+            assert!((a.yaw_rad - b.yaw_rad).abs() < 1e-12);
+            assert_eq!((a.roll_rad, a.pitch_rad), (0.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn settled_single_axis_tilt_matches_small_angle_model() {
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 25f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let yaw = 0.8;
+        let state = AttitudeQuadState {
+            pitch_rad: input.pitch_rad,
+            yaw_rad: yaw,
+            ..AttitudeQuadState::zero()
+        };
+        let dx = model().derivative(0.0, &state, &model().input_to_control(&input));
+
+        let simple = SimpleQuadcopter::new(0.1);
+        let dx_simple = simple.derivative(
+            0.0,
+            &SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, yaw),
+            &simple.input_to_control(&input),
+        );
+        assert!((dx.v_north_mps - dx_simple.v_north_mps).abs() < 1e-12);
+        assert!((dx.v_east_mps - dx_simple.v_east_mps).abs() < 1e-12);
+        assert_eq!(dx.pitch_rad, 0.0);
+    }
+
+    #[test]
+    fn attitude_lags_the_stick() {
+        let input = DroneInput {
+            roll_rad: 0.2,
+            pitch_rad: -0.1,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let tau = model().attitude_tau_s;
+        let p = predict(
+            &input,
+            AttitudeQuadState::zero(),
+            &model(),
+            &mut Rk4,
+            0.0,
+            tau,
+            200,
+        );
+
+        let end = p.final_state();
+        let expected = 1.0 - (-1.0f64).exp();
+        assert!((end.roll_rad / 0.2 - expected).abs() < 1e-9);
+        assert!((end.pitch_rad / -0.1 - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "attitude_tau_s must be > 0")]
+    fn new_rejects_a_non_positive_time_constant() {
+        AttitudeQuadcopter::new(0.1, 0.0);
+    }
+}
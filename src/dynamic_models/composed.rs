@@ -0,0 +1,190 @@
+use crate::{
+    traits::{DisturbanceModel, Dynamics, LinearizableDisturbance, LinearizableDynamics},
+    types::{DroneInput, IntegrableState, Saturation},
+};
+
+/// Base model plus an additive disturbance: `f(t, x, u) = f_base + f_disturbance`.
+/// Nest to stack several effects, e.g. `Composed::new(Composed::new(quad, wind), boost)`.
+#[derive(Debug, Clone)]
+pub struct Composed<M, D> {
+    pub base: M,
+    pub disturbance: D,
+}
+
+impl<M, D> Composed<M, D> {
+    pub fn new(base: M, disturbance: D) -> Self {
+        Self { base, disturbance }
+    }
+}
+
+impl<M, D> Dynamics for Composed<M, D>
+where
+    M: Dynamics,
+    D: DisturbanceModel<M>,
+{
+    type State = M::State;
+    type Control = M::Control;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        self.base.input_to_control(input)
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        self.base.input_to_control_checked(input)
+    }
+
+    fn derivative(&self, t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        let mut derivative = self.base.derivative(t, state, control);
+        let extra = self.disturbance.extra_derivative(t, state, control);
+        derivative.add_scaled_assign(&extra, 1.0);
+        derivative
+    }
+
+    fn validate_state(&self, state: &Self::State) {
+        self.base.validate_state(state);
+    }
+
+    fn project_state(&self, state: &mut Self::State) {
+        self.base.project_state(state);
+    }
+}
+
+impl<M, D> LinearizableDynamics for Composed<M, D>
+where
+    M: LinearizableDynamics,
+    D: LinearizableDisturbance<M>,
+{
+    fn jacobian(
+        &self,
+        t: f64,
+        state: &Self::State,
+        control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64> {
+        self.base.jacobian(t, state, control) + self.disturbance.jacobian(t, state, control)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_models::{SimpleQuadControl, SimpleQuadState, SimpleQuadcopter};
+
+    /// Steady wind seen through the linear drag term: `a = drag * wind`.
+    struct SteadyWind {
+        drag: f64,
+        north_mps: f64,
+        east_mps: f64,
+    }
+
+    impl<M> DisturbanceModel<M> for SteadyWind
+    where
+        M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+    {
+        fn extra_derivative(
+            &self,
+            _t: f64,
+            _state: &SimpleQuadState,
+            _control: &SimpleQuadControl,
+        ) -> SimpleQuadState {
+            SimpleQuadState::new(
+                0.0,
+                0.0,
+                self.drag * self.north_mps,
+                self.drag * self.east_mps,
+                0.0,
+            )
+        }
+    }
+
+    impl<M> LinearizableDisturbance<M> for SteadyWind
+    where
+        M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+    {
+        fn jacobian(
+            &self,
+            _t: f64,
+            _state: &SimpleQuadState,
+            _control: &SimpleQuadControl,
+        ) -> nalgebra::DMatrix<f64> {
+            nalgebra::DMatrix::zeros(7, 7)
+        }
+    }
+
+    /// Extra forward push proportional to ground speed, standing in for ground effect.
+    struct SpeedBoost {
+        gain: f64,
+    }
+
+    impl<M> DisturbanceModel<M> for SpeedBoost
+    where
+        M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+    {
+        fn extra_derivative(
+            &self,
+            _t: f64,
+            state: &SimpleQuadState,
+            _control: &SimpleQuadControl,
+        ) -> SimpleQuadState {
+            SimpleQuadState::new(
+                0.0,
+                0.0,
+                self.gain * state.v_north_mps,
+                self.gain * state.v_east_mps,
+                0.0,
+            )
+        }
+    }
+
+    impl<M> LinearizableDisturbance<M> for SpeedBoost
+    where
+        M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+    {
+        fn jacobian(
+            &self,
+            _t: f64,
+            _state: &SimpleQuadState,
+            _control: &SimpleQuadControl,
+        ) -> nalgebra::DMatrix<f64> {
+            let mut j = nalgebra::DMatrix::zeros(7, 7);
+            j[(2, 2)] = self.gain;
+            j[(3, 3)] = self.gain;
+            j
+        }
+    }
+
+    #[test]
+    fn stacked_disturbances_sum() {
+        let drag = 0.3;
+        let wind = || SteadyWind {
+            drag,
+            north_mps: 2.0,
+            east_mps: -1.0,
+        };
+        let boost = || SpeedBoost { gain: 0.05 };
+
+        let quad = || SimpleQuadcopter::new(drag);
+        let model = Composed::new(Composed::new(quad(), wind()), boost());
+
+        let state = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.4);
+        let control = quad().input_to_control(&DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        });
+
+        let base = quad().derivative(0.0, &state, &control);
+        let w =
+            DisturbanceModel::<SimpleQuadcopter>::extra_derivative(&wind(), 0.0, &state, &control);
+        let b =
+            DisturbanceModel::<SimpleQuadcopter>::extra_derivative(&boost(), 0.0, &state, &control);
+        let expected = base.add_scaled(&w, 1.0).add_scaled(&b, 1.0);
+        assert_eq!(model.derivative(0.0, &state, &control), expected);
+
+        let j = model.jacobian(0.0, &state, &control);
+        let mut expected_j = quad().jacobian(0.0, &state, &control);
+        expected_j[(2, 2)] += 0.05;
+        expected_j[(3, 3)] += 0.05;
+        assert!((j - expected_j).abs().max() < 1e-15);
+    }
+}
@@ -0,0 +1,323 @@
+use nalgebra::Vector2;
+
+use crate::{
+    math::{rotation_ned_from_body, rotation_ned_from_body_dyaw},
+    traits::{Dynamics, GRAVITY, LinearizableDynamics},
+    types::{
+        ControlChannels, DroneInput, GroundVelocity, Heading, IntegrableState, Position2D,
+        Saturation, StateVector,
+    },
+};
+
+/// Bank-to-turn kinematic model of a fixed-wing aircraft. The roll stick banks the
+/// aircraft into a coordinated turn at `g tan(bank) / speed`; the yaw-rate stick is
+/// unused. Pitch trades speed along the flight path (nose down speeds up) while an
+/// autothrottle pulls the speed back toward cruise, so it stays near-constant. The
+/// climb itself is not tracked: the state is planar.
+///
+/// Nose-up pitch is limited so the autothrottle settles no slower than
+/// [`MIN_SETTLED_SPEED_FRACTION`](Self::MIN_SETTLED_SPEED_FRACTION) of cruise; from a
+/// positive speed the aircraft then never stops, where the turn rate is undefined.
+#[derive(Debug, Clone)]
+pub struct CoordinatedTurn {
+    /// Speed the autothrottle holds [m/s].
+    pub cruise_speed_mps: f64,
+    /// Bandwidth of the autothrottle [1/s].
+    pub speed_hold_per_s: f64,
+    pub max_bank_rad: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinatedTurnState {
+    pub north_m: f64,
+    pub east_m: f64,
+    pub heading_rad: f64,
+    pub speed_mps: f64,
+}
+
+impl CoordinatedTurnState {
+    pub fn new(north_m: f64, east_m: f64, heading_rad: f64, speed_mps: f64) -> Self {
+        Self {
+            north_m,
+            east_m,
+            heading_rad,
+            speed_mps,
+        }
+    }
+
+    pub fn ensure_finite(&self) {
+        if let Some(field) = self.non_finite_field() {
+            panic!("{field} must be finite");
+        }
+    }
+}
+
+impl IntegrableState for CoordinatedTurnState {
+    fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+        Self {
+            north_m: self.north_m + scale * derivative.north_m,
+            east_m: self.east_m + scale * derivative.east_m,
+            heading_rad: self.heading_rad + scale * derivative.heading_rad,
+            speed_mps: self.speed_mps + scale * derivative.speed_mps,
+        }
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.north_m += scale * derivative.north_m;
+        self.east_m += scale * derivative.east_m;
+        self.heading_rad += scale * derivative.heading_rad;
+        self.speed_mps += scale * derivative.speed_mps;
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("north_m", self.north_m),
+            ("east_m", self.east_m),
+            ("heading_rad", self.heading_rad),
+            ("speed_mps", self.speed_mps),
+        ]
+        .into_iter()
+        .find(|(_, v)| !v.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+impl Position2D for CoordinatedTurnState {
+    fn position(&self) -> (f64, f64) {
+        (self.north_m, self.east_m)
+    }
+}
+
+impl GroundVelocity for CoordinatedTurnState {
+    fn ground_velocity(&self) -> (f64, f64) {
+        let v = rotation_ned_from_body(self.heading_rad) * Vector2::new(self.speed_mps, 0.0);
+        (v.x, v.y)
+    }
+}
+
+impl Heading for CoordinatedTurnState {
+    fn yaw_rad(&self) -> f64 {
+        self.heading_rad
+    }
+
+    fn with_yaw(&self, yaw_rad: f64) -> Self {
+        Self {
+            heading_rad: yaw_rad,
+            ..*self
+        }
+    }
+}
+
+impl StateVector for CoordinatedTurnState {
+    fn to_dvector(&self) -> nalgebra::DVector<f64> {
+        nalgebra::DVector::from_vec(vec![
+            self.north_m,
+            self.east_m,
+            self.heading_rad,
+            self.speed_mps,
+        ])
+    }
+
+    fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
+        assert!(
+            v.len() == 4,
+            "CoordinatedTurnState expects 4 elements (north, east, heading, speed)"
+        );
+        Self {
+            north_m: v[0],
+            east_m: v[1],
+            heading_rad: v[2],
+            speed_mps: v[3],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinatedTurnControl {
+    pub bank_rad: f64,
+    /// Flight-path pitch, positive nose down.
+    pub pitch_rad: f64,
+}
+
+impl ControlChannels for CoordinatedTurnControl {
+    fn channel_names() -> &'static [&'static str] {
+        &["bank [rad]", "pitch [rad]"]
+    }
+
+    fn channels(&self) -> Vec<f64> {
+        vec![self.bank_rad, self.pitch_rad]
+    }
+
+    fn from_channels(values: &[f64]) -> Self {
+        assert!(
+            values.len() == 2,
+            "CoordinatedTurnControl expects 2 channels"
+        );
+        Self {
+            bank_rad: values[0],
+            pitch_rad: values[1],
+        }
+    }
+}
+
+impl CoordinatedTurn {
+    /// Slowest settled speed a pitch command may ask for, as a fraction of cruise.
+    pub const MIN_SETTLED_SPEED_FRACTION: f64 = 0.5;
+
+    /// Steepest nose-up pitch [rad] (negative) whose settled speed
+    /// `cruise + g sin(pitch) / speed_hold` keeps the minimum fraction of cruise.
+    pub fn max_nose_up_rad(&self) -> f64 {
+        let margin = (1.0 - Self::MIN_SETTLED_SPEED_FRACTION) * self.cruise_speed_mps;
+        -(self.speed_hold_per_s * margin / GRAVITY).min(1.0).asin()
+    }
+}
+
+impl Dynamics for CoordinatedTurn {
+    type State = CoordinatedTurnState;
+    type Control = CoordinatedTurnControl;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        self.input_to_control_checked(input).0
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        let bank_rad = input.roll_rad.clamp(-self.max_bank_rad, self.max_bank_rad);
+        let pitch_rad = input.pitch_rad.max(self.max_nose_up_rad());
+        let control = CoordinatedTurnControl {
+            bank_rad,
+            pitch_rad,
+        };
+        let saturation = Saturation {
+            roll: bank_rad != input.roll_rad,
+            pitch: pitch_rad != input.pitch_rad,
+            ..Saturation::default()
+        };
+        (control, saturation)
+    }
+
+    fn derivative(&self, _t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        assert!(
+            state.speed_mps > 0.0,
+            "speed must be > 0 for a coordinated turn"
+        );
+
+        let v_ned = rotation_ned_from_body(state.heading_rad) * Vector2::new(state.speed_mps, 0.0);
+
+        CoordinatedTurnState {
+            north_m: v_ned.x,
+            east_m: v_ned.y,
+            heading_rad: GRAVITY * control.bank_rad.tan() / state.speed_mps,
+            speed_mps: GRAVITY * control.pitch_rad.sin()
+                - self.speed_hold_per_s * (state.speed_mps - self.cruise_speed_mps),
+        }
+    }
+}
+
+impl LinearizableDynamics for CoordinatedTurn {
+    fn jacobian(
+        &self,
+        _t: f64,
+        state: &Self::State,
+        control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64> {
+        let dv_dh =
+            rotation_ned_from_body_dyaw(state.heading_rad) * Vector2::new(state.speed_mps, 0.0);
+        let dv_ds = rotation_ned_from_body(state.heading_rad) * Vector2::new(1.0, 0.0);
+        let dturn_ds = -GRAVITY * control.bank_rad.tan() / (state.speed_mps * state.speed_mps);
+
+        let mut j = nalgebra::DMatrix::zeros(4, 4);
+        // Rows: north_dot, east_dot, heading_dot, speed_dot
+        j[(0, 2)] = dv_dh.x;
+        j[(0, 3)] = dv_ds.x;
+        j[(1, 2)] = dv_dh.y;
+        j[(1, 3)] = dv_ds.y;
+        j[(2, 3)] = dturn_ds;
+        j[(3, 3)] = -self.speed_hold_per_s;
+        j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dynamic_models::assert_jacobian_matches_fd, predict::predict, solvers::Rk4};
+
+    fn model() -> CoordinatedTurn {
+        CoordinatedTurn {
+            cruise_speed_mps: 20.0,
+            speed_hold_per_s: 0.5,
+            max_bank_rad: 45f64.to_radians(),
+        }
+    }
+
+    #[test]
+    fn banked_turn_traces_the_coordinated_turn_radius() {
+        let bank = 30f64.to_radians();
+        let input = DroneInput::try_new(bank, 0.0, 0.0).unwrap();
+        let speed = model().cruise_speed_mps;
+        let radius = speed * speed / (GRAVITY * bank.tan());
+        let period = std::f64::consts::TAU * radius / speed;
+
+        let prediction = predict(
+            &input,
+            CoordinatedTurnState::new(0.0, 0.0, 0.0, speed),
+            &model(),
+            &mut Rk4,
+            0.0,
+            period,
+            2000,
+        );
+
+        // Heading North and banking right puts the center due East
+        for s in &prediction.states {
+            let r = s.north_m.hypot(s.east_m - radius);
+            assert!((r - radius).abs() < 1e-6, "radius {r} vs {radius}");
+            assert_eq!(s.speed_mps, speed);
+        }
+        let end = prediction.final_state();
+        assert!(end.north_m.hypot(end.east_m) < 1e-6);
+    }
+
+    #[test]
+    fn nose_down_settles_above_cruise_speed() {
+        let pitch = 0.1_f64;
+        let input = DroneInput::try_new(0.0, pitch, 0.0).unwrap();
+        let start = CoordinatedTurnState::new(0.0, 0.0, 0.0, model().cruise_speed_mps);
+        let prediction = predict(&input, start, &model(), &mut Rk4, 0.0, 30.0, 300);
+
+        let expected = model().cruise_speed_mps + GRAVITY * pitch.sin() / model().speed_hold_per_s;
+        assert!((prediction.final_state().speed_mps - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn steep_nose_up_is_limited_to_keep_flying() {
+        let slow = CoordinatedTurn {
+            speed_hold_per_s: 0.2,
+            ..model()
+        };
+        let input = DroneInput::try_new(0.0, -80f64.to_radians(), 0.0).unwrap();
+        let (control, saturation) = slow.input_to_control_checked(&input);
+        assert!(saturation.pitch && !saturation.roll);
+        assert_eq!(control.pitch_rad, slow.max_nose_up_rad());
+
+        let start = CoordinatedTurnState::new(0.0, 0.0, 0.0, slow.cruise_speed_mps);
+        let prediction = predict(&input, start, &slow, &mut Rk4, 0.0, 60.0, 600);
+        let floor = CoordinatedTurn::MIN_SETTLED_SPEED_FRACTION * slow.cruise_speed_mps;
+        assert!((prediction.final_state().speed_mps - floor).abs() < 1e-3);
+
+        // Within the limit the pitch passes through
+        let (_, saturation) =
+            slow.input_to_control_checked(&DroneInput::try_new(0.0, -0.2, 0.0).unwrap());
+        assert!(!saturation.any());
+    }
+
+    #[test]
+    fn jacobian_matches_finite_difference() {
+        let state = CoordinatedTurnState::new(1.0, 2.0, 0.7, 18.0);
+        let control = CoordinatedTurnControl {
+            bank_rad: 0.3,
+            pitch_rad: -0.05,
+        };
+        assert_jacobian_matches_fd(&model(), &state, &control, 1e-7);
+    }
+}
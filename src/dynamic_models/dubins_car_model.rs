@@ -0,0 +1,254 @@
+use nalgebra::Vector2;
+
+use crate::{
+    math::{rotation_ned_from_body, rotation_ned_from_body_dyaw},
+    traits::{Dynamics, LinearizableDynamics},
+    types::{
+        ControlChannels, DroneInput, Heading, IntegrableState, Position2D, Saturation, StateVector,
+    },
+};
+
+/// Constant-speed, turn-rate-limited planar kinematic model (Dubins car).
+/// Heading = 0 faces North, positive clockwise. Only the yaw-rate stick is used.
+#[derive(Debug, Clone)]
+pub struct DubinsCar {
+    pub speed_mps: f64,
+    pub max_yaw_rate_rps: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DubinsState {
+    pub north_m: f64,
+    pub east_m: f64,
+    pub heading_rad: f64,
+}
+
+impl DubinsState {
+    pub fn new(north_m: f64, east_m: f64, heading_rad: f64) -> Self {
+        Self {
+            north_m,
+            east_m,
+            heading_rad,
+        }
+    }
+
+    pub fn ensure_finite(&self) {
+        if let Some(field) = self.non_finite_field() {
+            panic!("{field} must be finite");
+        }
+    }
+}
+
+impl IntegrableState for DubinsState {
+    fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+        Self {
+            north_m: self.north_m + scale * derivative.north_m,
+            east_m: self.east_m + scale * derivative.east_m,
+            heading_rad: self.heading_rad + scale * derivative.heading_rad,
+        }
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.north_m += scale * derivative.north_m;
+        self.east_m += scale * derivative.east_m;
+        self.heading_rad += scale * derivative.heading_rad;
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("north_m", self.north_m),
+            ("east_m", self.east_m),
+            ("heading_rad", self.heading_rad),
+        ]
+        .into_iter()
+        .find(|(_, v)| !v.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+impl Position2D for DubinsState {
+    fn position(&self) -> (f64, f64) {
+        (self.north_m, self.east_m)
+    }
+}
+
+impl Heading for DubinsState {
+    fn yaw_rad(&self) -> f64 {
+        self.heading_rad
+    }
+
+    fn with_yaw(&self, yaw_rad: f64) -> Self {
+        Self {
+            heading_rad: yaw_rad,
+            ..*self
+        }
+    }
+}
+
+impl StateVector for DubinsState {
+    fn to_dvector(&self) -> nalgebra::DVector<f64> {
+        nalgebra::DVector::from_vec(vec![self.north_m, self.east_m, self.heading_rad])
+    }
+
+    fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
+        assert!(
+            v.len() == 3,
+            "DubinsState expects 3 elements (north, east, heading)"
+        );
+        Self {
+            north_m: v[0],
+            east_m: v[1],
+            heading_rad: v[2],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DubinsControl {
+    pub yaw_rate_rps: f64,
+}
+
+impl ControlChannels for DubinsControl {
+    fn channel_names() -> &'static [&'static str] {
+        &["yaw_rate [rad/s]"]
+    }
+
+    fn channels(&self) -> Vec<f64> {
+        vec![self.yaw_rate_rps]
+    }
+
+    fn from_channels(values: &[f64]) -> Self {
+        assert!(values.len() == 1, "DubinsControl expects 1 channel");
+        Self {
+            yaw_rate_rps: values[0],
+        }
+    }
+}
+
+impl Dynamics for DubinsCar {
+    type State = DubinsState;
+    type Control = DubinsControl;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        self.input_to_control_checked(input).0
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        let yaw_rate_rps = input
+            .yaw_rate_rps
+            .clamp(-self.max_yaw_rate_rps, self.max_yaw_rate_rps);
+        let saturation = Saturation {
+            yaw_rate: yaw_rate_rps != input.yaw_rate_rps,
+            ..Saturation::default()
+        };
+        (DubinsControl { yaw_rate_rps }, saturation)
+    }
+
+    fn derivative(&self, _t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        state.ensure_finite();
+
+        // Body velocity is purely forward
+        let v_ned = rotation_ned_from_body(state.heading_rad) * Vector2::new(self.speed_mps, 0.0);
+
+        DubinsState {
+            north_m: v_ned.x,                  // north_dot
+            east_m: v_ned.y,                   // east_dot
+            heading_rad: control.yaw_rate_rps, // heading_dot
+        }
+    }
+}
+
+impl LinearizableDynamics for DubinsCar {
+    fn jacobian(
+        &self,
+        _t: f64,
+        state: &Self::State,
+        _control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64> {
+        // Partials of the velocity components w.r.t heading
+        let dv_dh =
+            rotation_ned_from_body_dyaw(state.heading_rad) * Vector2::new(self.speed_mps, 0.0);
+        let (dn_dh, de_dh) = (dv_dh.x, dv_dh.y);
+
+        nalgebra::DMatrix::from_row_slice(
+            3,
+            3,
+            &[
+                0.0, 0.0, dn_dh, // d(north_dot)/d(state)
+                0.0, 0.0, de_dh, // d(east_dot)/d(state)
+                0.0, 0.0, 0.0, // d(heading_dot)/d(state)
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dynamic_models::assert_jacobian_matches_fd, predict::predict, solvers::Rk4};
+
+    fn model() -> DubinsCar {
+        DubinsCar {
+            speed_mps: 10.0,
+            max_yaw_rate_rps: 0.5,
+        }
+    }
+
+    #[test]
+    fn yaw_rate_is_clamped_to_max_rate() {
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: -3.0,
+            thrust_norm: 0.5,
+        };
+        assert_eq!(model().input_to_control(&input).yaw_rate_rps, -0.5);
+    }
+
+    #[test]
+    fn max_rate_turn_traces_minimum_radius_circle() {
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 2.0,
+            thrust_norm: 0.5,
+        };
+        let radius = model().speed_mps / model().max_yaw_rate_rps;
+        let period = std::f64::consts::TAU / model().max_yaw_rate_rps;
+
+        let prediction = predict(
+            &input,
+            DubinsState::new(0.0, 0.0, 0.0),
+            &model(),
+            &mut Rk4,
+            0.0,
+            period,
+            2000,
+        );
+
+        // Heading North and turning clockwise puts the center due East
+        for s in &prediction.states {
+            let r = s.north_m.hypot(s.east_m - radius);
+            assert!((r - radius).abs() < 1e-6);
+        }
+        let end = prediction.final_state();
+        assert!(end.north_m.hypot(end.east_m) < 1e-6);
+    }
+
+    #[test]
+    fn jacobian_matches_finite_difference() {
+        let state = DubinsState::new(1.0, 2.0, 0.7);
+        let control = DubinsControl { yaw_rate_rps: 0.1 };
+        assert_jacobian_matches_fd(&model(), &state, &control, 1e-8);
+
+        // The default dfdx differences the same way, row by row
+        let j = model().jacobian(0.0, &state, &control);
+        let rows = model().dfdx(&state, &control);
+        assert_eq!(rows.len(), 3);
+        for (i, row) in rows.iter().enumerate() {
+            for (k, v) in row.iter().enumerate() {
+                assert!((v - j[(i, k)]).abs() < 1e-8);
+            }
+        }
+    }
+}
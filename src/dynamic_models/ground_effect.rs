@@ -0,0 +1,123 @@
+use nalgebra::DMatrix;
+
+use crate::{
+    dynamic_models::{SimpleQuadControl, SimpleQuadState},
+    traits::{DisturbanceModel, Dynamics, GRAVITY, LinearizableDisturbance},
+};
+
+/// Ground effect for the 3D [`SimpleQuadcopter`](crate::dynamic_models::SimpleQuadcopter):
+/// near the ground the rotors' thrust is augmented by a factor
+/// `1 + gain * exp(-h / decay_height_m)`, with `h` the height above ground. Opt in by
+/// composing it onto the base model with [`Composed`](crate::dynamic_models::Composed).
+#[derive(Debug, Clone)]
+pub struct GroundEffect {
+    /// NED `down_m` coordinate of the ground surface [m].
+    pub ground_down_m: f64,
+    /// Fractional thrust increase at zero height.
+    pub gain: f64,
+    /// Height [m] over which the augmentation decays by a factor `e`, typically of the
+    /// order of the rotor radius.
+    pub decay_height_m: f64,
+}
+
+impl GroundEffect {
+    pub fn new(ground_down_m: f64, gain: f64, decay_height_m: f64) -> Self {
+        assert!(gain >= 0.0, "gain must be >= 0");
+        assert!(decay_height_m > 0.0, "decay_height_m must be > 0");
+        Self {
+            ground_down_m,
+            gain,
+            decay_height_m,
+        }
+    }
+
+    /// Height above ground [m], clamped at zero below the surface.
+    fn height(&self, state: &SimpleQuadState) -> f64 {
+        (self.ground_down_m - state.down_m).max(0.0)
+    }
+
+    /// Extra upward acceleration per unit of rotor thrust (`gain * exp(-h / H)`).
+    fn augmentation(&self, state: &SimpleQuadState) -> f64 {
+        self.gain * (-self.height(state) / self.decay_height_m).exp()
+    }
+}
+
+/// Specific rotor thrust [m/s², upward]: gravity plus the commanded climb.
+fn thrust_mps2(control: &SimpleQuadControl) -> f64 {
+    GRAVITY - control.az_down_mps2
+}
+
+impl<M> DisturbanceModel<M> for GroundEffect
+where
+    M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+{
+    fn extra_derivative(
+        &self,
+        _t: f64,
+        state: &SimpleQuadState,
+        control: &SimpleQuadControl,
+    ) -> SimpleQuadState {
+        SimpleQuadState::zero().with_vertical(0.0, -self.augmentation(state) * thrust_mps2(control))
+    }
+}
+
+impl<M> LinearizableDisturbance<M> for GroundEffect
+where
+    M: Dynamics<State = SimpleQuadState, Control = SimpleQuadControl>,
+{
+    fn jacobian(
+        &self,
+        _t: f64,
+        state: &SimpleQuadState,
+        control: &SimpleQuadControl,
+    ) -> DMatrix<f64> {
+        let mut j = DMatrix::zeros(7, 7);
+        // h = ground - down, so d(exp(-h/H))/d(down) = exp(-h/H) / H above ground
+        if state.down_m < self.ground_down_m {
+            j[(6, 5)] = -self.augmentation(state) * thrust_mps2(control) / self.decay_height_m;
+        }
+        j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{Composed, SimpleQuadcopter, assert_jacobian_matches_fd},
+        types::DroneInput,
+    };
+
+    #[test]
+    fn same_throttle_climbs_harder_near_the_ground() {
+        let model = Composed::new(SimpleQuadcopter::new(0.1), GroundEffect::new(0.0, 0.3, 0.5));
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.6,
+        });
+
+        let low = SimpleQuadState::zero().with_vertical(-0.2, 0.0);
+        let high = SimpleQuadState::zero().with_vertical(-20.0, 0.0);
+        let climb = |s: &SimpleQuadState| -model.derivative(0.0, s, &control).v_down_mps;
+
+        let free = -model.base.derivative(0.0, &high, &control).v_down_mps;
+        assert!(climb(&low) > climb(&high));
+        assert!((climb(&high) - free).abs() < 1e-10);
+    }
+
+    #[test]
+    fn jacobian_matches_central_differences() {
+        let model = Composed::new(SimpleQuadcopter::new(0.2), GroundEffect::new(1.0, 0.3, 0.5));
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.7,
+        });
+        let state = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.4).with_vertical(0.6, -0.5);
+
+        assert_jacobian_matches_fd(&model, &state, &control, 1e-6);
+    }
+}
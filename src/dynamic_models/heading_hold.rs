@@ -0,0 +1,180 @@
+use nalgebra::DMatrix;
+
+use crate::{
+    dynamic_models::{SimpleQuadControl, SimpleQuadState, SimpleQuadcopter},
+    math::wrap_pi,
+    traits::{Dynamics, LinearizableDynamics},
+    types::{ControlChannels, DroneInput, Saturation},
+};
+
+/// [`SimpleQuadcopter`] flown through a heading-hold autopilot: the yaw stick channel
+/// carries a *target heading* [rad] instead of a rate, and a proportional loop commands
+/// `yaw_rate = gain * wrap(target - yaw)`, taking the short way round. Under a
+/// constant input the heading settles exponentially on the target with time constant
+/// `1 / gain` instead of rotating forever.
+#[derive(Debug, Clone)]
+pub struct HeadingHold {
+    pub base: SimpleQuadcopter,
+    /// Proportional gain [1/s] from heading error to yaw rate.
+    pub gain_per_s: f64,
+}
+
+impl HeadingHold {
+    pub fn new(base: SimpleQuadcopter, gain_per_s: f64) -> Self {
+        assert!(
+            gain_per_s.is_finite() && gain_per_s > 0.0,
+            "gain_per_s must be finite and > 0"
+        );
+        Self { base, gain_per_s }
+    }
+}
+
+/// Base quadcopter control with the heading target; its `yaw_rate_rps` is replaced by
+/// the autopilot's command at every evaluation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadingHoldControl {
+    pub quad: SimpleQuadControl,
+    pub target_heading_rad: f64,
+}
+
+impl ControlChannels for HeadingHoldControl {
+    fn channel_names() -> &'static [&'static str] {
+        &[
+            "ax_body [m/s²]",
+            "ay_body [m/s²]",
+            "yaw_rate [rad/s]",
+            "az_down [m/s²]",
+            "target_heading [rad]",
+        ]
+    }
+
+    fn channels(&self) -> Vec<f64> {
+        let mut values = self.quad.channels();
+        values.push(self.target_heading_rad);
+        values
+    }
+
+    fn from_channels(values: &[f64]) -> Self {
+        assert!(values.len() == 5, "HeadingHoldControl expects 5 channels");
+        Self {
+            quad: SimpleQuadControl::from_channels(&values[..4]),
+            target_heading_rad: values[4],
+        }
+    }
+}
+
+impl HeadingHold {
+    fn quad_control(
+        &self,
+        state: &SimpleQuadState,
+        control: &HeadingHoldControl,
+    ) -> SimpleQuadControl {
+        SimpleQuadControl {
+            yaw_rate_rps: self.gain_per_s * wrap_pi(control.target_heading_rad - state.yaw_rad),
+            ..control.quad
+        }
+    }
+}
+
+impl Dynamics for HeadingHold {
+    type State = SimpleQuadState;
+    type Control = HeadingHoldControl;
+
+    /// `input.yaw_rate_rps` is read as the target heading [rad], clockwise from North.
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        self.input_to_control_checked(input).0
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        let (quad, saturation) = self.base.input_to_control_checked(input);
+        let control = HeadingHoldControl {
+            quad,
+            target_heading_rad: input.yaw_rate_rps,
+        };
+        (control, saturation)
+    }
+
+    fn derivative(&self, t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        self.base
+            .derivative(t, state, &self.quad_control(state, control))
+    }
+
+    fn validate_state(&self, state: &Self::State) {
+        self.base.validate_state(state);
+    }
+
+    fn project_state(&self, state: &mut Self::State) {
+        self.base.project_state(state);
+    }
+}
+
+impl LinearizableDynamics for HeadingHold {
+    fn jacobian(&self, t: f64, state: &Self::State, control: &Self::Control) -> DMatrix<f64> {
+        // The loop adds d(yaw_dot)/d(yaw) = -gain; the rest is the base model's
+        let mut j = self
+            .base
+            .jacobian(t, state, &self.quad_control(state, control));
+        j[(4, 4)] = -self.gain_per_s;
+        j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dynamic_models::assert_jacobian_matches_fd, predict::predict, solvers::Rk4};
+
+    fn hold() -> HeadingHold {
+        HeadingHold::new(SimpleQuadcopter::new(0.2), 2.0)
+    }
+
+    fn target(heading_rad: f64) -> DroneInput {
+        DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.1,
+            yaw_rate_rps: heading_rad,
+            thrust_norm: 0.5,
+        }
+    }
+
+    #[test]
+    fn heading_settles_exponentially_on_the_target() {
+        let model = hold();
+        let prediction = predict(
+            &target(1.0),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            5.0,
+            500,
+        );
+
+        for (i, state) in prediction.states.iter().enumerate() {
+            let t = prediction.t_at(i);
+            let expected = 1.0 - (-model.gain_per_s * t).exp();
+            assert!((state.yaw_rad - expected).abs() < 1e-8, "t={t}");
+        }
+        // Settled rather than still rotating
+        assert!((prediction.final_state().yaw_rad - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn turns_the_short_way_across_the_wrap() {
+        let start = SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, 3.0);
+        let prediction = predict(&target(-3.0), start, &hold(), &mut Rk4, 0.0, 5.0, 500);
+        let yaw = prediction.final_state().yaw_rad;
+        // -3 rad is 2π - 3 ≈ 3.28 rad going clockwise
+        assert!((yaw - (std::f64::consts::TAU - 3.0)).abs() < 1e-3, "{yaw}");
+    }
+
+    #[test]
+    fn jacobian_matches_finite_differences() {
+        let model = hold();
+        let control = model.input_to_control(&target(0.5));
+        let state = SimpleQuadState::new(1.0, 2.0, 3.0, -1.0, 0.2).with_vertical(-4.0, 0.5);
+
+        assert_jacobian_matches_fd(&model, &state, &control, 1e-6);
+        assert_eq!(model.jacobian(0.0, &state, &control)[(4, 4)], -2.0);
+    }
+}
@@ -1,3 +1,280 @@
+// Sign conventions shared by every model (NED, body x-forward / y-right / z-down):
+//
+//   quantity               positive means                   check
+//   yaw / heading          clockwise from North, seen above  yaw 90° faces East
+//   yaw_rate_rps stick     turning clockwise                 heading increases
+//   pitch_rad stick        nose down                         accelerates along the heading
+//   roll_rad stick         right wing down                   accelerates to the right
+//   thrust_norm            0.5 holds altitude                higher climbs (v_down < 0)
+//
+// Exceptions: `HeadingHold` reads the yaw channel as a target heading, not a rate, and
+// `CoordinatedTurn` turns with the roll stick (bank-to-turn) and ignores the yaw stick.
+//
+// Horizontal body vectors map to NED through `math::rotation_ned_from_body`; the
+// `conventions` tests below hold every model to this table.
+
+pub mod air_relative_drag;
+pub mod attitude_quadcopter_model;
+pub mod composed;
+pub mod coordinated_turn_model;
+pub mod dubins_car_model;
+pub mod ground_effect;
+pub mod heading_hold;
+pub mod registry;
 pub mod simple_quadcopter_model;
+pub mod yaw_accel_quadcopter_model;
 
+pub use air_relative_drag::AirRelativeDrag;
+pub use attitude_quadcopter_model::{AttitudeQuadControl, AttitudeQuadState, AttitudeQuadcopter};
+pub use composed::Composed;
+pub use coordinated_turn_model::{CoordinatedTurn, CoordinatedTurnControl, CoordinatedTurnState};
+pub use dubins_car_model::{DubinsCar, DubinsControl, DubinsState};
+pub use ground_effect::GroundEffect;
+pub use heading_hold::{HeadingHold, HeadingHoldControl};
+pub use registry::{AnyModel, ModelParams, UnknownModelError, model_from_name};
 pub use simple_quadcopter_model::{SimpleQuadControl, SimpleQuadState, SimpleQuadcopter};
+pub use yaw_accel_quadcopter_model::{YawAccelQuadState, YawAccelQuadcopter};
+
+/// Central-difference check of [`LinearizableDynamics::jacobian`] at `(state, control)`:
+/// every column must match to `tol` in the max norm.
+///
+/// [`LinearizableDynamics::jacobian`]: crate::traits::LinearizableDynamics::jacobian
+#[cfg(test)]
+pub(crate) fn assert_jacobian_matches_fd<M>(
+    model: &M,
+    state: &M::State,
+    control: &M::Control,
+    tol: f64,
+) where
+    M: crate::traits::LinearizableDynamics,
+    M::State: crate::types::StateVector,
+{
+    use crate::types::StateVector;
+
+    let j = model.jacobian(0.0, state, control);
+    let columns = central_differences(&state.to_dvector(), |x| {
+        model
+            .derivative(0.0, &M::State::from_dvector(x), control)
+            .to_dvector()
+    });
+    assert_columns_match(&j, &columns, tol);
+}
+
+/// [`assert_jacobian_matches_fd`] for
+/// [`LinearizableDynamics::control_jacobian`], differencing over the control channels.
+///
+/// [`LinearizableDynamics::control_jacobian`]: crate::traits::LinearizableDynamics::control_jacobian
+#[cfg(test)]
+pub(crate) fn assert_control_jacobian_matches_fd<M>(
+    model: &M,
+    state: &M::State,
+    control: &M::Control,
+    tol: f64,
+) where
+    M: crate::traits::LinearizableDynamics,
+    M::State: crate::types::StateVector,
+    M::Control: crate::types::ControlChannels,
+{
+    use crate::types::{ControlChannels, StateVector};
+
+    let b = model.control_jacobian(0.0, state, control);
+    let u = nalgebra::DVector::from_vec(control.channels());
+    let columns = central_differences(&u, |u| {
+        model
+            .derivative(0.0, state, &M::Control::from_channels(u.as_slice()))
+            .to_dvector()
+    });
+    assert_columns_match(&b, &columns, tol);
+}
+
+/// `∂f/∂x_k` at `x` for every `k`, by central differences.
+#[cfg(test)]
+fn central_differences<F>(x: &nalgebra::DVector<f64>, f: F) -> Vec<nalgebra::DVector<f64>>
+where
+    F: Fn(nalgebra::DVector<f64>) -> nalgebra::DVector<f64>,
+{
+    const H: f64 = 1e-6;
+    (0..x.len())
+        .map(|k| {
+            let (mut hi, mut lo) = (x.clone(), x.clone());
+            hi[k] += H;
+            lo[k] -= H;
+            (f(hi) - f(lo)) / (2.0 * H)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn assert_columns_match(
+    analytic: &nalgebra::DMatrix<f64>,
+    columns: &[nalgebra::DVector<f64>],
+    tol: f64,
+) {
+    assert_eq!(analytic.ncols(), columns.len(), "Jacobian column count");
+    for (k, column) in columns.iter().enumerate() {
+        let error = (column - analytic.column(k)).amax();
+        assert!(error < tol, "column {k} off by {error:e}");
+    }
+}
+
+#[cfg(test)]
+mod conventions {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use crate::{
+        predict::predict,
+        solvers::Rk4,
+        traits::Dynamics,
+        types::{DroneInput, GroundVelocity, Heading, SecondOrderState, StateVector},
+    };
+
+    const HOVER: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    /// From rest facing East, positive pitch must build eastward ground velocity and
+    /// positive roll southward (to the right of the nose).
+    fn assert_tilt_follows_heading<M>(model: &M, facing_east: M::State)
+    where
+        M: Dynamics,
+        M::State: GroundVelocity + Heading,
+    {
+        assert_eq!(facing_east.yaw_rad(), FRAC_PI_2);
+        let run = |input: DroneInput| {
+            let prediction = predict(&input, facing_east.clone(), model, &mut Rk4, 0.0, 1.0, 100);
+            prediction.final_state().ground_velocity()
+        };
+
+        let (vn, ve) = run(DroneInput {
+            pitch_rad: 0.1,
+            ..HOVER
+        });
+        assert!(ve > 0.1 && vn.abs() < 1e-9 * ve, "pitch gave ({vn}, {ve})");
+
+        let (vn, ve) = run(DroneInput {
+            roll_rad: 0.1,
+            ..HOVER
+        });
+        assert!(
+            vn < -0.1 && ve.abs() < 1e-9 * vn.abs(),
+            "roll gave ({vn}, {ve})"
+        );
+    }
+
+    /// Positive yaw-rate stick must increase the heading.
+    fn assert_yaw_rate_turns_clockwise<M>(model: &M, start: M::State)
+    where
+        M: Dynamics,
+        M::State: Heading,
+    {
+        let yaw0 = start.yaw_rad();
+        let input = DroneInput {
+            yaw_rate_rps: 0.3,
+            ..HOVER
+        };
+        let prediction = predict(&input, start, model, &mut Rk4, 0.0, 1.0, 100);
+        let turned = prediction.final_state().yaw_rad() - yaw0;
+        assert!(turned > 0.05, "yaw changed by {turned}");
+    }
+
+    /// The derivative of every declared position must be the state's own paired
+    /// velocity, whatever the input.
+    fn assert_positions_integrate_velocities<M>(model: &M, state: M::State)
+    where
+        M: Dynamics,
+        M::State: SecondOrderState + StateVector,
+    {
+        let (positions, velocities) = (M::State::position_indices(), M::State::velocity_indices());
+        assert_eq!(positions.len(), velocities.len());
+        let x = state.to_dvector();
+        let input = DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: -0.2,
+            yaw_rate_rps: 0.4,
+            thrust_norm: 0.7,
+        };
+        let dx = model
+            .derivative(0.0, &state, &model.input_to_control(&input))
+            .to_dvector();
+        for (&p, &v) in positions.iter().zip(velocities) {
+            assert_eq!(dx[p], x[v], "position {p} vs velocity {v}");
+        }
+    }
+
+    #[test]
+    fn second_order_index_maps_match_the_derivatives() {
+        let simple = SimpleQuadState::new(1.0, 2.0, 3.0, -4.0, 0.5).with_vertical(-1.0, 0.7);
+        assert_positions_integrate_velocities(&SimpleQuadcopter::new(0.2), simple);
+
+        let yaw_accel = YawAccelQuadState {
+            v_north_mps: 1.5,
+            v_east_mps: -0.5,
+            yaw_rate_rps: 0.3,
+            ..YawAccelQuadState::zero()
+        };
+        let model = YawAccelQuadcopter {
+            drag: 0.2,
+            max_yaw_accel_rps2: 2.0,
+            yaw_rate_tau_s: 0.2,
+        };
+        assert_positions_integrate_velocities(&model, yaw_accel);
+    }
+
+    #[test]
+    fn every_model_follows_the_sign_table() {
+        let simple = SimpleQuadcopter::new(0.2);
+        let east = SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, FRAC_PI_2);
+        assert_tilt_follows_heading(&simple, east);
+        assert_yaw_rate_turns_clockwise(&simple, east);
+
+        let attitude = AttitudeQuadcopter::new(0.2, 0.1);
+        let east = AttitudeQuadState {
+            yaw_rad: FRAC_PI_2,
+            ..AttitudeQuadState::zero()
+        };
+        assert_tilt_follows_heading(&attitude, east);
+        assert_yaw_rate_turns_clockwise(&attitude, east);
+
+        let yaw_accel = YawAccelQuadcopter {
+            drag: 0.2,
+            max_yaw_accel_rps2: 2.0,
+            yaw_rate_tau_s: 0.2,
+        };
+        let east = YawAccelQuadState {
+            yaw_rad: FRAC_PI_2,
+            ..YawAccelQuadState::zero()
+        };
+        assert_tilt_follows_heading(&yaw_accel, east);
+        assert_yaw_rate_turns_clockwise(&yaw_accel, east);
+
+        let dubins = DubinsCar {
+            speed_mps: 5.0,
+            max_yaw_rate_rps: 1.0,
+        };
+        assert_yaw_rate_turns_clockwise(&dubins, DubinsState::new(0.0, 0.0, 0.0));
+
+        // Bank-to-turn: right wing down turns clockwise, nose down speeds up
+        let fixed_wing = CoordinatedTurn {
+            cruise_speed_mps: 20.0,
+            speed_hold_per_s: 0.5,
+            max_bank_rad: 0.8,
+        };
+        let start = CoordinatedTurnState::new(0.0, 0.0, FRAC_PI_2, 20.0);
+        let input = DroneInput {
+            roll_rad: 0.3,
+            pitch_rad: 0.1,
+            ..HOVER
+        };
+        let end = *predict(&input, start, &fixed_wing, &mut Rk4, 0.0, 1.0, 100).final_state();
+        assert!(end.heading_rad > FRAC_PI_2 + 0.05 && end.speed_mps > 20.1);
+
+        // Wrapping a model must not change its conventions
+        let composed = Composed::new(simple, GroundEffect::new(0.0, 0.0, 1.0));
+        let east = SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, FRAC_PI_2);
+        assert_tilt_follows_heading(&composed, east);
+    }
+}
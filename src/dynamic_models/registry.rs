@@ -0,0 +1,363 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    dynamic_models::{
+        AttitudeQuadControl, AttitudeQuadState, AttitudeQuadcopter, CoordinatedTurn, DubinsCar,
+        HeadingHold, SimpleQuadcopter, YawAccelQuadcopter,
+    },
+    traits::{Dynamics, LinearizableDynamics},
+    types::{ControlChannels, DroneInput, Saturation, StateVector},
+};
+
+/// Scalar parameters for building a model by name with [`model_from_name`]. Each model
+/// reads only the fields it needs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelParams {
+    /// Linear drag [1/s] of the quadcopter models.
+    pub drag: f64,
+    /// Attitude time constant [s] of `attitude_quad`.
+    pub attitude_tau_s: f64,
+    /// Yaw acceleration limit [rad/s²] of `yaw_accel_quad`.
+    pub max_yaw_accel_rps2: f64,
+    /// Yaw-rate time constant [s] of `yaw_accel_quad`.
+    pub yaw_rate_tau_s: f64,
+    /// Heading-loop gain [1/s] of `heading_hold`.
+    pub heading_gain_per_s: f64,
+    /// Forward speed [m/s] of `dubins` and cruise speed of `coordinated_turn`.
+    pub speed_mps: f64,
+    /// Turn-rate limit [rad/s] of `dubins`.
+    pub max_yaw_rate_rps: f64,
+    /// Autothrottle bandwidth [1/s] of `coordinated_turn`.
+    pub speed_hold_per_s: f64,
+    /// Bank limit [rad] of `coordinated_turn`.
+    pub max_bank_rad: f64,
+}
+
+impl Default for ModelParams {
+    fn default() -> Self {
+        Self {
+            drag: 0.1,
+            attitude_tau_s: 0.1,
+            max_yaw_accel_rps2: 2.0,
+            yaw_rate_tau_s: 0.2,
+            heading_gain_per_s: 2.0,
+            speed_mps: 10.0,
+            max_yaw_rate_rps: 0.5,
+            speed_hold_per_s: 0.5,
+            max_bank_rad: std::f64::consts::FRAC_PI_4,
+        }
+    }
+}
+
+/// [`model_from_name`] was given a name that is not in [`AnyModel::NAMES`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownModelError {
+    pub name: String,
+}
+
+impl fmt::Display for UnknownModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown model `{}` (expected one of: {})",
+            self.name,
+            AnyModel::NAMES.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownModelError {}
+
+/// Any of the shipped models behind one type, for selecting a model at runtime, e.g.
+/// from a command-line flag. Since the models' states and controls differ, it
+/// implements [`Dynamics`] on an erased state (the model's [`StateVector`] layout) and
+/// an erased control (its [`ControlChannels`]), so it runs through every solver and
+/// `predict` driver. It also implements [`LinearizableDynamics`] for the implicit
+/// solvers by dispatching to each model's Jacobian. `attitude_quad` has no analytic
+/// Jacobian and uses the central differences of [`Dynamics::dfdx`] instead. The erased
+/// control is a plain `Vec<f64>` with no [`ControlChannels`], so the control-Jacobian
+/// helpers such as `predict_with_sensitivity` do not apply.
+#[derive(Debug, Clone)]
+pub enum AnyModel {
+    SimpleQuad(SimpleQuadcopter),
+    AttitudeQuad(AttitudeQuadcopter),
+    YawAccelQuad(YawAccelQuadcopter),
+    HeadingHold(HeadingHold),
+    Dubins(DubinsCar),
+    CoordinatedTurn(CoordinatedTurn),
+}
+
+/// Apply `$body` to the concrete model inside an [`AnyModel`], bound as `$m`.
+macro_rules! dispatch {
+    ($model:expr, $m:ident => $body:expr) => {
+        match $model {
+            AnyModel::SimpleQuad($m) => $body,
+            AnyModel::AttitudeQuad($m) => $body,
+            AnyModel::YawAccelQuad($m) => $body,
+            AnyModel::HeadingHold($m) => $body,
+            AnyModel::Dubins($m) => $body,
+            AnyModel::CoordinatedTurn($m) => $body,
+        }
+    };
+}
+
+impl AnyModel {
+    /// Registered names, in the order of the variants.
+    pub const NAMES: &'static [&'static str] = &[
+        "simple_quad",
+        "attitude_quad",
+        "yaw_accel_quad",
+        "heading_hold",
+        "dubins",
+        "coordinated_turn",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        let index = match self {
+            Self::SimpleQuad(_) => 0,
+            Self::AttitudeQuad(_) => 1,
+            Self::YawAccelQuad(_) => 2,
+            Self::HeadingHold(_) => 3,
+            Self::Dubins(_) => 4,
+            Self::CoordinatedTurn(_) => 5,
+        };
+        Self::NAMES[index]
+    }
+
+    /// Length of the erased state vector.
+    pub fn state_dim(&self) -> usize {
+        match self {
+            Self::SimpleQuad(_) | Self::AttitudeQuad(_) | Self::HeadingHold(_) => 7,
+            Self::YawAccelQuad(_) => 6,
+            Self::Dubins(_) => 3,
+            Self::CoordinatedTurn(_) => 4,
+        }
+    }
+}
+
+/// Build the model registered as `name` (see [`AnyModel::NAMES`]) from `params`.
+pub fn model_from_name(name: &str, params: &ModelParams) -> Result<AnyModel, UnknownModelError> {
+    let model = match name {
+        "simple_quad" => AnyModel::SimpleQuad(SimpleQuadcopter::new(params.drag)),
+        "attitude_quad" => {
+            AnyModel::AttitudeQuad(AttitudeQuadcopter::new(params.drag, params.attitude_tau_s))
+        }
+        "yaw_accel_quad" => AnyModel::YawAccelQuad(YawAccelQuadcopter {
+            drag: params.drag,
+            max_yaw_accel_rps2: params.max_yaw_accel_rps2,
+            yaw_rate_tau_s: params.yaw_rate_tau_s,
+        }),
+        "heading_hold" => AnyModel::HeadingHold(HeadingHold::new(
+            SimpleQuadcopter::new(params.drag),
+            params.heading_gain_per_s,
+        )),
+        "dubins" => AnyModel::Dubins(DubinsCar {
+            speed_mps: params.speed_mps,
+            max_yaw_rate_rps: params.max_yaw_rate_rps,
+        }),
+        "coordinated_turn" => AnyModel::CoordinatedTurn(CoordinatedTurn {
+            cruise_speed_mps: params.speed_mps,
+            speed_hold_per_s: params.speed_hold_per_s,
+            max_bank_rad: params.max_bank_rad,
+        }),
+        _ => {
+            return Err(UnknownModelError {
+                name: name.to_owned(),
+            });
+        }
+    };
+    Ok(model)
+}
+
+/// Erased [`Dynamics::derivative`] of one concrete model.
+fn derivative_of<M>(model: &M, t: f64, state: &DVector<f64>, control: &[f64]) -> DVector<f64>
+where
+    M: Dynamics,
+    M::State: StateVector,
+    M::Control: ControlChannels,
+{
+    let state = M::State::from_dvector(state.clone());
+    let control = M::Control::from_channels(control);
+    model.derivative(t, &state, &control).to_dvector()
+}
+
+/// Erased [`LinearizableDynamics::jacobian`] of one concrete model.
+fn jacobian_of<M>(model: &M, t: f64, state: &DVector<f64>, control: &[f64]) -> DMatrix<f64>
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+    M::Control: ControlChannels,
+{
+    let state = M::State::from_dvector(state.clone());
+    let control = M::Control::from_channels(control);
+    model.jacobian(t, &state, &control)
+}
+
+fn validate_state_of<M>(model: &M, state: &DVector<f64>)
+where
+    M: Dynamics,
+    M::State: StateVector,
+{
+    model.validate_state(&M::State::from_dvector(state.clone()));
+}
+
+fn project_state_of<M>(model: &M, state: &DVector<f64>) -> DVector<f64>
+where
+    M: Dynamics,
+    M::State: StateVector,
+{
+    let mut state = M::State::from_dvector(state.clone());
+    model.project_state(&mut state);
+    state.to_dvector()
+}
+
+impl Dynamics for AnyModel {
+    type State = DVector<f64>;
+    type Control = Vec<f64>;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        dispatch!(self, m => m.input_to_control(input).channels())
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        dispatch!(self, m => {
+            let (control, saturation) = m.input_to_control_checked(input);
+            (control.channels(), saturation)
+        })
+    }
+
+    fn derivative(&self, t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        assert!(
+            state.len() == self.state_dim(),
+            "{} expects a state of dimension {}, got {}",
+            self.name(),
+            self.state_dim(),
+            state.len()
+        );
+        dispatch!(self, m => derivative_of(m, t, state, control))
+    }
+
+    fn validate_state(&self, state: &Self::State) {
+        dispatch!(self, m => validate_state_of(m, state))
+    }
+
+    fn project_state(&self, state: &mut Self::State) {
+        *state = dispatch!(self, m => project_state_of(m, state));
+    }
+}
+
+impl LinearizableDynamics for AnyModel {
+    fn jacobian(&self, t: f64, state: &Self::State, control: &Self::Control) -> DMatrix<f64> {
+        match self {
+            Self::SimpleQuad(m) => jacobian_of(m, t, state, control),
+            Self::AttitudeQuad(m) => {
+                let state = AttitudeQuadState::from_dvector(state.clone());
+                let control = AttitudeQuadControl::from_channels(control);
+                let rows = m.dfdx(&state, &control);
+                DMatrix::from_fn(rows.len(), rows.len(), |i, k| rows[i][k])
+            }
+            Self::YawAccelQuad(m) => jacobian_of(m, t, state, control),
+            Self::HeadingHold(m) => jacobian_of(m, t, state, control),
+            Self::Dubins(m) => jacobian_of(m, t, state, control),
+            Self::CoordinatedTurn(m) => jacobian_of(m, t, state, control),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        predict::{HorizonConfig, predict, predict_cfg},
+        solvers::{BackwardEuler, Rk4},
+    };
+
+    #[test]
+    fn every_registered_model_steps_through_the_erased_interface() {
+        let params = ModelParams::default();
+        let input = DroneInput::try_new(0.1, 0.1, 0.2).unwrap();
+        for &name in AnyModel::NAMES {
+            let model = model_from_name(name, &params).unwrap();
+            assert_eq!(model.name(), name);
+
+            let mut start = DVector::zeros(model.state_dim());
+            if name == "coordinated_turn" {
+                start[3] = params.speed_mps;
+            }
+            let prediction = predict(&input, start.clone(), &model, &mut Rk4, 0.0, 0.1, 1);
+            let end = prediction.final_state();
+            assert_eq!(end.len(), model.state_dim(), "{name}");
+            assert!(end.iter().all(|v| v.is_finite()), "{name}");
+            assert_ne!(*end, start, "{name} did not move");
+        }
+    }
+
+    #[test]
+    fn erased_model_matches_the_concrete_one() {
+        let model = model_from_name("simple_quad", &ModelParams::default()).unwrap();
+        let concrete = SimpleQuadcopter::new(ModelParams::default().drag);
+        let input = DroneInput::try_new(0.05, 0.2, 0.3).unwrap();
+        let start = crate::dynamic_models::SimpleQuadState::new(1.0, 2.0, 0.5, 0.0, 0.4);
+
+        let erased = predict(&input, start.to_dvector(), &model, &mut Rk4, 0.0, 3.0, 30);
+        let typed = predict(&input, start, &concrete, &mut Rk4, 0.0, 3.0, 30);
+        assert_eq!(*erased.final_state(), typed.final_state().to_dvector());
+    }
+
+    #[test]
+    fn every_registered_model_linearizes_for_the_implicit_solvers() {
+        let params = ModelParams::default();
+        let input = DroneInput::try_new(0.1, 0.1, 0.2).unwrap();
+        for &name in AnyModel::NAMES {
+            let model = model_from_name(name, &params).unwrap();
+            let mut start = DVector::from_element(model.state_dim(), 0.1);
+            if name == "coordinated_turn" {
+                start[3] = params.speed_mps;
+            }
+
+            let control = model.input_to_control(&input);
+            let j = model.jacobian(0.0, &start, &control);
+            assert_eq!(j.shape(), (model.state_dim(), model.state_dim()), "{name}");
+            let rk4 = predict(&input, start.clone(), &model, &mut Rk4, 0.0, 0.1, 10);
+            let mut solver = BackwardEuler::default();
+            let implicit = predict(&input, start, &model, &mut solver, 0.0, 0.1, 10);
+            let gap = (implicit.final_state() - rk4.final_state()).amax();
+            assert!(gap < 1e-2, "{name}: {gap}");
+        }
+
+        let concrete = SimpleQuadcopter::new(params.drag);
+        let state = crate::dynamic_models::SimpleQuadState::new(1.0, 2.0, 0.5, 0.0, 0.4);
+        let control = concrete.input_to_control(&input);
+        let erased = model_from_name("simple_quad", &params).unwrap().jacobian(
+            0.0,
+            &state.to_dvector(),
+            &control.channels(),
+        );
+        assert_eq!(erased, concrete.jacobian(0.0, &state, &control));
+    }
+
+    #[test]
+    fn erased_model_runs_through_predict_cfg() {
+        // DVector states and Vec<f64> controls have no Position2D or ControlChannels
+        let model = model_from_name("dubins", &ModelParams::default()).unwrap();
+        let cfg = HorizonConfig::new(0.0, 1.0, 10)
+            .unwrap()
+            .with_output_stride(4);
+        let input = DroneInput::try_new(0.0, 0.0, 0.2).unwrap();
+        let start = DVector::zeros(model.state_dim());
+        let prediction = predict_cfg(&input, start.clone(), &model, &mut Rk4, &cfg);
+        let full = predict(&input, start, &model, &mut Rk4, 0.0, 1.0, 10);
+        assert_eq!(prediction.states.len(), 4);
+        assert_eq!(prediction.final_state(), full.final_state());
+    }
+
+    #[test]
+    fn unknown_name_lists_the_registered_ones() {
+        let err = model_from_name("blimp", &ModelParams::default()).unwrap_err();
+        assert_eq!(err.name, "blimp");
+        let message = err.to_string();
+        assert!(message.contains("`blimp`") && message.contains("coordinated_turn"));
+    }
+}
@@ -1,12 +1,95 @@
+use nalgebra::Vector2;
+
 use crate::{
+    autodiff::{AutoDiffDynamics, Scalar},
+    math::{rotation_ned_from_body, rotation_ned_from_body_dyaw},
     traits::{Dynamics, GRAVITY, LinearizableDynamics},
-    types::{DroneInput, IntegrableState, Position2D, StateVector},
+    types::{
+        ControlChannels, Deadband, DroneInput, GroundVelocity, Heading, IntegrableState,
+        Position2D, Saturation, SecondOrderState, StateVector, Workspace,
+    },
 };
 
-/// Planar NED quadcopter model using hover small-angle thrust and linear drag.
+/// NED quadcopter model using hover small-angle thrust and linear drag. The horizontal
+/// dynamics are planar; a decoupled vertical channel follows the throttle stick.
 /// Body frame: x-forward, y-right, z-down. Yaw = 0 faces North, positive clockwise.
+#[derive(Debug, Clone)]
 pub struct SimpleQuadcopter {
     pub drag: f64,
+    /// Climb/descent acceleration [m/s²] commanded by full-up/full-down throttle.
+    pub max_vertical_accel_mps2: f64,
+    /// Roll and pitch commands beyond this magnitude [rad] are clamped.
+    pub max_tilt_rad: f64,
+    /// Firmware ground-speed limit [m/s]. When set, the horizontal velocity is scaled
+    /// back onto this magnitude after every step: a kinematic clamp applied by the
+    /// `predict` drivers, not a physical force in the dynamics.
+    pub max_ground_speed_mps: Option<f64>,
+    /// Arena the horizontal position is confined to. When set, a sample that leaves it
+    /// is clamped back onto the wall and the velocity into the wall is zeroed after
+    /// every step (a soft, perfectly inelastic wall): like the speed limit, a
+    /// kinematic constraint of the `predict` drivers, not a contact force.
+    pub workspace: Option<Workspace>,
+    /// Stick deadband applied to the raw input before it is converted to a control.
+    pub deadband: Option<Deadband>,
+}
+
+impl SimpleQuadcopter {
+    /// Default full-throttle climb/descent acceleration [m/s²].
+    pub const DEFAULT_MAX_VERTICAL_ACCEL_MPS2: f64 = 4.0;
+    /// Default tilt limit, just short of ±90° where tan() blows up.
+    pub const DEFAULT_MAX_TILT_RAD: f64 = std::f64::consts::FRAC_PI_2 * 0.95;
+
+    pub fn new(drag: f64) -> Self {
+        Self {
+            drag,
+            max_vertical_accel_mps2: Self::DEFAULT_MAX_VERTICAL_ACCEL_MPS2,
+            max_tilt_rad: Self::DEFAULT_MAX_TILT_RAD,
+            max_ground_speed_mps: None,
+            workspace: None,
+            deadband: None,
+        }
+    }
+
+    /// Enable the kinematic ground-speed clamp, see [`Self::max_ground_speed_mps`].
+    pub fn with_max_ground_speed(self, max_ground_speed_mps: f64) -> Self {
+        assert!(
+            max_ground_speed_mps.is_finite() && max_ground_speed_mps > 0.0,
+            "max_ground_speed_mps must be finite and > 0"
+        );
+        Self {
+            max_ground_speed_mps: Some(max_ground_speed_mps),
+            ..self
+        }
+    }
+
+    /// Tighten the tilt limit, e.g. to a firmware angle limit, see [`Self::max_tilt_rad`].
+    pub fn with_max_tilt(self, max_tilt_rad: f64) -> Self {
+        assert!(
+            max_tilt_rad > 0.0 && max_tilt_rad <= Self::DEFAULT_MAX_TILT_RAD,
+            "max_tilt_rad must be in (0, {}]",
+            Self::DEFAULT_MAX_TILT_RAD
+        );
+        Self {
+            max_tilt_rad,
+            ..self
+        }
+    }
+
+    /// Enable the kinematic arena constraint, see [`Self::workspace`].
+    pub fn with_workspace(self, workspace: Workspace) -> Self {
+        Self {
+            workspace: Some(workspace),
+            ..self
+        }
+    }
+
+    /// Model the stick deadband, see [`Self::deadband`].
+    pub fn with_deadband(self, deadband: Deadband) -> Self {
+        Self {
+            deadband: Some(deadband),
+            ..self
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -16,6 +99,9 @@ pub struct SimpleQuadState {
     pub v_north_mps: f64,
     pub v_east_mps: f64,
     pub yaw_rad: f64,
+    /// Depth below the start [m], positive down (altitude is `-down_m`).
+    pub down_m: f64,
+    pub v_down_mps: f64,
 }
 
 impl SimpleQuadState {
@@ -26,6 +112,17 @@ impl SimpleQuadState {
             v_north_mps,
             v_east_mps,
             yaw_rad,
+            down_m: 0.0,
+            v_down_mps: 0.0,
+        }
+    }
+
+    /// Same state with the vertical channel set.
+    pub fn with_vertical(self, down_m: f64, v_down_mps: f64) -> Self {
+        Self {
+            down_m,
+            v_down_mps,
+            ..self
         }
     }
 
@@ -34,11 +131,9 @@ impl SimpleQuadState {
     }
 
     pub fn ensure_finite(&self) {
-        assert!(self.north_m.is_finite(), "north must be finite");
-        assert!(self.east_m.is_finite(), "east must be finite");
-        assert!(self.v_north_mps.is_finite(), "v_north must be finite");
-        assert!(self.v_east_mps.is_finite(), "v_east must be finite");
-        assert!(self.yaw_rad.is_finite(), "yaw must be finite");
+        if let Some(field) = self.non_finite_field() {
+            panic!("{field} must be finite");
+        }
     }
 }
 
@@ -50,6 +145,64 @@ impl IntegrableState for SimpleQuadState {
             v_north_mps: self.v_north_mps + scale * derivative.v_north_mps,
             v_east_mps: self.v_east_mps + scale * derivative.v_east_mps,
             yaw_rad: self.yaw_rad + scale * derivative.yaw_rad,
+            down_m: self.down_m + scale * derivative.down_m,
+            v_down_mps: self.v_down_mps + scale * derivative.v_down_mps,
+        }
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.north_m += scale * derivative.north_m;
+        self.east_m += scale * derivative.east_m;
+        self.v_north_mps += scale * derivative.v_north_mps;
+        self.v_east_mps += scale * derivative.v_east_mps;
+        self.yaw_rad += scale * derivative.yaw_rad;
+        self.down_m += scale * derivative.down_m;
+        self.v_down_mps += scale * derivative.v_down_mps;
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("north_m", self.north_m),
+            ("east_m", self.east_m),
+            ("v_north_mps", self.v_north_mps),
+            ("v_east_mps", self.v_east_mps),
+            ("yaw_rad", self.yaw_rad),
+            ("down_m", self.down_m),
+            ("v_down_mps", self.v_down_mps),
+        ]
+        .into_iter()
+        .find(|(_, v)| !v.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+/// North, east and down are positions; yaw follows the commanded rate directly and
+/// counts as a rate.
+impl SecondOrderState for SimpleQuadState {
+    fn position_indices() -> &'static [usize] {
+        &[0, 1, 5]
+    }
+
+    fn velocity_indices() -> &'static [usize] {
+        &[2, 3, 6]
+    }
+
+    fn add_scaled_rates(&self, derivative: &Self, scale: f64) -> Self {
+        Self {
+            v_north_mps: self.v_north_mps + scale * derivative.v_north_mps,
+            v_east_mps: self.v_east_mps + scale * derivative.v_east_mps,
+            yaw_rad: self.yaw_rad + scale * derivative.yaw_rad,
+            v_down_mps: self.v_down_mps + scale * derivative.v_down_mps,
+            ..*self
+        }
+    }
+
+    fn advance_positions(&self, scale: f64) -> Self {
+        Self {
+            north_m: self.north_m + scale * self.v_north_mps,
+            east_m: self.east_m + scale * self.v_east_mps,
+            down_m: self.down_m + scale * self.v_down_mps,
+            ..*self
         }
     }
 }
@@ -60,6 +213,22 @@ impl Position2D for SimpleQuadState {
     }
 }
 
+impl GroundVelocity for SimpleQuadState {
+    fn ground_velocity(&self) -> (f64, f64) {
+        (self.v_north_mps, self.v_east_mps)
+    }
+}
+
+impl Heading for SimpleQuadState {
+    fn yaw_rad(&self) -> f64 {
+        self.yaw_rad
+    }
+
+    fn with_yaw(&self, yaw_rad: f64) -> Self {
+        Self { yaw_rad, ..*self }
+    }
+}
+
 impl StateVector for SimpleQuadState {
     fn to_dvector(&self) -> nalgebra::DVector<f64> {
         nalgebra::DVector::from_vec(vec![
@@ -68,13 +237,15 @@ impl StateVector for SimpleQuadState {
             self.v_north_mps,
             self.v_east_mps,
             self.yaw_rad,
+            self.down_m,
+            self.v_down_mps,
         ])
     }
 
     fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
         assert!(
-            v.len() == 5,
-            "SimpleQuadState expects 5 elements (north, east, v_north, v_east, yaw)"
+            v.len() == 7,
+            "SimpleQuadState expects 7 elements (north, east, v_north, v_east, yaw, down, v_down)"
         );
         Self {
             north_m: v[0],
@@ -82,6 +253,8 @@ impl StateVector for SimpleQuadState {
             v_north_mps: v[2],
             v_east_mps: v[3],
             yaw_rad: v[4],
+            down_m: v[5],
+            v_down_mps: v[6],
         }
     }
 }
@@ -91,6 +264,44 @@ pub struct SimpleQuadControl {
     pub ax_body_mps2: f64,
     pub ay_body_mps2: f64,
     pub yaw_rate_rps: f64,
+    pub az_down_mps2: f64,
+}
+
+impl SimpleQuadControl {
+    /// Horizontal body-frame acceleration `(forward, right)`.
+    pub fn accel_body(&self) -> Vector2<f64> {
+        Vector2::new(self.ax_body_mps2, self.ay_body_mps2)
+    }
+}
+
+impl ControlChannels for SimpleQuadControl {
+    fn channel_names() -> &'static [&'static str] {
+        &[
+            "ax_body [m/s²]",
+            "ay_body [m/s²]",
+            "yaw_rate [rad/s]",
+            "az_down [m/s²]",
+        ]
+    }
+
+    fn channels(&self) -> Vec<f64> {
+        vec![
+            self.ax_body_mps2,
+            self.ay_body_mps2,
+            self.yaw_rate_rps,
+            self.az_down_mps2,
+        ]
+    }
+
+    fn from_channels(values: &[f64]) -> Self {
+        assert!(values.len() == 4, "SimpleQuadControl expects 4 channels");
+        Self {
+            ax_body_mps2: values[0],
+            ay_body_mps2: values[1],
+            yaw_rate_rps: values[2],
+            az_down_mps2: values[3],
+        }
+    }
 }
 
 impl Dynamics for SimpleQuadcopter {
@@ -98,45 +309,100 @@ impl Dynamics for SimpleQuadcopter {
     type Control = SimpleQuadControl;
 
     fn input_to_control(&self, input: &DroneInput) -> Self::Control {
-        // Limit tilt to avoid tan() blowing up near ±90°
-        const MAX_TILT_RAD: f64 = std::f64::consts::FRAC_PI_2 * 0.95;
-        let pitch = input.pitch_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD);
-        let roll = input.roll_rad.clamp(-MAX_TILT_RAD, MAX_TILT_RAD);
+        self.input_to_control_checked(input).0
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        let input = &self.deadband.map_or(*input, |d| d.apply(input));
+        let max_tilt = self.max_tilt_rad;
+        let pitch = input.pitch_rad.clamp(-max_tilt, max_tilt);
+        let roll = input.roll_rad.clamp(-max_tilt, max_tilt);
+        let thrust = input.thrust_norm.clamp(0.0, 1.0);
 
         // Small-angle hover approximation: a_forward ≈ g * tan(pitch)
         // Body frame: x-forward, y-right, z-down
-        SimpleQuadControl {
-            ax_body_mps2: -GRAVITY * pitch.tan(),
+        let control = SimpleQuadControl {
+            ax_body_mps2: GRAVITY * pitch.tan(),
             ay_body_mps2: GRAVITY * roll.tan(),
             yaw_rate_rps: input.yaw_rate_rps,
-        }
+            // Throttle around hover: 0.5 holds altitude, 1.0 climbs at the limit
+            az_down_mps2: -(2.0 * thrust - 1.0) * self.max_vertical_accel_mps2,
+        };
+        let saturation = Saturation {
+            roll: roll != input.roll_rad,
+            pitch: pitch != input.pitch_rad,
+            yaw_rate: false,
+            thrust: thrust != input.thrust_norm,
+        };
+        (control, saturation)
     }
 
-    fn derivative(&self, _t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+    fn derivative(&self, t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
         state.ensure_finite();
 
-        let c = state.yaw_rad.cos();
-        let s = state.yaw_rad.sin();
-
-        // Rotate body accelerations into NED (x = North, y = East)
-        let ax_n = control.ax_body_mps2 * c - control.ay_body_mps2 * s;
-        let ay_e = control.ax_body_mps2 * s + control.ay_body_mps2 * c;
-
-        // Linear drag in N/E directions
-        let dv_n = ax_n - self.drag * state.v_north_mps;
-        let dv_e = ay_e - self.drag * state.v_east_mps;
-
+        let x = [
+            state.north_m,
+            state.east_m,
+            state.v_north_mps,
+            state.v_east_mps,
+            state.yaw_rad,
+            state.down_m,
+            state.v_down_mps,
+        ];
+        let mut dx = [0.0; 7];
+        self.derivative_generic(t, &x, control, &mut dx);
+        let [
+            north_m,
+            east_m,
+            v_north_mps,
+            v_east_mps,
+            yaw_rad,
+            down_m,
+            v_down_mps,
+        ] = dx;
         SimpleQuadState {
-            north_m: state.v_north_mps,    // north_dot
-            east_m: state.v_east_mps,      // east_dot
-            v_north_mps: dv_n,             // v_north_dot
-            v_east_mps: dv_e,              // v_east_dot
-            yaw_rad: control.yaw_rate_rps, // yaw_dot
+            north_m,
+            east_m,
+            v_north_mps,
+            v_east_mps,
+            yaw_rad,
+            down_m,
+            v_down_mps,
         }
     }
 
-    fn validate_state(&self, state: &Self::State) {
-        state.ensure_finite();
+    fn dfdx(&self, state: &Self::State, control: &Self::Control) -> Vec<Vec<f64>> {
+        #[cfg(feature = "autodiff")]
+        let j = crate::autodiff::jacobian(self, 0.0, state, control);
+        #[cfg(not(feature = "autodiff"))]
+        let j = self.jacobian(0.0, state, control);
+        j.row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect()
+    }
+
+    fn project_state(&self, state: &mut Self::State) {
+        if let Some(max) = self.max_ground_speed_mps {
+            let speed = state.ground_speed();
+            if speed > max {
+                let scale = max / speed;
+                state.v_north_mps *= scale;
+                state.v_east_mps *= scale;
+            }
+        }
+        if let Some(workspace) = &self.workspace {
+            (
+                state.north_m,
+                state.east_m,
+                state.v_north_mps,
+                state.v_east_mps,
+            ) = workspace.confine(
+                state.north_m,
+                state.east_m,
+                state.v_north_mps,
+                state.v_east_mps,
+            );
+        }
     }
 }
 
@@ -147,33 +413,89 @@ impl LinearizableDynamics for SimpleQuadcopter {
         state: &Self::State,
         control: &Self::Control,
     ) -> nalgebra::DMatrix<f64> {
-        let c = state.yaw_rad.cos();
-        let s = state.yaw_rad.sin();
-
         // Partials of rotated accelerations w.r.t yaw
-        let dax_dyaw = -control.ax_body_mps2 * s - control.ay_body_mps2 * c;
-        let day_dyaw = control.ax_body_mps2 * c - control.ay_body_mps2 * s;
+        let da_dyaw = rotation_ned_from_body_dyaw(state.yaw_rad) * control.accel_body();
+        let (dax_dyaw, day_dyaw) = (da_dyaw.x, da_dyaw.y);
 
+        let drag = -self.drag;
         nalgebra::DMatrix::from_row_slice(
-            5,
-            5,
+            7,
+            7,
             &[
-                0.0, 0.0, 1.0, 0.0, 0.0, // d(north_dot)/d(state)
-                0.0, 0.0, 0.0, 1.0, 0.0, // d(east_dot)/d(state)
-                0.0, 0.0, -self.drag, 0.0, dax_dyaw, // d(v_n_dot)/d(yaw)
-                0.0, 0.0, 0.0, -self.drag, day_dyaw, // d(v_e_dot)/d(yaw)
-                0.0, 0.0, 0.0, 0.0, 0.0, // d(yaw_dot)/d(state)
+                0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, // d(north_dot)/d(state)
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, // d(east_dot)/d(state)
+                0.0, 0.0, drag, 0.0, dax_dyaw, 0.0, 0.0, // d(v_n_dot)/d(yaw)
+                0.0, 0.0, 0.0, drag, day_dyaw, 0.0, 0.0, // d(v_e_dot)/d(yaw)
+                0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, // d(yaw_dot)/d(state)
+                0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, // d(down_dot)/d(state)
+                0.0, 0.0, 0.0, 0.0, 0.0, 0.0, drag, // d(v_d_dot)/d(state)
+            ],
+        )
+    }
+
+    fn control_jacobian(
+        &self,
+        _t: f64,
+        state: &Self::State,
+        _control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64> {
+        // Body accelerations rotate into the velocity rates; yaw rate and az pass through
+        let r = rotation_ned_from_body(state.yaw_rad);
+        let (r00, r01, r10, r11) = (r[(0, 0)], r[(0, 1)], r[(1, 0)], r[(1, 1)]);
+        nalgebra::DMatrix::from_row_slice(
+            7,
+            4,
+            &[
+                0.0, 0.0, 0.0, 0.0, // d(north_dot)/d(control)
+                0.0, 0.0, 0.0, 0.0, // d(east_dot)/d(control)
+                r00, r01, 0.0, 0.0, // d(v_n_dot)/d(ax, ay)
+                r10, r11, 0.0, 0.0, // d(v_e_dot)/d(ax, ay)
+                0.0, 0.0, 1.0, 0.0, // d(yaw_dot)/d(yaw_rate)
+                0.0, 0.0, 0.0, 0.0, // d(down_dot)/d(control)
+                0.0, 0.0, 0.0, 1.0, // d(v_d_dot)/d(az_down)
             ],
         )
     }
 }
 
+impl AutoDiffDynamics for SimpleQuadcopter {
+    fn derivative_generic<T: Scalar>(
+        &self,
+        _t: f64,
+        x: &[T],
+        control: &Self::Control,
+        dx: &mut [T],
+    ) {
+        let c = T::constant;
+        let drag = c(self.drag);
+        let (ax, ay) = (c(control.ax_body_mps2), c(control.ay_body_mps2));
+
+        // Rotate body accelerations into NED (x = North, y = East), linear drag per axis
+        let (s, co) = (x[4].sin(), x[4].cos());
+        dx.copy_from_slice(&[
+            x[2],                                  // north_dot
+            x[3],                                  // east_dot
+            co * ax - s * ay - drag * x[2],        // v_north_dot
+            s * ax + co * ay - drag * x[3],        // v_east_dot
+            c(control.yaw_rate_rps),               // yaw_dot
+            x[6],                                  // down_dot
+            c(control.az_down_mps2) - drag * x[6], // v_down_dot
+        ]);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        dynamic_models::assert_control_jacobian_matches_fd,
+        predict::predict,
+        solvers::{BackwardEuler, Rk4},
+        types::InputLimits,
+    };
 
     fn model() -> SimpleQuadcopter {
-        SimpleQuadcopter { drag: 0.1 }
+        SimpleQuadcopter::new(0.1)
     }
 
     #[test]
@@ -182,6 +504,7 @@ mod tests {
             roll_rad: 0.0,
             pitch_rad: 10f64.to_radians(),
             yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
         };
 
         let control = model().input_to_control(&input);
@@ -192,12 +515,34 @@ mod tests {
         assert!(dx.v_east_mps.abs() < 1e-9);
     }
 
+    #[test]
+    fn stick_signs_follow_the_drone_input_convention() {
+        let control = model().input_to_control(&DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.1,
+            yaw_rate_rps: 0.1,
+            thrust_norm: 0.5,
+        });
+
+        // DroneInput: nose down, right wing down and clockwise yaw are positive
+        assert!(control.ax_body_mps2 > 0.0, "nose down accelerates forward");
+        assert!(
+            control.ay_body_mps2 > 0.0,
+            "right wing down accelerates right"
+        );
+        assert!(
+            control.yaw_rate_rps > 0.0,
+            "positive yaw rate turns clockwise"
+        );
+    }
+
     #[test]
     fn roll_right_pushes_east_when_yaw_zero() {
         let input = DroneInput {
             roll_rad: 5f64.to_radians(),
             pitch_rad: 0.0,
             yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
         };
 
         let control = model().input_to_control(&input);
@@ -214,6 +559,7 @@ mod tests {
             roll_rad: 0.0,
             pitch_rad: 10f64.to_radians(),
             yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
         };
         let control = model().input_to_control(&input);
 
@@ -231,6 +577,7 @@ mod tests {
             roll_rad: 0.0,
             pitch_rad: 0.0,
             yaw_rate_rps: 1.0,
+            thrust_norm: 0.5,
         };
         let control = model().input_to_control(&input);
         let state = SimpleQuadState::zero();
@@ -238,4 +585,544 @@ mod tests {
 
         assert_eq!(dx.yaw_rad, 1.0);
     }
+
+    #[test]
+    fn main_scenario_runs_end_to_end_with_rk4_and_backward_euler() {
+        // Same setup as src/main.rs
+        let input = DroneInput {
+            roll_rad: 20f64.to_radians(),
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let initial_state = SimpleQuadState::new(0.0, 0.0, 5.0, 1.0, 20.0);
+        let model = SimpleQuadcopter::new(0.0);
+        let (t_final, steps) = (10.0, 30_000);
+
+        // Constant heading and no drag: constant NED acceleration, so the endpoint is
+        // p0 + v0 t + a t² / 2
+        let control = model.input_to_control(&input);
+        let a = model.derivative(0.0, &initial_state, &control);
+        let expected_north = 5.0 * t_final + 0.5 * a.v_north_mps * t_final * t_final;
+        let expected_east = 1.0 * t_final + 0.5 * a.v_east_mps * t_final * t_final;
+
+        let rk4 = predict(&input, initial_state, &model, &mut Rk4, 0.0, t_final, steps);
+        let mut be_solver = BackwardEuler::default();
+        let be = predict(
+            &input,
+            initial_state,
+            &model,
+            &mut be_solver,
+            0.0,
+            t_final,
+            steps,
+        );
+
+        for end in [rk4.final_state(), be.final_state()] {
+            end.ensure_finite();
+            assert_eq!(end.yaw_rad, 20.0);
+        }
+        let rk4_end = rk4.final_state();
+        assert!((rk4_end.north_m - expected_north).abs() < 1e-6);
+        assert!((rk4_end.east_m - expected_east).abs() < 1e-6);
+
+        // Backward Euler lags by about a * dt * t / 2 in position
+        let be_end = be.final_state();
+        assert!((be_end.north_m - expected_north).abs() < 0.01);
+        assert!((be_end.east_m - expected_east).abs() < 0.01);
+    }
+
+    #[test]
+    fn vertical_channel_leaves_horizontal_dynamics_untouched() {
+        let input = |thrust_norm| DroneInput {
+            roll_rad: 8f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.4,
+            thrust_norm,
+        };
+        let start = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.2);
+
+        let hover = predict(&input(0.5), start, &model(), &mut Rk4, 0.0, 10.0, 1000);
+        let climb = predict(&input(0.8), start, &model(), &mut Rk4, 0.0, 10.0, 1000);
+
+        for (h, c) in hover.states.iter().zip(&climb.states) {
+            assert_eq!(
+                (h.north_m, h.east_m, h.v_north_mps, h.v_east_mps, h.yaw_rad),
+                (c.north_m, c.east_m, c.v_north_mps, c.v_east_mps, c.yaw_rad)
+            );
+            assert_eq!((h.down_m, h.v_down_mps), (0.0, 0.0));
+        }
+
+        // Throttle above hover climbs (down decreases) towards a drag-limited speed
+        let end = climb.final_state();
+        let terminal = -0.6 * model().max_vertical_accel_mps2 / model().drag;
+        let exact_v = terminal * (1.0 - (-model().drag * 10.0).exp());
+        assert!(end.down_m < 0.0);
+        assert!((end.v_down_mps - exact_v).abs() < 1e-9);
+    }
+
+    #[test]
+    fn throttle_is_centred_on_hover() {
+        let throttle = |thrust_norm| {
+            model()
+                .input_to_control(&DroneInput {
+                    roll_rad: 0.0,
+                    pitch_rad: 0.0,
+                    yaw_rate_rps: 0.0,
+                    thrust_norm,
+                })
+                .az_down_mps2
+        };
+        let max = model().max_vertical_accel_mps2;
+
+        assert_eq!(throttle(0.5), 0.0);
+        assert_eq!(throttle(1.0), -max);
+        assert_eq!(throttle(0.0), max);
+        assert_eq!(throttle(0.75), -0.5 * max);
+        // Beyond the stick range the command saturates
+        assert_eq!(throttle(1.5), -max);
+
+        let neutral = predict(
+            &DroneInput {
+                roll_rad: 0.1,
+                pitch_rad: 0.2,
+                yaw_rate_rps: 0.3,
+                thrust_norm: 0.5,
+            },
+            SimpleQuadState::zero(),
+            &model(),
+            &mut Rk4,
+            0.0,
+            20.0,
+            200,
+        );
+        assert!(neutral.states.iter().all(|s| s.down_m == 0.0));
+    }
+
+    #[test]
+    fn control_jacobian_matches_finite_differences() {
+        use crate::dynamic_models::{Composed, GroundEffect};
+
+        let state = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.7).with_vertical(-5.0, 1.0);
+        let control = SimpleQuadControl {
+            ax_body_mps2: 1.5,
+            ay_body_mps2: -0.8,
+            yaw_rate_rps: 0.3,
+            az_down_mps2: -1.0,
+        };
+        let b = model().control_jacobian(0.0, &state, &control);
+        assert_eq!(b.shape(), (7, 4));
+        assert_control_jacobian_matches_fd(&model(), &state, &control, 1e-8);
+
+        // Composed models fall back to the numerical default, which must agree
+        let composed = Composed::new(model(), GroundEffect::new(0.0, 0.0, 1.0));
+        let numeric = composed.control_jacobian(0.0, &state, &control);
+        assert!((numeric - b).amax() < 1e-8);
+    }
+
+    #[cfg(feature = "autodiff")]
+    #[test]
+    fn autodiff_jacobian_matches_hand_written() {
+        use crate::autodiff;
+
+        let model = SimpleQuadcopter::new(0.3);
+        let state = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.8).with_vertical(-5.0, 0.2);
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.7,
+        });
+
+        let ad = autodiff::jacobian(&model, 0.0, &state, &control);
+        let hand = model.jacobian(0.0, &state, &control);
+        assert!((&ad - hand).amax() < 1e-15);
+
+        // dfdx goes through autodiff with the feature on
+        let rows = model.dfdx(&state, &control);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(*row, ad.row(i).iter().copied().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn jacobian_batch_matches_single_calls() {
+        let model = model();
+        let states: Vec<_> = (0..5)
+            .map(|k| SimpleQuadState::new(k as f64, 1.0, 2.0 - k as f64, 0.5, 0.4 * k as f64))
+            .collect();
+        let controls: Vec<_> = (0..5)
+            .map(|k| SimpleQuadControl {
+                ax_body_mps2: 1.0,
+                ay_body_mps2: -0.5 * k as f64,
+                yaw_rate_rps: 0.1,
+                az_down_mps2: 0.0,
+            })
+            .collect();
+        let times = [0.0, 0.1, 0.2, 0.3, 0.4];
+
+        let batch = model.jacobian_batch(&states, &controls, &times);
+        assert_eq!(batch.len(), 5);
+        for k in 0..5 {
+            assert_eq!(batch[k], model.jacobian(times[k], &states[k], &controls[k]));
+        }
+    }
+
+    #[test]
+    fn ground_speed_clamp_plateaus_at_the_limit() {
+        let steep = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 30f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let limit = 12.0;
+        // Drag-balanced speed g tan(30°) / 0.1 ≈ 57 m/s is far above the limit
+        let clamped = SimpleQuadcopter::new(0.1).with_max_ground_speed(limit);
+        let prediction = predict(
+            &steep,
+            SimpleQuadState::zero(),
+            &clamped,
+            &mut Rk4,
+            0.0,
+            30.0,
+            600,
+        );
+
+        let speeds = prediction.speed_series();
+        assert!(speeds.iter().all(|v| *v <= limit * (1.0 + 1e-12)));
+        assert!(speeds[300..].iter().all(|v| (v - limit).abs() < 1e-9));
+
+        let free = predict(
+            &steep,
+            SimpleQuadState::zero(),
+            &model(),
+            &mut Rk4,
+            0.0,
+            30.0,
+            600,
+        );
+        assert!(free.final_state().ground_speed() > 2.0 * limit);
+    }
+
+    #[test]
+    fn workspace_stops_the_drone_at_the_wall() {
+        let toward_north_wall = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 20f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let cage = Workspace::new(-50.0, 50.0, -50.0, 50.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 0.0, 1.0, 0.0);
+        let caged = predict(
+            &toward_north_wall,
+            start,
+            &model().with_workspace(cage),
+            &mut Rk4,
+            0.0,
+            20.0,
+            400,
+        );
+
+        assert!(caged.states.iter().all(|s| cage.contains(s.position())));
+        let end = caged.final_state();
+        assert_eq!((end.north_m, end.v_north_mps), (50.0, 0.0));
+        // Sliding along the wall is untouched
+        assert!(end.east_m > 5.0 && end.v_east_mps > 0.0);
+
+        let free = predict(
+            &toward_north_wall,
+            start,
+            &model(),
+            &mut Rk4,
+            0.0,
+            20.0,
+            400,
+        );
+        assert!(free.final_state().north_m > 100.0);
+    }
+
+    #[test]
+    fn pure_turn_closes_into_circle_after_one_revolution() {
+        use crate::predict::Prediction;
+        use crate::solvers::ForwardEuler;
+        use num_complex::Complex64;
+
+        // Constant forward thrust rotating at omega against linear drag. Writing
+        // velocity as V = v_n + i v_e, V' = a e^{i omega t} - d V, whose periodic
+        // solution V = a e^{i omega t} / (d + i omega) traces a circle of radius
+        // a / (omega |d + i omega|). Starting on that solution removes the transient.
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 5f64.to_radians(),
+            yaw_rate_rps: 0.5,
+            thrust_norm: 0.5,
+        };
+        let model = model();
+        let a = model.input_to_control(&input).ax_body_mps2;
+        let (d, omega) = (model.drag, input.yaw_rate_rps);
+        let pole = Complex64::new(d, omega);
+        let v0 = a / pole;
+        let radius = a / (omega * pole.norm());
+        let center = -a / (Complex64::i() * omega * pole);
+
+        let initial = SimpleQuadState::new(0.0, 0.0, v0.re, v0.im, 0.0);
+        let period = std::f64::consts::TAU / omega;
+        let steps = 2_000;
+
+        let closure = |pred: &Prediction<SimpleQuadState, SimpleQuadControl>| {
+            let (n, e) = pred.final_state().position();
+            n.hypot(e)
+        };
+        let max_radius_error = |pred: &Prediction<SimpleQuadState, SimpleQuadControl>| {
+            pred.positions_iter()
+                .map(|(n, e)| ((Complex64::new(n, e) - center).norm() - radius).abs())
+                .fold(0.0, f64::max)
+        };
+
+        let rk4 = predict(&input, initial, &model, &mut Rk4, 0.0, period, steps);
+        assert!(closure(&rk4) < 1e-9, "RK4 closure {}", closure(&rk4));
+        assert!(max_radius_error(&rk4) < 1e-9);
+
+        // First order: closes to O(dt), still on the right circle
+        let be = predict(
+            &input,
+            initial,
+            &model,
+            &mut BackwardEuler::default(),
+            0.0,
+            period,
+            steps,
+        );
+        assert!(closure(&be) < 1e-2 * radius, "BE closure {}", closure(&be));
+        assert!(max_radius_error(&be) < 1e-2 * radius);
+
+        // ForwardEuler drifts by the same order: the expected first-order error of
+        // sampling the heading at the step start, not a heading/ordering bug.
+        let fe = predict(
+            &input,
+            initial,
+            &model,
+            &mut ForwardEuler,
+            0.0,
+            period,
+            steps,
+        );
+        assert!(closure(&fe) < 1e-2 * radius, "FE closure {}", closure(&fe));
+        assert!(closure(&fe) > 1e3 * closure(&rk4));
+    }
+
+    #[test]
+    fn nan_field_reports_not_finite_through_the_trait() {
+        fn finite<S: IntegrableState>(state: &S) -> bool {
+            state.is_finite()
+        }
+        let good = SimpleQuadState::new(1.0, 2.0, 0.5, 0.0, 0.3);
+        assert!(finite(&good));
+        let bad = good.with_vertical(f64::NAN, 0.0);
+        assert!(!finite(&bad));
+        assert_eq!(bad.non_finite_field(), Some("down_m"));
+    }
+
+    #[test]
+    #[should_panic(expected = "v_east_mps must be finite")]
+    fn default_validate_state_rejects_non_finite_start() {
+        let start = SimpleQuadState::new(0.0, 0.0, 0.0, f64::INFINITY, 0.0);
+        predict(
+            &DroneInput::try_new(0.0, 0.0, 0.0).unwrap(),
+            start,
+            &model(),
+            &mut Rk4,
+            0.0,
+            1.0,
+            10,
+        );
+    }
+
+    #[test]
+    fn pitch_inside_deadband_is_ignored_and_rescaled_beyond() {
+        let deadband = Deadband {
+            roll_rad: 0.0,
+            pitch_rad: 0.05,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.0,
+            full_scale: InputLimits {
+                max_tilt_rad: 0.55,
+                max_yaw_rate_rps: 2.0,
+            },
+        };
+        let quad = model().with_deadband(deadband);
+
+        let inside = DroneInput::try_new(0.0, 0.04, 0.0).unwrap();
+        assert_eq!(quad.input_to_control(&inside).ax_body_mps2, 0.0);
+
+        // Just past the edge: (0.06 - 0.05) stretched by 0.55 / 0.5
+        let beyond = DroneInput::try_new(0.0, 0.06, 0.0).unwrap();
+        let expected = GRAVITY * (0.01_f64 * 1.1).tan();
+        assert!((quad.input_to_control(&beyond).ax_body_mps2 - expected).abs() < 1e-12);
+
+        // Full scale is unchanged
+        let full = DroneInput::try_new(0.0, -0.55, 0.0).unwrap();
+        let (a, b) = (
+            quad.input_to_control(&full).ax_body_mps2,
+            model().input_to_control(&full).ax_body_mps2,
+        );
+        assert!((a - b).abs() < 1e-12);
+    }
+
+    #[test]
+    fn checked_conversion_reports_pitch_beyond_the_tilt_limit() {
+        let quad = model().with_max_tilt(60f64.to_radians());
+
+        let steep = DroneInput::try_new(0.0, 70f64.to_radians(), 0.0).unwrap();
+        let (control, saturation) = quad.input_to_control_checked(&steep);
+        assert_eq!(
+            saturation,
+            Saturation {
+                pitch: true,
+                ..Saturation::default()
+            }
+        );
+        assert!((control.ax_body_mps2 - GRAVITY * 60f64.to_radians().tan()).abs() < 1e-12);
+
+        let gentle = DroneInput::try_new(0.0, 10f64.to_radians(), 0.0).unwrap();
+        let (control, saturation) = quad.input_to_control_checked(&gentle);
+        assert!(!saturation.any());
+        assert_eq!(control, quad.input_to_control(&gentle));
+    }
+}
+
+/// Randomized invariant checks. proptest is not available to this crate, so cases are
+/// drawn from a fixed-seed [`SplitMix64`](crate::rng::SplitMix64) and every failure
+/// reports the seed and case index needed to reproduce it.
+#[cfg(all(test, feature = "rng"))]
+mod invariants {
+    use super::*;
+    use crate::{
+        rng::{Rng, SplitMix64},
+        solvers::{BackwardEuler, ForwardEuler, Rk4},
+        traits::Stepper,
+    };
+
+    const SEED: u64 = 0x5EED_0581;
+    const CASES: usize = 64;
+    const STEPS: usize = 200;
+    const NO_INPUT: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    fn range(rng: &mut SplitMix64, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * rng.uniform()
+    }
+
+    fn random_state(rng: &mut SplitMix64, max_speed: f64) -> SimpleQuadState {
+        SimpleQuadState::new(
+            range(rng, -100.0, 100.0),
+            range(rng, -100.0, 100.0),
+            range(rng, -max_speed, max_speed),
+            range(rng, -max_speed, max_speed),
+            range(rng, -std::f64::consts::PI, std::f64::consts::PI),
+        )
+    }
+
+    fn kinetic(s: &SimpleQuadState) -> f64 {
+        0.5 * (s.v_north_mps * s.v_north_mps + s.v_east_mps * s.v_east_mps)
+    }
+
+    /// Runs `check` on `CASES` random `(model, state, yaw_rate, dt)` draws for every solver.
+    fn for_all_solvers<F>(max_speed: f64, mut check: F)
+    where
+        F: FnMut(&mut dyn Stepper<SimpleQuadcopter>, &SimpleQuadcopter, SimpleQuadState, f64, f64),
+    {
+        let mut rng = SplitMix64::new(SEED);
+        for _ in 0..CASES {
+            let model = SimpleQuadcopter::new(range(&mut rng, 0.01, 2.0));
+            let state = random_state(&mut rng, max_speed);
+            let yaw_rate = range(&mut rng, -2.0, 2.0);
+            let dt = range(&mut rng, 1e-3, 0.1);
+
+            check(&mut ForwardEuler, &model, state, yaw_rate, dt);
+            check(&mut Rk4, &model, state, yaw_rate, dt);
+            check(&mut BackwardEuler::default(), &model, state, yaw_rate, dt);
+        }
+    }
+
+    fn run<F>(
+        solver: &mut dyn Stepper<SimpleQuadcopter>,
+        model: &SimpleQuadcopter,
+        mut state: SimpleQuadState,
+        input: &DroneInput,
+        dt: f64,
+        mut each: F,
+    ) where
+        F: FnMut(&SimpleQuadState, &SimpleQuadState),
+    {
+        let control = model.input_to_control(input);
+        for i in 0..STEPS {
+            let next = solver.step(model, i as f64 * dt, &state, &control, dt);
+            each(&state, &next);
+            state = next;
+        }
+    }
+
+    #[test]
+    fn zero_input_at_rest_never_moves() {
+        let mut case = 0;
+        for_all_solvers(0.0, |solver, model, start, _, dt| {
+            run(solver, model, start, &NO_INPUT, dt, |_, s| {
+                assert_eq!(s.position(), start.position(), "seed {SEED:#x} case {case}");
+                assert_eq!(
+                    s.ground_velocity(),
+                    (0.0, 0.0),
+                    "seed {SEED:#x} case {case}"
+                );
+            });
+            case += 1;
+        });
+    }
+
+    #[test]
+    fn kinetic_energy_non_increasing_without_thrust() {
+        let mut case = 0;
+        for_all_solvers(20.0, |solver, model, start, yaw_rate, dt| {
+            let input = DroneInput {
+                yaw_rate_rps: yaw_rate,
+                ..NO_INPUT
+            };
+            run(solver, model, start, &input, dt, |prev, next| {
+                assert!(
+                    kinetic(next) <= kinetic(prev) * (1.0 + 1e-12),
+                    "seed {SEED:#x} case {case}: {} -> {}",
+                    kinetic(prev),
+                    kinetic(next)
+                );
+            });
+            case += 1;
+        });
+    }
+
+    #[test]
+    fn pure_yaw_rate_at_rest_keeps_position() {
+        let mut case = 0;
+        for_all_solvers(0.0, |solver, model, start, yaw_rate, dt| {
+            let input = DroneInput {
+                yaw_rate_rps: yaw_rate,
+                ..NO_INPUT
+            };
+            let mut last_yaw = start.yaw_rad;
+            run(solver, model, start, &input, dt, |_, s| {
+                assert_eq!(s.position(), start.position(), "seed {SEED:#x} case {case}");
+                assert!(
+                    (s.yaw_rad - last_yaw - yaw_rate * dt).abs() < 1e-9,
+                    "seed {SEED:#x} case {case}"
+                );
+                last_yaw = s.yaw_rad;
+            });
+            case += 1;
+        });
+    }
 }
@@ -0,0 +1,302 @@
+use crate::{
+    math::{rotation_ned_from_body, rotation_ned_from_body_dyaw},
+    traits::{Dynamics, LinearizableDynamics},
+    types::{
+        DroneInput, GroundVelocity, Heading, IntegrableState, Position2D, Saturation,
+        SecondOrderState, StateVector,
+    },
+};
+
+use super::{SimpleQuadControl, SimpleQuadcopter};
+
+/// Planar [`SimpleQuadcopter`] whose yaw rate is a state instead of following the stick
+/// instantly. The rate approaches the command with time constant `yaw_rate_tau_s`, and
+/// its change is clamped to `±max_yaw_accel_rps2`. The vertical channel is not modelled.
+#[derive(Debug, Clone)]
+pub struct YawAccelQuadcopter {
+    pub drag: f64,
+    pub max_yaw_accel_rps2: f64,
+    pub yaw_rate_tau_s: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YawAccelQuadState {
+    pub north_m: f64,
+    pub east_m: f64,
+    pub v_north_mps: f64,
+    pub v_east_mps: f64,
+    pub yaw_rad: f64,
+    pub yaw_rate_rps: f64,
+}
+
+impl YawAccelQuadState {
+    pub fn zero() -> Self {
+        Self {
+            north_m: 0.0,
+            east_m: 0.0,
+            v_north_mps: 0.0,
+            v_east_mps: 0.0,
+            yaw_rad: 0.0,
+            yaw_rate_rps: 0.0,
+        }
+    }
+
+    pub fn ensure_finite(&self) {
+        if let Some(field) = self.non_finite_field() {
+            panic!("{field} must be finite");
+        }
+    }
+}
+
+impl IntegrableState for YawAccelQuadState {
+    fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+        let mut next = *self;
+        next.add_scaled_assign(derivative, scale);
+        next
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.north_m += scale * derivative.north_m;
+        self.east_m += scale * derivative.east_m;
+        self.v_north_mps += scale * derivative.v_north_mps;
+        self.v_east_mps += scale * derivative.v_east_mps;
+        self.yaw_rad += scale * derivative.yaw_rad;
+        self.yaw_rate_rps += scale * derivative.yaw_rate_rps;
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("north_m", self.north_m),
+            ("east_m", self.east_m),
+            ("v_north_mps", self.v_north_mps),
+            ("v_east_mps", self.v_east_mps),
+            ("yaw_rad", self.yaw_rad),
+            ("yaw_rate_rps", self.yaw_rate_rps),
+        ]
+        .into_iter()
+        .find(|(_, v)| !v.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+/// Yaw is a position here, driven by the yaw rate held in the state.
+impl SecondOrderState for YawAccelQuadState {
+    fn position_indices() -> &'static [usize] {
+        &[0, 1, 4]
+    }
+
+    fn velocity_indices() -> &'static [usize] {
+        &[2, 3, 5]
+    }
+
+    fn add_scaled_rates(&self, derivative: &Self, scale: f64) -> Self {
+        Self {
+            v_north_mps: self.v_north_mps + scale * derivative.v_north_mps,
+            v_east_mps: self.v_east_mps + scale * derivative.v_east_mps,
+            yaw_rate_rps: self.yaw_rate_rps + scale * derivative.yaw_rate_rps,
+            ..*self
+        }
+    }
+
+    fn advance_positions(&self, scale: f64) -> Self {
+        Self {
+            north_m: self.north_m + scale * self.v_north_mps,
+            east_m: self.east_m + scale * self.v_east_mps,
+            yaw_rad: self.yaw_rad + scale * self.yaw_rate_rps,
+            ..*self
+        }
+    }
+}
+
+impl Position2D for YawAccelQuadState {
+    fn position(&self) -> (f64, f64) {
+        (self.north_m, self.east_m)
+    }
+}
+
+impl GroundVelocity for YawAccelQuadState {
+    fn ground_velocity(&self) -> (f64, f64) {
+        (self.v_north_mps, self.v_east_mps)
+    }
+}
+
+impl Heading for YawAccelQuadState {
+    fn yaw_rad(&self) -> f64 {
+        self.yaw_rad
+    }
+
+    fn with_yaw(&self, yaw_rad: f64) -> Self {
+        Self { yaw_rad, ..*self }
+    }
+}
+
+impl StateVector for YawAccelQuadState {
+    fn to_dvector(&self) -> nalgebra::DVector<f64> {
+        nalgebra::DVector::from_vec(vec![
+            self.north_m,
+            self.east_m,
+            self.v_north_mps,
+            self.v_east_mps,
+            self.yaw_rad,
+            self.yaw_rate_rps,
+        ])
+    }
+
+    fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
+        assert!(
+            v.len() == 6,
+            "YawAccelQuadState expects 6 elements (north, east, v_north, v_east, yaw, yaw_rate)"
+        );
+        Self {
+            north_m: v[0],
+            east_m: v[1],
+            v_north_mps: v[2],
+            v_east_mps: v[3],
+            yaw_rad: v[4],
+            yaw_rate_rps: v[5],
+        }
+    }
+}
+
+impl YawAccelQuadcopter {
+    /// Unclamped rate response and whether the acceleration limit is active.
+    fn yaw_accel(&self, state: &YawAccelQuadState, control: &SimpleQuadControl) -> (f64, bool) {
+        let demanded = (control.yaw_rate_rps - state.yaw_rate_rps) / self.yaw_rate_tau_s;
+        let limit = self.max_yaw_accel_rps2;
+        (demanded.clamp(-limit, limit), demanded.abs() > limit)
+    }
+}
+
+impl Dynamics for YawAccelQuadcopter {
+    type State = YawAccelQuadState;
+    /// `yaw_rate_rps` is the commanded rate; `az_down_mps2` is ignored.
+    type Control = SimpleQuadControl;
+
+    fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+        SimpleQuadcopter::new(self.drag).input_to_control(input)
+    }
+
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        SimpleQuadcopter::new(self.drag).input_to_control_checked(input)
+    }
+
+    fn derivative(&self, _t: f64, state: &Self::State, control: &Self::Control) -> Self::State {
+        state.ensure_finite();
+        assert!(
+            self.max_yaw_accel_rps2 > 0.0 && self.yaw_rate_tau_s > 0.0,
+            "yaw acceleration limit and time constant must be > 0"
+        );
+
+        let a_ned = rotation_ned_from_body(state.yaw_rad) * control.accel_body();
+        let (yaw_accel, _) = self.yaw_accel(state, control);
+
+        YawAccelQuadState {
+            north_m: state.v_north_mps,                           // north_dot
+            east_m: state.v_east_mps,                             // east_dot
+            v_north_mps: a_ned.x - self.drag * state.v_north_mps, // v_north_dot
+            v_east_mps: a_ned.y - self.drag * state.v_east_mps,   // v_east_dot
+            yaw_rad: state.yaw_rate_rps,                          // yaw_dot
+            yaw_rate_rps: yaw_accel,                              // yaw_rate_dot
+        }
+    }
+}
+
+impl LinearizableDynamics for YawAccelQuadcopter {
+    fn jacobian(
+        &self,
+        _t: f64,
+        state: &Self::State,
+        control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64> {
+        let da_dyaw = rotation_ned_from_body_dyaw(state.yaw_rad) * control.accel_body();
+        let (dax_dyaw, day_dyaw) = (da_dyaw.x, da_dyaw.y);
+
+        // Saturated acceleration no longer depends on the current rate
+        let (_, saturated) = self.yaw_accel(state, control);
+        let drate = if saturated {
+            0.0
+        } else {
+            -1.0 / self.yaw_rate_tau_s
+        };
+        let drag = -self.drag;
+
+        nalgebra::DMatrix::from_row_slice(
+            6,
+            6,
+            &[
+                0.0, 0.0, 1.0, 0.0, 0.0, 0.0, // d(north_dot)/d(state)
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, // d(east_dot)/d(state)
+                0.0, 0.0, drag, 0.0, dax_dyaw, 0.0, // d(v_n_dot)/d(state)
+                0.0, 0.0, 0.0, drag, day_dyaw, 0.0, // d(v_e_dot)/d(state)
+                0.0, 0.0, 0.0, 0.0, 0.0, 1.0, // d(yaw_dot)/d(state)
+                0.0, 0.0, 0.0, 0.0, 0.0, drate, // d(yaw_rate_dot)/d(state)
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dynamic_models::assert_jacobian_matches_fd, predict::predict, solvers::Rk4};
+
+    fn model() -> YawAccelQuadcopter {
+        YawAccelQuadcopter {
+            drag: 0.1,
+            max_yaw_accel_rps2: 2.0,
+            yaw_rate_tau_s: 0.1,
+        }
+    }
+
+    fn input(yaw_rate_rps: f64) -> DroneInput {
+        DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps,
+            thrust_norm: 0.5,
+        }
+    }
+
+    #[test]
+    fn step_yaw_command_ramps_heading_rate() {
+        let prediction = predict(
+            &input(1.0),
+            YawAccelQuadState::zero(),
+            &model(),
+            &mut Rk4,
+            0.0,
+            3.0,
+            3000,
+        );
+
+        // Acceleration-limited ramp at first: 2 rad/s² for the first ~0.45 s
+        let at = |t: f64| &prediction.states[(t / prediction.dt()).round() as usize];
+        assert!((at(0.1).yaw_rate_rps - 0.2).abs() < 1e-9);
+        assert!((at(0.3).yaw_rate_rps - 0.6).abs() < 1e-9);
+        assert!((at(0.1).yaw_rad - 0.5 * 2.0 * 0.01).abs() < 1e-9);
+
+        // ...then settles on the command
+        assert!((prediction.final_state().yaw_rate_rps - 1.0).abs() < 1e-6);
+        assert!(
+            prediction
+                .states
+                .windows(2)
+                .all(|w| w[1].yaw_rate_rps >= w[0].yaw_rate_rps)
+        );
+    }
+
+    #[test]
+    fn jacobian_matches_finite_difference() {
+        for (cmd, rate) in [(0.5, 0.45), (3.0, 0.0)] {
+            let state = YawAccelQuadState {
+                v_north_mps: 2.0,
+                v_east_mps: -1.0,
+                yaw_rad: 0.7,
+                yaw_rate_rps: rate,
+                ..YawAccelQuadState::zero()
+            };
+            let control = model().input_to_control(&input(cmd));
+            assert_jacobian_matches_fd(&model(), &state, &control, 1e-6);
+        }
+    }
+}
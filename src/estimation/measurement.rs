@@ -0,0 +1,87 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::types::{GroundVelocity, Position2D, StateVector};
+
+/// Observation model `z = h(x)` for the estimators: what a sensor reports about a state
+/// and how that report changes with it.
+pub trait Measurement<S> {
+    /// Predicted measurement `h(state)`.
+    fn measure(&self, state: &S) -> DVector<f64>;
+
+    /// Jacobian `∂h/∂x` at `state`, one row per measured value and one column per
+    /// [`StateVector`] component.
+    fn jacobian(&self, state: &S) -> DMatrix<f64>;
+}
+
+/// `(north, east)` position fix [m], e.g. from GNSS.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PositionMeasurement;
+
+/// `(north, east, v_north, v_east)` position and ground velocity fix [m, m/s].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PositionVelocityMeasurement;
+
+impl<S: Position2D + StateVector> Measurement<S> for PositionMeasurement {
+    fn measure(&self, state: &S) -> DVector<f64> {
+        let (n, e) = state.position();
+        DVector::from_vec(vec![n, e])
+    }
+
+    fn jacobian(&self, state: &S) -> DMatrix<f64> {
+        linear_jacobian(self, state)
+    }
+}
+
+impl<S: Position2D + GroundVelocity + StateVector> Measurement<S> for PositionVelocityMeasurement {
+    fn measure(&self, state: &S) -> DVector<f64> {
+        let (n, e) = state.position();
+        let (vn, ve) = state.ground_velocity();
+        DVector::from_vec(vec![n, e, vn, ve])
+    }
+
+    fn jacobian(&self, state: &S) -> DMatrix<f64> {
+        linear_jacobian(self, state)
+    }
+}
+
+/// Jacobian of a measurement that is linear in the state vector, where unit
+/// perturbations give every column exactly.
+fn linear_jacobian<S, H>(h: &H, state: &S) -> DMatrix<f64>
+where
+    S: StateVector,
+    H: Measurement<S>,
+{
+    let x = state.to_dvector();
+    let z0 = h.measure(state);
+    let mut jac = DMatrix::zeros(z0.len(), x.len());
+    for k in 0..x.len() {
+        let mut shifted = x.clone();
+        shifted[k] += 1.0;
+        jac.set_column(k, &(h.measure(&S::from_dvector(shifted)) - &z0));
+    }
+    jac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_models::SimpleQuadState;
+
+    #[test]
+    fn position_measurement_selects_north_east() {
+        let state = SimpleQuadState::new(3.0, -4.0, 1.5, 0.5, 0.2).with_vertical(-10.0, 0.3);
+
+        let z = PositionMeasurement.measure(&state);
+        assert_eq!(z.as_slice(), &[3.0, -4.0]);
+        let jac = PositionMeasurement.jacobian(&state);
+        assert_eq!(jac.shape(), (2, 7));
+        assert_eq!(jac, DMatrix::identity(2, 7));
+
+        let z = PositionVelocityMeasurement.measure(&state);
+        assert_eq!(z.as_slice(), &[3.0, -4.0, 1.5, 0.5]);
+        assert_eq!(
+            PositionVelocityMeasurement.jacobian(&state),
+            DMatrix::identity(4, 7)
+        );
+    }
+}
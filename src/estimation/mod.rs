@@ -0,0 +1,9 @@
+mod measurement;
+mod observability;
+#[cfg(feature = "rng")]
+mod particle_filter;
+
+pub use measurement::{Measurement, PositionMeasurement, PositionVelocityMeasurement};
+pub use observability::{is_observable, observability_matrix, observability_rank};
+#[cfg(feature = "rng")]
+pub use particle_filter::ParticleFilter;
@@ -0,0 +1,272 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    rng::{Rng, SplitMix64},
+    solvers::EulerMaruyama,
+    traits::{Dynamics, Stepper},
+    types::{Position2D, StateVector},
+};
+
+/// Bootstrap particle filter over a model's state, for non-Gaussian (e.g. multimodal)
+/// intent estimation. Particles are propagated with [`EulerMaruyama`] under the
+/// `diffusion` matrix, reweighted against `(north, east)` position fixes with an
+/// isotropic Gaussian likelihood, and resampled systematically whenever the effective
+/// sample size drops below half the particle count.
+#[derive(Clone, Debug)]
+pub struct ParticleFilter<M: Dynamics, R = SplitMix64> {
+    pub model: M,
+    pub diffusion: DMatrix<f64>,
+    /// Position measurement standard deviation [m], per axis.
+    pub measurement_std_m: f64,
+    particles: Vec<M::State>,
+    weights: Vec<f64>,
+    rng: R,
+}
+
+impl<M> ParticleFilter<M>
+where
+    M: Dynamics,
+    M::State: StateVector + Position2D,
+{
+    /// Seeded with the crate's [`SplitMix64`].
+    pub fn new(
+        model: M,
+        particles: Vec<M::State>,
+        diffusion: DMatrix<f64>,
+        measurement_std_m: f64,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(
+            model,
+            particles,
+            diffusion,
+            measurement_std_m,
+            SplitMix64::new(seed),
+        )
+    }
+}
+
+impl<M, R> ParticleFilter<M, R>
+where
+    M: Dynamics,
+    M::State: StateVector + Position2D,
+    R: Rng,
+{
+    /// Start from equally weighted `particles`, e.g. samples of the prior.
+    pub fn with_rng(
+        model: M,
+        particles: Vec<M::State>,
+        diffusion: DMatrix<f64>,
+        measurement_std_m: f64,
+        rng: R,
+    ) -> Self {
+        assert!(!particles.is_empty(), "need at least one particle");
+        assert!(
+            measurement_std_m.is_finite() && measurement_std_m > 0.0,
+            "measurement_std_m must be finite and > 0"
+        );
+
+        let n = particles.len();
+        Self {
+            model,
+            diffusion,
+            measurement_std_m,
+            particles,
+            weights: vec![1.0 / n as f64; n],
+            rng,
+        }
+    }
+
+    pub fn particles(&self) -> &[M::State] {
+        &self.particles
+    }
+
+    /// Normalised particle weights, summing to one.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// `1 / Σ w²`: the number of particles effectively carrying the estimate.
+    pub fn effective_sample_size(&self) -> f64 {
+        1.0 / self.weights.iter().map(|w| w * w).sum::<f64>()
+    }
+
+    /// Propagate every particle from `t` to `t + dt` under `control`.
+    pub fn predict(&mut self, control: &M::Control, t: f64, dt: f64) {
+        let mut integrator = EulerMaruyama::with_rng(self.diffusion.clone(), &mut self.rng);
+        for p in &mut self.particles {
+            *p = integrator.step(&self.model, t, p, control, dt);
+        }
+    }
+
+    /// Reweight against a position fix `(north, east)` [m] and resample if degenerate.
+    pub fn update(&mut self, measurement: (f64, f64)) {
+        let inv_var = 1.0 / (self.measurement_std_m * self.measurement_std_m);
+        let log_likelihood: Vec<f64> = self
+            .particles
+            .iter()
+            .map(|p| {
+                let (n, e) = p.position();
+                let d2 = (n - measurement.0).powi(2) + (e - measurement.1).powi(2);
+                -0.5 * d2 * inv_var
+            })
+            .collect();
+
+        // Shift by the max log-likelihood so distant fixes don't underflow to all zeros
+        let max = log_likelihood
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        for (w, l) in self.weights.iter_mut().zip(&log_likelihood) {
+            *w *= (l - max).exp();
+        }
+        let total: f64 = self.weights.iter().sum();
+        assert!(total > 0.0, "all particle weights vanished");
+        self.weights.iter_mut().for_each(|w| *w /= total);
+
+        if self.effective_sample_size() < 0.5 * self.particles.len() as f64 {
+            self.resample();
+        }
+    }
+
+    /// Systematic resampling: one uniform offset, `N` evenly spaced pointers into the
+    /// cumulative weights. Weights are reset to uniform.
+    pub fn resample(&mut self) {
+        let n = self.particles.len();
+        let step = 1.0 / n as f64;
+        let offset = self.rng.uniform() * step;
+
+        let mut resampled = Vec::with_capacity(n);
+        let (mut i, mut cumulative) = (0, self.weights[0]);
+        for k in 0..n {
+            let pointer = offset + k as f64 * step;
+            while pointer > cumulative && i + 1 < n {
+                i += 1;
+                cumulative += self.weights[i];
+            }
+            resampled.push(self.particles[i].clone());
+        }
+
+        self.particles = resampled;
+        self.weights.fill(step);
+    }
+
+    /// Weighted mean of the particles.
+    pub fn mean(&self) -> DVector<f64> {
+        self.particles
+            .iter()
+            .zip(&self.weights)
+            .map(|(p, w)| p.to_dvector() * *w)
+            .reduce(|a, b| a + b)
+            .expect("need at least one particle")
+    }
+
+    /// Weighted covariance of the particles about [`mean`](Self::mean).
+    pub fn covariance(&self) -> DMatrix<f64> {
+        let mean = self.mean();
+        let dim = mean.len();
+        self.particles
+            .iter()
+            .zip(&self.weights)
+            .fold(DMatrix::zeros(dim, dim), |acc, (p, w)| {
+                let d = p.to_dvector() - &mean;
+                acc + &d * d.transpose() * *w
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::Rk4,
+        types::DroneInput,
+    };
+
+    #[test]
+    fn converges_from_bimodal_prior() {
+        let model = SimpleQuadcopter::new(0.1);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 5f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let control = model.input_to_control(&input);
+        let (dt, steps) = (0.1, 60);
+
+        // Truth flies north; the prior can't tell north from south
+        let truth_start = SimpleQuadState::new(0.0, 0.0, 3.0, 0.0, 0.0);
+        let truth = predict(
+            &input,
+            truth_start,
+            &model,
+            &mut Rk4,
+            0.0,
+            dt * steps as f64,
+            steps,
+        );
+
+        let mut rng = SplitMix64::new(5);
+        let prior: Vec<SimpleQuadState> = (0..600)
+            .map(|i| {
+                let v_north = if i % 2 == 0 { 3.0 } else { -3.0 };
+                SimpleQuadState::new(
+                    2.0 * rng.standard_normal(),
+                    2.0 * rng.standard_normal(),
+                    v_north + 0.5 * rng.standard_normal(),
+                    0.5 * rng.standard_normal(),
+                    0.0,
+                )
+            })
+            .collect();
+
+        let mut diffusion = DMatrix::zeros(7, 2);
+        diffusion[(2, 0)] = 0.3;
+        diffusion[(3, 1)] = 0.3;
+        let mut pf = ParticleFilter::new(model, prior, diffusion, 0.5, 9);
+
+        let initial_spread = pf.covariance()[(2, 2)];
+        assert!(pf.mean()[2].abs() < 0.5, "prior mean velocity is ambiguous");
+
+        let mut noise = SplitMix64::new(17);
+        for i in 0..steps {
+            pf.predict(&control, i as f64 * dt, dt);
+            let (n, e) = truth.states[i + 1].position();
+            pf.update((
+                n + 0.5 * noise.standard_normal(),
+                e + 0.5 * noise.standard_normal(),
+            ));
+        }
+
+        let mean = pf.mean();
+        let end = truth.final_state();
+        assert!((mean[0] - end.north_m).abs() < 0.5, "north {}", mean[0]);
+        assert!((mean[1] - end.east_m).abs() < 0.5, "east {}", mean[1]);
+        assert!(
+            (mean[2] - end.v_north_mps).abs() < 0.5,
+            "v_north {}",
+            mean[2]
+        );
+        assert!(pf.covariance()[(2, 2)] < 0.05 * initial_spread);
+        assert!((pf.weights().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn systematic_resampling_follows_weights() {
+        let model = SimpleQuadcopter::new(0.1);
+        let particles = vec![
+            SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, 0.0),
+            SimpleQuadState::new(10.0, 0.0, 0.0, 0.0, 0.0),
+            SimpleQuadState::new(-10.0, 0.0, 0.0, 0.0, 0.0),
+        ];
+        let mut pf = ParticleFilter::new(model, particles, DMatrix::zeros(7, 2), 1.0, 1);
+
+        // A fix at the second particle leaves the others with negligible weight
+        pf.update((10.0, 0.0));
+        assert!(pf.particles().iter().all(|p| p.north_m == 10.0));
+        assert_eq!(pf.weights(), &[1.0 / 3.0; 3]);
+    }
+}
@@ -0,0 +1,202 @@
+// Aviation math. NED convention throughout: angles from North, positive clockwise;
+// velocities as (north, east) pairs.
+
+/// Ground velocity from the air-relative velocity and the wind.
+///
+/// `heading_rad` is where the nose points, `wind` is the velocity of the air mass
+/// `(north, east)` [m/s] (the direction the wind blows *towards*).
+/// Returns `(ground_speed, track_rad)`.
+pub fn wind_triangle(
+    airspeed: f64,
+    heading_rad: f64,
+    wind_north: f64,
+    wind_east: f64,
+) -> (f64, f64) {
+    let vn = airspeed * heading_rad.cos() + wind_north;
+    let ve = airspeed * heading_rad.sin() + wind_east;
+    (vn.hypot(ve), ve.atan2(vn))
+}
+
+/// Heading that makes good `desired_track_rad` at `airspeed` in `wind` `(north, east)`,
+/// i.e. the track corrected by the wind-correction (crab) angle.
+/// Returns `None` when the crosswind exceeds the airspeed or the resulting ground speed
+/// along the track would not be positive.
+pub fn required_heading(desired_track_rad: f64, airspeed: f64, wind: (f64, f64)) -> Option<f64> {
+    let (s, c) = desired_track_rad.sin_cos();
+    let (wn, we) = wind;
+
+    // Wind components across (positive = pushing right of track) and along the track
+    let cross = -wn * s + we * c;
+    let along = wn * c + we * s;
+
+    if cross.abs() > airspeed {
+        return None;
+    }
+    let correction = (cross / airspeed).asin();
+    if airspeed * correction.cos() + along <= 0.0 {
+        return None;
+    }
+
+    Some(desired_track_rad - correction)
+}
+
+/// Decompose `point` relative to the reference line from `line_start` to `line_end`
+/// (all `(north, east)` [m]). Returns `(along, cross)`: the distance along the line
+/// from `line_start`, and the signed offset across it (positive = right of track).
+pub fn cross_track_error(
+    point: (f64, f64),
+    line_start: (f64, f64),
+    line_end: (f64, f64),
+) -> (f64, f64) {
+    let (dn, de) = (line_end.0 - line_start.0, line_end.1 - line_start.1);
+    let length = dn.hypot(de);
+    assert!(length > 0.0, "reference line must have distinct endpoints");
+    let (c, s) = (dn / length, de / length);
+
+    let (rn, re) = (point.0 - line_start.0, point.1 - line_start.1);
+    (rn * c + re * s, -rn * s + re * c)
+}
+
+const WGS84_A: f64 = 6_378_137.0;
+const WGS84_E2: f64 = 6.694_379_990_14e-3;
+
+/// Latitude/longitude [deg] of a local NED offset `(north_m, east_m)` from an origin,
+/// using the WGS84 radii of curvature at the origin (flat-earth tangent plane; fine
+/// for offsets of a few tens of km).
+pub fn georeference(
+    north_m: f64,
+    east_m: f64,
+    origin_lat_deg: f64,
+    origin_lon_deg: f64,
+) -> (f64, f64) {
+    let lat0 = origin_lat_deg.to_radians();
+    let w = (1.0 - WGS84_E2 * lat0.sin().powi(2)).sqrt();
+    let meridian = WGS84_A * (1.0 - WGS84_E2) / (w * w * w);
+    let prime_vertical = WGS84_A / w;
+
+    let lat = origin_lat_deg + (north_m / meridian).to_degrees();
+    let lon = origin_lon_deg + (east_m / (prime_vertical * lat0.cos())).to_degrees();
+    (lat, lon)
+}
+
+/// Mean Earth radius [m] (IUGG), for the spherical formulas below.
+const MEAN_EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Great-circle distance [m] between two latitude/longitude points [deg] (haversine on
+/// a spherical Earth, within about 0.5% of the ellipsoid). Unlike [`georeference`] it
+/// stays valid over any range.
+pub fn haversine_distance(lat1_deg: f64, lon1_deg: f64, lat2_deg: f64, lon2_deg: f64) -> f64 {
+    let (lat1, lat2) = (lat1_deg.to_radians(), lat2_deg.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (lon2_deg - lon1_deg).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * MEAN_EARTH_RADIUS_M * h.sqrt().min(1.0).asin()
+}
+
+/// Initial bearing [rad] of the great circle from the first point to the second (both
+/// latitude/longitude [deg]), clockwise from North like the model headings, in
+/// `(-pi, pi]`. The bearing changes along the way except on meridians and the equator.
+pub fn initial_bearing(lat1_deg: f64, lon1_deg: f64, lat2_deg: f64, lon2_deg: f64) -> f64 {
+    let (lat1, lat2) = (lat1_deg.to_radians(), lat2_deg.to_radians());
+    let dlon = (lon2_deg - lon1_deg).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    y.atan2(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOT: f64 = 0.514_444;
+
+    #[test]
+    fn textbook_crab_angle() {
+        // Track 360° at 100 kt TAS, wind from 270° at 20 kt (blowing towards East)
+        let airspeed = 100.0 * KNOT;
+        let wind = (0.0, 20.0 * KNOT);
+
+        let heading = required_heading(0.0, airspeed, wind).unwrap();
+        let wca_deg = heading.to_degrees();
+        assert!((wca_deg - -11.537).abs() < 1e-3);
+
+        let (gs, track) = wind_triangle(airspeed, heading, wind.0, wind.1);
+        assert!(track.abs() < 1e-12);
+        assert!((gs / KNOT - 97.980).abs() < 1e-3);
+    }
+
+    #[test]
+    fn headwind_only_reduces_ground_speed() {
+        let (gs, track) = wind_triangle(50.0, 0.0, -10.0, 0.0);
+        assert!((gs - 40.0).abs() < 1e-12);
+        assert!(track.abs() < 1e-12);
+        assert_eq!(required_heading(0.0, 50.0, (-10.0, 0.0)), Some(0.0));
+    }
+
+    #[test]
+    fn crosswind_stronger_than_airspeed_has_no_solution() {
+        assert_eq!(required_heading(0.0, 10.0, (0.0, 15.0)), None);
+        assert_eq!(required_heading(0.0, 10.0, (-20.0, 0.0)), None);
+    }
+
+    #[test]
+    fn cross_track_sign_follows_right_of_track() {
+        // Track towards North-East; a point due East of the start lies right of it
+        let (along, cross) = cross_track_error((0.0, 2.0), (0.0, 0.0), (10.0, 10.0));
+        assert!((along - 2f64.sqrt()).abs() < 1e-12);
+        assert!((cross - 2f64.sqrt()).abs() < 1e-12);
+
+        let (along, cross) = cross_track_error((-3.0, -1.0), (0.0, 0.0), (5.0, 0.0));
+        assert_eq!((along, cross), (-3.0, -1.0));
+    }
+
+    #[test]
+    fn georeference_degree_lengths() {
+        // At the equator one degree of latitude spans ~110.574 km, of longitude ~111.320 km
+        let (lat, lon) = georeference(110_574.0, 0.0, 0.0, 10.0);
+        assert!((lat - 1.0).abs() < 1e-4 && lon == 10.0);
+        let (lat, lon) = georeference(0.0, 111_320.0, 0.0, 10.0);
+        assert!(lat == 0.0 && (lon - 11.0).abs() < 1e-4);
+
+        // Longitude degrees shrink with cos(latitude)
+        let (_, lon) = georeference(0.0, 1000.0, 60.0, 0.0);
+        let (_, lon_eq) = georeference(0.0, 1000.0, 0.0, 0.0);
+        assert!((lon / lon_eq - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn city_pair_distances_and_bearings() {
+        const LONDON: (f64, f64) = (51.5074, -0.1278);
+        const PARIS: (f64, f64) = (48.8566, 2.3522);
+        const NEW_YORK: (f64, f64) = (40.7128, -74.0060);
+        const LOS_ANGELES: (f64, f64) = (34.0522, -118.2437);
+
+        let d = haversine_distance(LONDON.0, LONDON.1, PARIS.0, PARIS.1);
+        assert!((d / 343_500.0 - 1.0).abs() < 0.01, "London-Paris {d}");
+        let d = haversine_distance(NEW_YORK.0, NEW_YORK.1, LOS_ANGELES.0, LOS_ANGELES.1);
+        assert!(
+            (d / 3_936_000.0 - 1.0).abs() < 0.01,
+            "New York-Los Angeles {d}"
+        );
+
+        let b = initial_bearing(LONDON.0, LONDON.1, PARIS.0, PARIS.1).to_degrees();
+        assert!((b - 148.1).abs() < 0.5, "London-Paris bearing {b}");
+        let b = initial_bearing(PARIS.0, PARIS.1, LONDON.0, LONDON.1).to_degrees();
+        assert!(
+            b < 0.0 && (b + 30.0).abs() < 1.0,
+            "Paris-London bearing {b}"
+        );
+
+        // Along the equator the bearing is due East and a degree is ~111.2 km
+        let b = initial_bearing(0.0, 10.0, 0.0, 11.0);
+        assert!((b - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((haversine_distance(0.0, 10.0, 0.0, 11.0) - 111_195.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn haversine_agrees_with_georeference_at_short_range() {
+        let (lat, lon) = georeference(3000.0, 4000.0, 52.0, 5.0);
+        let d = haversine_distance(52.0, 5.0, lat, lon);
+        assert!((d / 5000.0 - 1.0).abs() < 0.005, "{d}");
+    }
+}
@@ -0,0 +1,261 @@
+// Interacting-multiple-model style blending: a fixed set of behaviour modes, each a
+// model driven by a constant input, weighted by how well it explains observed paths.
+
+use nalgebra::DVector;
+
+use crate::{
+    compare::compare_trajectories,
+    predict::{Prediction, predict},
+    traits::{Dynamics, Stepper},
+    types::{DroneInput, Position2D, StateVector},
+};
+
+/// One behaviour hypothesis, e.g. "straight", "turning left" or "hover".
+#[derive(Debug, Clone)]
+pub struct ImmMode<M> {
+    pub name: &'static str,
+    pub model: M,
+    pub input: DroneInput,
+}
+
+/// Per-mode predictions and their probability-weighted mean, see [`ImmPredictor::predict`].
+#[derive(Debug, Clone)]
+pub struct ImmPrediction<S, U> {
+    /// One prediction per mode, in mode order.
+    pub modes: Vec<Prediction<S, U>>,
+    /// Mode probabilities used for the blend.
+    pub probabilities: Vec<f64>,
+    /// Component-wise weighted mean state at each sample. Angles are averaged linearly,
+    /// so the mean yaw is only meaningful while the modes stay within ±π of each other.
+    pub mean: Vec<S>,
+}
+
+/// Bank of behaviour modes with probabilities updated from observed trajectories.
+#[derive(Debug, Clone)]
+pub struct ImmPredictor<M> {
+    /// Per-sample XY position noise [m] of the observations, setting how sharply the
+    /// likelihood separates the modes.
+    pub measurement_std_m: f64,
+    /// Probability of leaving the current mode between updates, spread evenly over the
+    /// others. Keeps every mode recoverable after a long run of evidence against it.
+    pub switch_probability: f64,
+    modes: Vec<ImmMode<M>>,
+    probabilities: Vec<f64>,
+}
+
+impl<M: Dynamics> ImmPredictor<M> {
+    /// Uniform prior over `modes`.
+    pub fn new(modes: Vec<ImmMode<M>>, measurement_std_m: f64, switch_probability: f64) -> Self {
+        assert!(!modes.is_empty(), "need at least one mode");
+        assert!(measurement_std_m > 0.0, "measurement_std_m must be > 0");
+        assert!(
+            (0.0..=1.0).contains(&switch_probability),
+            "switch_probability must be in [0, 1]"
+        );
+        let n = modes.len();
+        Self {
+            measurement_std_m,
+            switch_probability,
+            modes,
+            probabilities: vec![1.0 / n as f64; n],
+        }
+    }
+
+    pub fn modes(&self) -> &[ImmMode<M>] {
+        &self.modes
+    }
+
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Name and probability of the currently most likely mode.
+    pub fn most_likely(&self) -> (&'static str, f64) {
+        let (k, p) = self
+            .probabilities
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        (self.modes[k].name, *p)
+    }
+
+    /// Bayesian update from an observed path spanning `t_final`, evenly sampled. Each
+    /// mode is predicted from `observed[0]` and scored with a Gaussian likelihood of its
+    /// summed squared position error (via [`compare_trajectories`]); the prior is first
+    /// mixed by [`switch_probability`](Self::switch_probability).
+    pub fn update<St>(&mut self, observed: &[M::State], solver: &mut St, t_final: f64)
+    where
+        M::State: Position2D,
+        St: Stepper<M>,
+    {
+        assert!(observed.len() >= 2, "need at least 2 observed states");
+        let steps = observed.len() - 1;
+        let n = self.modes.len();
+
+        let log_posterior: Vec<f64> = self
+            .modes
+            .iter()
+            .zip(&self.probabilities)
+            .map(|(mode, p)| {
+                let prediction = predict(
+                    &mode.input,
+                    observed[0].clone(),
+                    &mode.model,
+                    solver,
+                    0.0,
+                    t_final,
+                    steps,
+                );
+                let rmse = compare_trajectories(observed, &prediction.states, false).rmse_m;
+                let sum_sq = rmse * rmse * observed.len() as f64;
+
+                let prior = if n > 1 {
+                    (1.0 - self.switch_probability) * p
+                        + self.switch_probability * (1.0 - p) / (n - 1) as f64
+                } else {
+                    *p
+                };
+                prior.ln() - 0.5 * sum_sq / self.measurement_std_m.powi(2)
+            })
+            .collect();
+
+        // Normalize in log space so sharply separated modes do not underflow to 0 / 0
+        let max = log_posterior
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = log_posterior.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        self.probabilities = weights.iter().map(|w| w / total).collect();
+    }
+
+    /// Predict every mode from `initial_state` and blend the states with the current
+    /// mode probabilities.
+    pub fn predict<St>(
+        &self,
+        initial_state: &M::State,
+        solver: &mut St,
+        t0: f64,
+        t_final: f64,
+        steps: usize,
+    ) -> ImmPrediction<M::State, M::Control>
+    where
+        M::State: StateVector,
+        St: Stepper<M>,
+    {
+        let modes: Vec<_> = self
+            .modes
+            .iter()
+            .map(|mode| {
+                predict(
+                    &mode.input,
+                    initial_state.clone(),
+                    &mode.model,
+                    solver,
+                    t0,
+                    t_final,
+                    steps,
+                )
+            })
+            .collect();
+
+        let mean = (0..=steps)
+            .map(|i| {
+                let blended = modes
+                    .iter()
+                    .zip(&self.probabilities)
+                    .fold(None, |acc: Option<DVector<f64>>, (prediction, p)| {
+                        let weighted = prediction.states[i].to_dvector() * *p;
+                        Some(match acc {
+                            Some(sum) => sum + weighted,
+                            None => weighted,
+                        })
+                    })
+                    .unwrap();
+                M::State::from_dvector(blended)
+            })
+            .collect();
+
+        ImmPrediction {
+            modes,
+            probabilities: self.probabilities.clone(),
+            mean,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        solvers::Rk4,
+    };
+
+    const STRAIGHT: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.2,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+    const TURN: DroneInput = DroneInput {
+        yaw_rate_rps: 0.6,
+        ..STRAIGHT
+    };
+
+    fn bank() -> ImmPredictor<SimpleQuadcopter> {
+        let mode = |name, input| ImmMode {
+            name,
+            model: SimpleQuadcopter::new(0.2),
+            input,
+        };
+        ImmPredictor::new(
+            vec![mode("straight", STRAIGHT), mode("turning", TURN)],
+            0.5,
+            0.05,
+        )
+    }
+
+    #[test]
+    fn curved_observation_raises_turning_probability() {
+        let mut imm = bank();
+        let start = SimpleQuadState::new(0.0, 0.0, 4.0, 0.0, 0.0);
+        assert_eq!(imm.probabilities(), &[0.5, 0.5]);
+
+        let observed = predict(&TURN, start, &imm.modes()[1].model, &mut Rk4, 0.0, 3.0, 30);
+        imm.update(&observed.states, &mut Rk4, 3.0);
+        assert!(imm.probabilities()[1] > 0.99, "{:?}", imm.probabilities());
+        assert!((imm.probabilities().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(imm.most_likely().0, "turning");
+
+        // The switch probability keeps the straight mode recoverable
+        let observed = predict(
+            &STRAIGHT,
+            start,
+            &imm.modes()[0].model,
+            &mut Rk4,
+            0.0,
+            3.0,
+            30,
+        );
+        imm.update(&observed.states, &mut Rk4, 3.0);
+        assert_eq!(imm.most_likely().0, "straight");
+    }
+
+    #[test]
+    fn mean_prediction_is_the_weighted_blend() {
+        let imm = bank();
+        let start = SimpleQuadState::new(0.0, 0.0, 4.0, 0.0, 0.0);
+        let blended = imm.predict(&start, &mut Rk4, 0.0, 3.0, 30);
+
+        assert_eq!(blended.mean.len(), 31);
+        let (a, b) = (
+            blended.modes[0].final_state().to_dvector(),
+            blended.modes[1].final_state().to_dvector(),
+        );
+        let expected = (a + b) * 0.5;
+        assert!((blended.mean[30].to_dvector() - expected).amax() < 1e-12);
+        assert_eq!(blended.mean[0], start);
+    }
+}
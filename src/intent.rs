@@ -0,0 +1,802 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    compare::compare_trajectories,
+    predict::{predict, predict_with_sensitivity},
+    traits::{Dynamics, LinearizableDynamics, Stepper},
+    types::{ControlChannels, DroneInput, InputLimits, Position2D, StateVector},
+};
+
+/// Constant pilot input that best explains an observed path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntentEstimate {
+    pub input: DroneInput,
+    /// Sum of squared position errors [m²] between the observation and the prediction.
+    pub residual: f64,
+}
+
+const MAX_ITER: usize = 50;
+const FD_STEP: f64 = 1e-6;
+const MIN_STEP_NORM: f64 = 1e-10;
+const DAMPING: f64 = 1e-9;
+/// Singular values below this fraction of the largest are treated as zero in
+/// [`min_norm_step`].
+const SVD_RELATIVE_EPS: f64 = 1e-12;
+/// Steps of the long run [`solve_bvp`] searches for a better horizon.
+const HORIZON_SEARCH_STEPS: usize = 2000;
+
+/// Estimate the constant `DroneInput` whose prediction from `observed[0]` best matches
+/// the observed positions in the least-squares sense, assuming the samples are evenly
+/// spaced over `t_final`. Uses Gauss-Newton with a finite-difference Jacobian of the
+/// position residuals with respect to (roll, pitch, yaw rate), halving steps that
+/// would increase the cost. For linearizable models,
+/// [`infer_input_with_sensitivity`] takes the Jacobian from the forward sensitivities
+/// instead.
+pub fn infer_input<M, St>(
+    observed: &[M::State],
+    model: &M,
+    solver: &mut St,
+    t_final: f64,
+) -> IntentEstimate
+where
+    M: Dynamics,
+    M::State: Position2D,
+    St: Stepper<M>,
+{
+    assert!(observed.len() >= 2, "need at least 2 observed states");
+    let steps = observed.len() - 1;
+
+    let residuals = |p: &DVector<f64>| -> DVector<f64> {
+        let prediction = predict(
+            &to_input(p),
+            observed[0].clone(),
+            model,
+            solver,
+            0.0,
+            t_final,
+            steps,
+        );
+        position_residuals(&prediction.states, observed)
+    };
+
+    gauss_newton(residuals, |residuals, p| {
+        let columns = [0, 1, 2].map(|k| {
+            let mut hi = p.clone();
+            let mut lo = p.clone();
+            hi[k] += FD_STEP;
+            lo[k] -= FD_STEP;
+            (residuals(&hi) - residuals(&lo)) / (2.0 * FD_STEP)
+        });
+        DMatrix::from_columns(&columns)
+    })
+}
+
+/// [`infer_input`] with RK4 and the Jacobian of the position residuals taken from the
+/// forward sensitivities of [`predict_with_sensitivity`]. The Jacobian is then the
+/// derivative of the residuals being fitted rather than a finite-difference estimate
+/// of it, so Gauss-Newton converges as it would on the exact problem, at the cost of
+/// integrating the sensitivities alongside the state.
+pub fn infer_input_with_sensitivity<M>(
+    observed: &[M::State],
+    model: &M,
+    t_final: f64,
+) -> IntentEstimate
+where
+    M: LinearizableDynamics,
+    M::State: Position2D + StateVector,
+    M::Control: ControlChannels,
+{
+    assert!(observed.len() >= 2, "need at least 2 observed states");
+    let steps = observed.len() - 1;
+    let selector = position_selector(&observed[0]);
+    let run = |p: &DVector<f64>| {
+        predict_with_sensitivity(
+            &to_input(p),
+            observed[0].clone(),
+            model,
+            0.0,
+            t_final,
+            steps,
+        )
+    };
+
+    gauss_newton(
+        |p| position_residuals(&run(p).prediction.states, observed),
+        |_, p| {
+            let sens = run(p);
+            let mut jac = DMatrix::zeros(2 * observed.len(), 3);
+            for (i, s) in sens.sensitivities.iter().enumerate() {
+                jac.view_mut((2 * i, 0), (2, 3)).copy_from(&(&selector * s));
+            }
+            jac
+        },
+    )
+}
+
+/// Gauss-Newton over (roll, pitch, yaw rate) from the zero input, halving steps that
+/// would increase the cost. `jacobian` is handed `residuals` so it can difference it.
+fn gauss_newton<R, J>(mut residuals: R, mut jacobian: J) -> IntentEstimate
+where
+    R: FnMut(&DVector<f64>) -> DVector<f64>,
+    J: FnMut(&mut R, &DVector<f64>) -> DMatrix<f64>,
+{
+    let mut p = DVector::zeros(3);
+    let mut r = residuals(&p);
+    let mut cost = r.norm_squared();
+
+    for _ in 0..MAX_ITER {
+        let jac = jacobian(&mut residuals, &p);
+
+        // Small Levenberg-style damping keeps the normal equations solvable when a
+        // channel is momentarily unobservable (e.g. yaw rate while not accelerating)
+        let jt = jac.transpose();
+        let mut jtj = &jt * &jac;
+        let damping = DAMPING * jtj.diagonal().amax().max(1.0);
+        for k in 0..3 {
+            jtj[(k, k)] += damping;
+        }
+        let Some(delta) = jtj.lu().solve(&(-(&jt * &r))) else {
+            break;
+        };
+
+        // Backtrack until the cost decreases
+        let mut scale = 1.0;
+        let mut accepted = false;
+        while scale * delta.norm() > MIN_STEP_NORM {
+            let candidate = &p + &delta * scale;
+            let r_candidate = residuals(&candidate);
+            let cost_candidate = r_candidate.norm_squared();
+            if cost_candidate < cost {
+                p = candidate;
+                r = r_candidate;
+                cost = cost_candidate;
+                accepted = true;
+                break;
+            }
+            scale *= 0.5;
+        }
+
+        if !accepted {
+            break;
+        }
+    }
+
+    IntentEstimate {
+        input: to_input(&p),
+        residual: cost,
+    }
+}
+
+/// Score each candidate input by the RMS position error [m] of its prediction from
+/// `observed[0]` against the observed path, best (lowest) first.
+pub fn rank_intents<M, St>(
+    observed: &[M::State],
+    candidates: &[DroneInput],
+    model: &M,
+    solver: &mut St,
+    t_final: f64,
+) -> Vec<(DroneInput, f64)>
+where
+    M: Dynamics,
+    M::State: Position2D,
+    St: Stepper<M>,
+{
+    assert!(observed.len() >= 2, "need at least 2 observed states");
+    let steps = observed.len() - 1;
+
+    let mut ranked: Vec<(DroneInput, f64)> = candidates
+        .iter()
+        .map(|input| {
+            let prediction = predict(
+                input,
+                observed[0].clone(),
+                model,
+                solver,
+                0.0,
+                t_final,
+                steps,
+            );
+            let r = position_residuals(&prediction.states, observed);
+            (*input, (r.norm_squared() / observed.len() as f64).sqrt())
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+    ranked
+}
+
+/// Confidence in `[0, 1]` that `predicted` explains `observed`: `exp(-rms / sigma_m)`
+/// of the RMS position residual from
+/// [`compare_trajectories`](crate::compare::compare_trajectories), so a residual of
+/// `sigma_m` metres scores about 37%. 1 means an exact fit.
+pub fn intent_confidence<S: Position2D>(observed: &[S], predicted: &[S], sigma_m: f64) -> f64 {
+    assert!(
+        sigma_m.is_finite() && sigma_m > 0.0,
+        "sigma_m must be finite and > 0"
+    );
+    let rms = compare_trajectories(observed, predicted, false).rmse_m;
+    (-rms / sigma_m).exp()
+}
+
+/// Partial derivatives `(dN, dE)` of the final XY position with respect to roll, pitch
+/// and yaw rate (in that order) at `input`, by central finite differences. Channels
+/// with small sensitivities are poorly observable to [`infer_input`].
+pub fn endpoint_sensitivity<M, St>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut St,
+    t_final: f64,
+    steps: usize,
+) -> [(f64, f64); 3]
+where
+    M: Dynamics,
+    M::State: Position2D,
+    St: Stepper<M>,
+{
+    let mut endpoint = |input: &DroneInput| {
+        predict(
+            input,
+            initial_state.clone(),
+            model,
+            solver,
+            0.0,
+            t_final,
+            steps,
+        )
+        .final_state()
+        .position()
+    };
+
+    let perturbed = |k: usize, h: f64| {
+        let mut p = *input;
+        match k {
+            0 => p.roll_rad += h,
+            1 => p.pitch_rad += h,
+            _ => p.yaw_rate_rps += h,
+        }
+        p
+    };
+
+    std::array::from_fn(|k| {
+        let (n_hi, e_hi) = endpoint(&perturbed(k, FD_STEP));
+        let (n_lo, e_lo) = endpoint(&perturbed(k, -FD_STEP));
+        (
+            (n_hi - n_lo) / (2.0 * FD_STEP),
+            (e_hi - e_lo) / (2.0 * FD_STEP),
+        )
+    })
+}
+
+/// Settings for [`solve_bvp`].
+#[derive(Debug, Clone, Copy)]
+pub struct BvpOpts {
+    /// Horizon [s] the shooting iteration starts from.
+    pub initial_t_final: f64,
+    /// Stick limits the solution must respect.
+    pub limits: InputLimits,
+    /// Accepted endpoint miss distance [m].
+    pub tolerance_m: f64,
+    pub max_iter: usize,
+    /// Longest horizon [s] searched when no shorter one looks reachable, see
+    /// [`solve_bvp`].
+    pub max_t_final: f64,
+}
+
+impl Default for BvpOpts {
+    fn default() -> Self {
+        Self {
+            initial_t_final: 5.0,
+            limits: InputLimits {
+                max_tilt_rad: 35f64.to_radians(),
+                max_yaw_rate_rps: 1.0,
+            },
+            tolerance_m: 1e-6,
+            max_iter: 50,
+            max_t_final: 120.0,
+        }
+    }
+}
+
+/// Constant input and horizon found by [`solve_bvp`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BvpSolution {
+    pub input: DroneInput,
+    pub t_final: f64,
+    /// Remaining distance [m] between the endpoint and the target.
+    pub miss_m: f64,
+}
+
+/// Why [`solve_bvp`] found no input reaching the target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BvpError {
+    /// The iteration stalled with parameters held at the stick limits: even the step
+    /// over the remaining free parameters and the horizon no longer reduces the miss,
+    /// so the target is out of reach under saturation.
+    Unreachable { miss_m: f64 },
+    /// No step reduced the miss further away from the limits, or `max_iter` was
+    /// reached.
+    NotConverged { miss_m: f64 },
+}
+
+impl fmt::Display for BvpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreachable { miss_m } => {
+                write!(
+                    f,
+                    "target unreachable within input limits (miss {miss_m} m)"
+                )
+            }
+            Self::NotConverged { miss_m } => {
+                write!(f, "shooting did not converge (miss {miss_m} m)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BvpError {}
+
+/// Find a constant input (roll, pitch, yaw rate; hover throttle) and horizon that take
+/// `start` to `target_xy` (north, east) [m] by shooting: minimum-norm Newton steps on
+/// the endpoint miss, with the Jacobian from [`predict_with_sensitivity`] plus the
+/// endpoint velocity for the horizon. The problem is underdetermined, so the solution
+/// is the one reached from the zero-input guess, not unique.
+///
+/// Stick limits are handled with an active set: a parameter at its limit that the step
+/// would push further out is held there, and the step is taken over the free
+/// parameters and the horizon only. A step that crosses a limit part way is projected
+/// back onto it.
+///
+/// The miss along the horizon need not be monotone (braking first carries the vehicle
+/// away from a target behind it), so when the step asks for a non-positive horizon the
+/// horizon restarts from the closest approach along a run of `opts.max_t_final`.
+///
+/// The miss is evaluated with `solver`, but the sensitivities always come from the RK4
+/// integration of [`predict_with_sensitivity`]. With another solver the Jacobian is
+/// that of a slightly different discretization: it still steers the iteration, but
+/// convergence may be slower than quadratic.
+pub fn solve_bvp<M, St>(
+    start: &M::State,
+    target_xy: (f64, f64),
+    model: &M,
+    solver: &mut St,
+    steps: usize,
+    opts: BvpOpts,
+) -> Result<BvpSolution, BvpError>
+where
+    M: LinearizableDynamics,
+    M::State: Position2D + StateVector,
+    M::Control: ControlChannels,
+    St: Stepper<M>,
+{
+    assert!(
+        opts.initial_t_final.is_finite() && opts.initial_t_final > 0.0,
+        "initial_t_final must be finite and > 0"
+    );
+    assert!(
+        opts.max_t_final.is_finite() && opts.max_t_final > 0.0,
+        "max_t_final must be finite and > 0"
+    );
+
+    let clamp = |p: &DVector<f64>| -> DVector<f64> {
+        let tilt = opts.limits.max_tilt_rad;
+        let yaw = opts.limits.max_yaw_rate_rps;
+        DVector::from_vec(vec![
+            p[0].clamp(-tilt, tilt),
+            p[1].clamp(-tilt, tilt),
+            p[2].clamp(-yaw, yaw),
+            p[3],
+        ])
+    };
+    let bounds = [
+        opts.limits.max_tilt_rad,
+        opts.limits.max_tilt_rad,
+        opts.limits.max_yaw_rate_rps,
+    ];
+
+    let endpoint = |state: &M::State| {
+        let (n, e) = state.position();
+        DVector::from_vec(vec![n - target_xy.0, e - target_xy.1])
+    };
+    let selector = position_selector(start);
+
+    let mut run = |p: &DVector<f64>, t_final: f64, steps: usize| {
+        predict(
+            &to_input(p),
+            start.clone(),
+            model,
+            solver,
+            0.0,
+            t_final,
+            steps,
+        )
+    };
+
+    let mut p = DVector::from_vec(vec![0.0, 0.0, 0.0, opts.initial_t_final]);
+    let mut r = endpoint(run(&p, p[3], steps).final_state());
+    let mut stalled_at_limit = false;
+
+    for _ in 0..opts.max_iter {
+        if r.norm() <= opts.tolerance_m {
+            return Ok(BvpSolution {
+                input: to_input(&p),
+                t_final: p[3],
+                miss_m: r.norm(),
+            });
+        }
+
+        let input = to_input(&p);
+        let sens = predict_with_sensitivity(&input, start.clone(), model, 0.0, p[3], steps);
+        let end = sens.prediction.final_state();
+        let control = model.input_to_control(&input);
+        let velocity = model.derivative(p[3], end, &control).to_dvector();
+
+        let mut jac = DMatrix::zeros(2, 4);
+        let s_end = &sens.sensitivities[steps];
+        jac.view_mut((0, 0), (2, 3)).copy_from(&(&selector * s_end));
+        jac.set_column(3, &(&selector * velocity));
+
+        // Grow the active set until no free parameter at a limit is pushed outward
+        let mut active = [false; 3];
+        let delta = loop {
+            let delta = min_norm_step(&jac, &r, &active);
+            let pushed_out: Vec<usize> = (0..3)
+                .filter(|&k| !active[k] && p[k].abs() >= bounds[k] && delta[k] * p[k] > 0.0)
+                .collect();
+            if pushed_out.is_empty() {
+                break delta;
+            }
+            for k in pushed_out {
+                active[k] = true;
+            }
+        };
+
+        if p[3] + delta[3] <= 0.0 {
+            let search = run(&p, opts.max_t_final, HORIZON_SEARCH_STEPS);
+            let (_, t_closest, _) = search.closest_approach(target_xy);
+            if t_closest > 0.0 {
+                let mut restart = p.clone();
+                restart[3] = t_closest;
+                let r_restart = endpoint(run(&restart, t_closest, steps).final_state());
+                if r_restart.norm() < r.norm() {
+                    p = restart;
+                    r = r_restart;
+                    continue;
+                }
+            }
+        }
+
+        // Backtrack until the miss decreases, keeping the horizon positive
+        let mut scale = 1.0;
+        let mut accepted = false;
+        while scale * delta.norm() > MIN_STEP_NORM {
+            let candidate = clamp(&(&p + &delta * scale));
+            if candidate[3] > 0.0 {
+                let r_candidate = endpoint(run(&candidate, candidate[3], steps).final_state());
+                if r_candidate.norm() < r.norm() {
+                    p = candidate;
+                    r = r_candidate;
+                    accepted = true;
+                    break;
+                }
+            }
+            scale *= 0.5;
+        }
+        if !accepted {
+            stalled_at_limit = active.contains(&true);
+            break;
+        }
+    }
+
+    let miss_m = r.norm();
+    if miss_m <= opts.tolerance_m {
+        Ok(BvpSolution {
+            input: to_input(&p),
+            t_final: p[3],
+            miss_m,
+        })
+    } else if stalled_at_limit {
+        Err(BvpError::Unreachable { miss_m })
+    } else {
+        Err(BvpError::NotConverged { miss_m })
+    }
+}
+
+/// Minimum-norm least-squares step `δ` with `J δ ≈ -r` over the parameters not flagged
+/// in `active` (the first columns of `jac`), which keep `δ = 0`. Solved through the SVD,
+/// so the reduced problem may be rank deficient, e.g. with only the horizon left free.
+fn min_norm_step(jac: &DMatrix<f64>, r: &DVector<f64>, active: &[bool]) -> DVector<f64> {
+    let mut reduced = jac.clone();
+    for (k, _) in active.iter().enumerate().filter(|(_, held)| **held) {
+        reduced.column_mut(k).fill(0.0);
+    }
+    let svd = reduced.svd(true, true);
+    let eps = SVD_RELATIVE_EPS * svd.singular_values.max();
+    svd.solve(&-r, eps).expect("SVD computed with both U and V")
+}
+
+/// The selector `∂(n, e)/∂x` of the position within the state vector, exact for a
+/// linear projection. `state` only fixes the dimension.
+fn position_selector<S: Position2D + StateVector>(state: &S) -> DMatrix<f64> {
+    let x = state.to_dvector();
+    let (n, e) = state.position();
+    DMatrix::from_fn(2, x.len(), |i, k| {
+        let mut shifted = x.clone();
+        shifted[k] += 1.0;
+        let (sn, se) = S::from_dvector(shifted).position();
+        [sn - n, se - e][i]
+    })
+}
+
+/// Stacked `[dn_0, de_0, dn_1, de_1, ...]` position differences `predicted - observed`.
+fn position_residuals<S: Position2D>(predicted: &[S], observed: &[S]) -> DVector<f64> {
+    let mut r = DVector::zeros(2 * observed.len());
+    for (i, (pred, obs)) in predicted.iter().zip(observed).enumerate() {
+        let (pn, pe) = pred.position();
+        let (on, oe) = obs.position();
+        r[2 * i] = pn - on;
+        r[2 * i + 1] = pe - oe;
+    }
+    r
+}
+
+fn to_input(p: &DVector<f64>) -> DroneInput {
+    DroneInput {
+        roll_rad: p[0],
+        pitch_rad: p[1],
+        yaw_rate_rps: p[2],
+        thrust_norm: 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{DubinsCar, DubinsState, SimpleQuadState, SimpleQuadcopter},
+        solvers::Rk4,
+        traits::GRAVITY,
+    };
+
+    #[test]
+    fn confidence_falls_monotonically_with_the_fit_error() {
+        let model = SimpleQuadcopter::new(0.2);
+        let run = |pitch: f64| {
+            let input = DroneInput::try_new(0.0, pitch, 0.1).unwrap();
+            predict(
+                &input,
+                SimpleQuadState::zero(),
+                &model,
+                &mut Rk4,
+                0.0,
+                10.0,
+                50,
+            )
+            .states
+        };
+        let observed = run(0.1);
+
+        assert!((intent_confidence(&observed, &observed, 2.0) - 1.0).abs() < 1e-12);
+        let scores: Vec<f64> = [0.1, 0.11, 0.13, 0.2, 0.5, -0.5]
+            .iter()
+            .map(|&pitch| intent_confidence(&observed, &run(pitch), 2.0))
+            .collect();
+        assert!(scores.windows(2).all(|w| w[1] < w[0]), "scores {scores:?}");
+        assert!(scores.iter().all(|c| (0.0..=1.0).contains(c)));
+        assert!(*scores.last().unwrap() < 1e-6);
+    }
+
+    #[test]
+    fn pitch_dominates_north_sensitivity_at_zero_yaw() {
+        let model = SimpleQuadcopter::new(0.2);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 8f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::zero();
+
+        let [roll, pitch, yaw_rate] =
+            endpoint_sensitivity(&input, start, &model, &mut Rk4, 5.0, 100);
+
+        assert!(pitch.0 > 10.0 * roll.0.abs());
+        assert!(pitch.0 > 10.0 * yaw_rate.0.abs());
+        // Roll only moves the endpoint east at zero yaw
+        assert!(roll.1 > 10.0 * pitch.1.abs());
+
+        // Pitch-to-north matches the analytic derivative of the drag-limited endpoint:
+        // N(T) = g tan(p) / d * (T - (1 - e^{-dT}) / d)
+        let (d, t) = (0.2, 5.0);
+        let dn_dp =
+            GRAVITY / (d * input.pitch_rad.cos().powi(2)) * (t - (1.0 - (-d * t).exp()) / d);
+        assert!((pitch.0 - dn_dp).abs() < 1e-5 * dn_dp);
+    }
+
+    #[test]
+    fn bvp_reaches_waypoint_from_forward_run() {
+        let model = SimpleQuadcopter::new(0.2);
+        let truth = DroneInput {
+            roll_rad: 6f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.3);
+        let waypoint = predict(&truth, start, &model, &mut Rk4, 0.0, 6.0, 300)
+            .final_state()
+            .position();
+
+        let solution =
+            solve_bvp(&start, waypoint, &model, &mut Rk4, 300, BvpOpts::default()).unwrap();
+        assert!(solution.miss_m <= 1e-6);
+
+        let reached = predict(
+            &solution.input,
+            start,
+            &model,
+            &mut Rk4,
+            0.0,
+            solution.t_final,
+            300,
+        )
+        .final_state()
+        .position();
+        assert!((reached.0 - waypoint.0).hypot(reached.1 - waypoint.1) < 1e-6);
+        let limits = BvpOpts::default().limits;
+        assert!(solution.input.pitch_rad.abs() <= limits.max_tilt_rad);
+        assert!(solution.input.roll_rad.abs() <= limits.max_tilt_rad);
+    }
+
+    #[test]
+    fn bvp_holds_saturated_pitch_and_solves_for_the_horizon() {
+        let model = SimpleQuadcopter::new(0.5);
+        let opts = BvpOpts {
+            initial_t_final: 2.0,
+            limits: InputLimits {
+                max_tilt_rad: 5f64.to_radians(),
+                max_yaw_rate_rps: 0.1,
+            },
+            max_iter: 20,
+            ..BvpOpts::default()
+        };
+        // Behind the start with barely any tilt authority: full nose-up brake, held
+        // long enough for the reverse drift to carry it back
+        let start = SimpleQuadState::new(0.0, 0.0, 10.0, 0.0, 0.0);
+        let solution = solve_bvp(&start, (-50.0, 0.0), &model, &mut Rk4, 100, opts).unwrap();
+
+        assert_eq!(solution.input.pitch_rad, -opts.limits.max_tilt_rad);
+        assert!(solution.input.roll_rad.abs() < 1e-9);
+        assert!(solution.input.yaw_rate_rps.abs() < 1e-9);
+        // N(T) = v_ss T + (v0 - v_ss)(1 - e^{-dT}) / d with v_ss = g tan(pitch) / d
+        let v_ss = GRAVITY * solution.input.pitch_rad.tan() / 0.5;
+        let t = solution.t_final;
+        let north = v_ss * t + (10.0 - v_ss) * (1.0 - (-0.5 * t).exp()) / 0.5;
+        assert!((north + 50.0).abs() < 1e-3, "t_final {t}, north {north}");
+    }
+
+    #[test]
+    fn bvp_reports_target_inside_the_turn_radius_unreachable() {
+        // Constant-rate turns at 10 m/s can't tighten below a 20 m radius; the closest
+        // arc passes (10, 10) at 20 - |(10, 10) - (0, 20)| ≈ 5.86 m
+        let model = DubinsCar {
+            speed_mps: 10.0,
+            max_yaw_rate_rps: 0.5,
+        };
+        let opts = BvpOpts {
+            limits: InputLimits {
+                max_tilt_rad: 35f64.to_radians(),
+                max_yaw_rate_rps: 0.5,
+            },
+            initial_t_final: 1.0,
+            ..BvpOpts::default()
+        };
+        let result = solve_bvp(
+            &DubinsState::new(0.0, 0.0, 0.0),
+            (10.0, 10.0),
+            &model,
+            &mut Rk4,
+            200,
+            opts,
+        );
+        let expected = 20.0 - 200f64.sqrt();
+        assert!(
+            matches!(result, Err(BvpError::Unreachable { miss_m }) if (miss_m - expected).abs() < 1e-3),
+            "{result:?}"
+        );
+    }
+
+    /// Path flown on a constant input, with deterministic pseudo-noise of a few
+    /// centimeters on the positions.
+    fn noisy_path(model: &SimpleQuadcopter) -> (DroneInput, Vec<SimpleQuadState>) {
+        let truth = DroneInput {
+            roll_rad: 4f64.to_radians(),
+            pitch_rad: 8f64.to_radians(),
+            yaw_rate_rps: 0.15,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.5, 0.3);
+
+        let mut observed = predict(&truth, start, model, &mut Rk4, 0.0, 5.0, 100).states;
+        for (i, s) in observed.iter_mut().enumerate().skip(1) {
+            s.north_m += 0.03 * (i as f64 * 1.7).sin();
+            s.east_m += 0.03 * (i as f64 * 2.3).cos();
+        }
+        (truth, observed)
+    }
+
+    #[test]
+    fn recovers_input_from_noisy_path() {
+        let model = SimpleQuadcopter::new(0.2);
+        let (truth, observed) = noisy_path(&model);
+
+        let estimate = infer_input(&observed, &model, &mut Rk4, 5.0);
+
+        assert!((estimate.input.roll_rad - truth.roll_rad).abs() < 0.2f64.to_radians());
+        assert!((estimate.input.pitch_rad - truth.pitch_rad).abs() < 0.2f64.to_radians());
+        assert!((estimate.input.yaw_rate_rps - truth.yaw_rate_rps).abs() < 2e-3);
+        // Residual is on the order of the injected noise
+        assert!(estimate.residual < 2.0 * 101.0 * 0.03 * 0.03);
+    }
+
+    #[test]
+    fn sensitivity_jacobian_reaches_the_finite_difference_fit() {
+        let model = SimpleQuadcopter::new(0.2);
+        let (_, observed) = noisy_path(&model);
+
+        let numeric = infer_input(&observed, &model, &mut Rk4, 5.0);
+        let analytic = infer_input_with_sensitivity(&observed, &model, 5.0);
+
+        let [a, n] = [analytic.input, numeric.input];
+        assert!((a.roll_rad - n.roll_rad).abs() < 1e-6, "{a:?} vs {n:?}");
+        assert!((a.pitch_rad - n.pitch_rad).abs() < 1e-6, "{a:?} vs {n:?}");
+        assert!(
+            (a.yaw_rate_rps - n.yaw_rate_rps).abs() < 1e-6,
+            "{a:?} vs {n:?}"
+        );
+        assert!(analytic.residual <= numeric.residual * (1.0 + 1e-9));
+    }
+
+    #[test]
+    fn true_input_ranks_first_in_candidate_grid() {
+        let model = SimpleQuadcopter::new(0.2);
+        let truth = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        };
+        let observed = predict(
+            &truth,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            4.0,
+            80,
+        )
+        .states;
+
+        let mut candidates = Vec::new();
+        for pitch_deg in [0.0, 5.0, 10.0, 15.0] {
+            for yaw_rate in [-0.2, 0.0, 0.2, 0.4] {
+                candidates.push(DroneInput {
+                    roll_rad: 0.0,
+                    pitch_rad: f64::to_radians(pitch_deg),
+                    yaw_rate_rps: yaw_rate,
+                    thrust_norm: 0.5,
+                });
+            }
+        }
+
+        let ranked = rank_intents(&observed, &candidates, &model, &mut Rk4, 4.0);
+
+        assert_eq!(ranked.len(), candidates.len());
+        assert_eq!(ranked[0].0, truth);
+        assert!(ranked[0].1 < 1e-12);
+        assert!(ranked.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+}
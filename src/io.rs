@@ -0,0 +1,472 @@
+//! Reading and writing predictions: a compact binary snapshot for caching, and KML
+//! and CSV exports for inspection.
+//!
+//! The snapshot is a hand-rolled little-endian format rather than `serde` with
+//! `bincode`, so the crate keeps its dependency list and needs no `serde` feature or
+//! derives on every state and control type; it goes through [`StateVector`] and
+//! [`ControlChannels`] instead. The file starts with a magic tag carrying the format
+//! version, and [`Prediction::load_binary`] rejects any other version.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    time::Duration,
+};
+
+use nalgebra::DVector;
+
+use crate::{
+    frames::georeference,
+    predict::{Accumulators, Prediction},
+    types::{ControlChannels, GroundVelocity, Heading, Position2D, StateVector},
+};
+
+/// Leading bytes of a file written by [`Prediction::save_binary`]; the last byte is the
+/// format version.
+const MAGIC: &[u8; 8] = b"PIPRED\x00\x02";
+
+impl<S: StateVector, U: ControlChannels> Prediction<S, U> {
+    /// Write a compact little-endian binary snapshot: `t0`, `t_final`, `cpu_time` (as
+    /// nanoseconds), `control`, the `controls` schedule, every state, then the sample
+    /// times of a non-uniform grid and the accumulators, each behind a count that is
+    /// zero when absent.
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let state_dim = self.states.first().map_or(0, |s| s.to_dvector().len());
+        let control = self.control.channels();
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        put_u64(&mut buf, state_dim as u64);
+        put_u64(&mut buf, control.len() as u64);
+        put_u64(&mut buf, self.states.len() as u64);
+        put_u64(&mut buf, self.controls.len() as u64);
+        put_f64(&mut buf, self.t0);
+        put_f64(&mut buf, self.t_final);
+        put_u64(&mut buf, self.cpu_time.as_nanos() as u64);
+
+        control.iter().for_each(|&v| put_f64(&mut buf, v));
+        for (t, u) in &self.controls {
+            put_f64(&mut buf, *t);
+            u.channels().iter().for_each(|&v| put_f64(&mut buf, v));
+        }
+        for s in &self.states {
+            let v = s.to_dvector();
+            assert!(
+                v.len() == state_dim,
+                "state dimension changed along the prediction"
+            );
+            v.iter().for_each(|&x| put_f64(&mut buf, x));
+        }
+
+        let times = self.sample_times.as_deref().unwrap_or_default();
+        put_u64(&mut buf, times.len() as u64);
+        times.iter().for_each(|&t| put_f64(&mut buf, t));
+        let (arc, effort) = self.accumulators.as_ref().map_or((&[][..], &[][..]), |a| {
+            (&a.arc_length_m[..], &a.control_effort[..])
+        });
+        assert!(
+            arc.len() == effort.len(),
+            "accumulator series have different lengths"
+        );
+        put_u64(&mut buf, arc.len() as u64);
+        arc.iter().chain(effort).for_each(|&v| put_f64(&mut buf, v));
+
+        fs::File::create(path)?.write_all(&buf)
+    }
+
+    /// Read a prediction written by [`Prediction::save_binary`]. Values round-trip exactly.
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut bytes)?;
+        let mut r = Reader { bytes: &bytes };
+
+        if r.take(MAGIC.len())? != MAGIC {
+            return Err(invalid("not a saved prediction"));
+        }
+        let state_dim = r.len()?;
+        let control_dim = r.len()?;
+        let n_states = r.len()?;
+        let n_controls = r.len()?;
+        let t0 = r.f64()?;
+        let t_final = r.f64()?;
+        let cpu_time = Duration::from_nanos(r.u64()?);
+
+        let control = U::from_channels(&r.f64s(control_dim)?);
+        let mut controls = Vec::with_capacity(n_controls);
+        for _ in 0..n_controls {
+            let t = r.f64()?;
+            controls.push((t, U::from_channels(&r.f64s(control_dim)?)));
+        }
+        let mut states = Vec::with_capacity(n_states);
+        for _ in 0..n_states {
+            states.push(S::from_dvector(DVector::from_vec(r.f64s(state_dim)?)));
+        }
+        let n_times = r.len()?;
+        let sample_times = if n_times > 0 {
+            Some(r.f64s(n_times)?)
+        } else {
+            None
+        };
+        let n_acc = r.len()?;
+        let accumulators = if n_acc > 0 {
+            Some(Accumulators {
+                arc_length_m: r.f64s(n_acc)?,
+                control_effort: r.f64s(n_acc)?,
+            })
+        } else {
+            None
+        };
+        if !r.bytes.is_empty() {
+            return Err(invalid("trailing bytes after prediction"));
+        }
+
+        Ok(Prediction {
+            states,
+            control,
+            controls,
+            t0,
+            t_final,
+            cpu_time,
+            accumulators,
+            sample_times,
+        })
+    }
+}
+
+/// KML document with a single `Placemark` holding the XY path as a `LineString` of
+/// `lon,lat,alt` tuples, georeferenced from `(origin_lat, origin_lon)` [deg] with
+/// [`georeference`]. The path is drawn at constant `altitude_m` above the ground.
+pub fn prediction_to_kml<S: Position2D, U>(
+    prediction: &Prediction<S, U>,
+    origin_lat: f64,
+    origin_lon: f64,
+    altitude_m: f64,
+) -> String {
+    let coordinates: Vec<String> = prediction
+        .states
+        .iter()
+        .map(|s| {
+            let (north, east) = s.position();
+            let (lat, lon) = georeference(north, east, origin_lat, origin_lon);
+            format!("{lon:.8},{lat:.8},{altitude_m:.2}")
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+  <Placemark>
+    <name>Predicted path</name>
+    <LineString>
+      <altitudeMode>relativeToGround</altitudeMode>
+      <coordinates>
+        {}
+      </coordinates>
+    </LineString>
+  </Placemark>
+</kml>
+"#,
+        coordinates.join("\n        ")
+    )
+}
+
+const CSV_COLUMNS: &str = "t_s,north_m,east_m,v_north_mps,v_east_mps,yaw_rad";
+
+/// Stream `prediction` to `writer` as CSV, one row per state with the header
+/// `t_s,north_m,east_m,v_north_mps,v_east_mps,yaw_rad`, at full precision.
+pub fn prediction_to_csv<S, U, W>(prediction: &Prediction<S, U>, mut writer: W) -> io::Result<()>
+where
+    S: Position2D + GroundVelocity + Heading,
+    W: Write,
+{
+    writeln!(writer, "{CSV_COLUMNS}")?;
+    write_csv_rows(prediction, "", &mut writer)
+}
+
+/// Stream a whole ensemble to `writer` as one long-format CSV: the
+/// [`prediction_to_csv`] columns prefixed with `sample_id`, the index of the
+/// prediction in `predictions`. Rows are written as they are formatted, so memory
+/// stays flat however large the ensemble; wrap files in a [`std::io::BufWriter`].
+pub fn ensemble_to_csv<S, U, W>(predictions: &[Prediction<S, U>], mut writer: W) -> io::Result<()>
+where
+    S: Position2D + GroundVelocity + Heading,
+    W: Write,
+{
+    writeln!(writer, "sample_id,{CSV_COLUMNS}")?;
+    for (id, prediction) in predictions.iter().enumerate() {
+        write_csv_rows(prediction, &format!("{id},"), &mut writer)?;
+    }
+    Ok(())
+}
+
+fn write_csv_rows<S, U, W>(
+    prediction: &Prediction<S, U>,
+    prefix: &str,
+    writer: &mut W,
+) -> io::Result<()>
+where
+    S: Position2D + GroundVelocity + Heading,
+    W: Write,
+{
+    for (i, s) in prediction.states.iter().enumerate() {
+        let (n, e) = s.position();
+        let (vn, ve) = s.ground_velocity();
+        writeln!(
+            writer,
+            "{prefix}{:e},{n:e},{e:e},{vn:e},{ve:e},{:e}",
+            prediction.t_at(i),
+            s.yaw_rad()
+        )?;
+    }
+    Ok(())
+}
+
+fn put_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_f64(buf: &mut Vec<u8>, v: f64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Cursor over the remaining bytes of a snapshot.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() < n {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated prediction",
+            ));
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// A count, rejected if it could not possibly fit in the remaining bytes.
+    fn len(&mut self) -> io::Result<usize> {
+        let n = self.u64()?;
+        if n > self.bytes.len() as u64 {
+            return Err(invalid("length exceeds file size"));
+        }
+        Ok(n as usize)
+    }
+
+    fn f64(&mut self) -> io::Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f64s(&mut self, n: usize) -> io::Result<Vec<f64>> {
+        (0..n).map(|_| self.f64()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadControl, SimpleQuadState, SimpleQuadcopter},
+        predict::{HorizonConfig, predict, predict_cfg_with_accumulators, predict_sequence},
+        solvers::Rk4,
+        types::DroneInput,
+    };
+
+    fn out_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("pilots_intent_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn ensemble_csv_has_one_row_per_state_tagged_by_sample() {
+        let model = SimpleQuadcopter::new(0.1);
+        let predictions: Vec<_> = [10, 20, 5]
+            .iter()
+            .enumerate()
+            .map(|(k, &steps)| {
+                let input = DroneInput::try_new(0.0, 0.1, 0.1 * k as f64).unwrap();
+                predict(
+                    &input,
+                    SimpleQuadState::zero(),
+                    &model,
+                    &mut Rk4,
+                    0.0,
+                    2.0,
+                    steps,
+                )
+            })
+            .collect();
+
+        let mut buf = Vec::new();
+        ensemble_to_csv(&predictions, &mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("sample_id,t_s,north_m,east_m,v_north_mps,v_east_mps,yaw_rad")
+        );
+
+        let rows: Vec<Vec<f64>> = lines
+            .map(|l| l.split(',').map(|v| v.parse().unwrap()).collect())
+            .collect();
+        let total: usize = predictions.iter().map(|p| p.states.len()).sum();
+        assert_eq!(rows.len(), total);
+
+        let mut row = rows.iter();
+        for (id, prediction) in predictions.iter().enumerate() {
+            for (i, s) in prediction.states.iter().enumerate() {
+                let r = row.next().unwrap();
+                assert_eq!(r[0], id as f64);
+                assert_eq!(r[1], prediction.t_at(i));
+                assert_eq!((r[2], r[3], r[6]), (s.north_m, s.east_m, s.yaw_rad));
+            }
+        }
+
+        // The single-prediction export is the same rows without the id column
+        let mut single = Vec::new();
+        prediction_to_csv(&predictions[1], &mut single).unwrap();
+        let single = String::from_utf8(single).unwrap();
+        let tagged: Vec<&str> = csv.lines().filter_map(|l| l.strip_prefix("1,")).collect();
+        assert_eq!(single.lines().skip(1).collect::<Vec<_>>(), tagged);
+    }
+
+    #[test]
+    fn binary_round_trip_is_exact() {
+        let turn = DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.6,
+        };
+        let straight = DroneInput {
+            yaw_rate_rps: 0.0,
+            ..turn
+        };
+        let prediction = predict_sequence(
+            &[(0.0, turn), (1.5, straight)],
+            SimpleQuadState::new(1.0, 2.0, 3.0, 4.0, 0.5),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.25,
+            3.0,
+            300,
+        );
+
+        let path = out_path("prediction.bin");
+        prediction.save_binary(&path).unwrap();
+        let loaded = QuadPrediction::load_binary(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.states, prediction.states);
+        assert_eq!(loaded.controls, prediction.controls);
+        assert_eq!(loaded.control, prediction.control);
+        assert_eq!(
+            (loaded.t0, loaded.t_final),
+            (prediction.t0, prediction.t_final)
+        );
+        assert_eq!(loaded.cpu_time(), prediction.cpu_time());
+
+        // Strided output keeps its sample times and accumulators
+        let cfg = HorizonConfig::new(0.0, 10.0, 1000)
+            .unwrap()
+            .with_output_stride(30);
+        let strided = predict_cfg_with_accumulators(
+            &turn,
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            &cfg,
+        );
+        strided.save_binary(&path).unwrap();
+        let loaded = QuadPrediction::load_binary(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!((loaded.dt() - 0.3).abs() < 1e-12);
+        assert!((loaded.t_at(5) - 1.5).abs() < 1e-12);
+        assert_eq!(loaded.times(), strided.times());
+        assert_eq!(loaded.accumulators, strided.accumulators);
+        assert_eq!(loaded.states, strided.states);
+    }
+
+    type QuadPrediction = Prediction<SimpleQuadState, SimpleQuadControl>;
+
+    #[test]
+    fn load_rejects_foreign_and_truncated_files() {
+        let path = out_path("not_a_prediction.bin");
+        std::fs::write(&path, b"hello world, definitely not a prediction").unwrap();
+        let err = QuadPrediction::load_binary(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut other_version = *MAGIC;
+        other_version[7] = 1;
+        std::fs::write(&path, other_version).unwrap();
+        let err = QuadPrediction::load_binary(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        std::fs::write(&path, MAGIC).unwrap();
+        let err = QuadPrediction::load_binary(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    /// Minimal well-formedness check: every open tag is closed in order.
+    fn assert_well_formed(xml: &str) {
+        let mut stack = Vec::new();
+        for tag in xml.split('<').skip(1).map(|t| t.split('>').next().unwrap()) {
+            if tag.starts_with('?') || tag.ends_with('/') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop(), Some(name), "mismatched closing tag");
+            } else {
+                stack.push(tag.split_whitespace().next().unwrap());
+            }
+        }
+        assert!(stack.is_empty(), "unclosed tags: {stack:?}");
+    }
+
+    #[test]
+    fn kml_has_one_coordinate_per_state() {
+        let prediction = predict_sequence(
+            &[(
+                0.0,
+                DroneInput {
+                    roll_rad: 0.1,
+                    pitch_rad: 0.2,
+                    yaw_rate_rps: 0.3,
+                    thrust_norm: 0.5,
+                },
+            )],
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            10.0,
+            100,
+        );
+
+        let kml = prediction_to_kml(&prediction, 52.0, 4.5, 120.0);
+        assert_well_formed(&kml);
+
+        let coords = kml
+            .split("<coordinates>")
+            .nth(1)
+            .and_then(|rest| rest.split("</coordinates>").next())
+            .unwrap();
+        let tuples: Vec<&str> = coords.split_whitespace().collect();
+        assert_eq!(tuples.len(), prediction.states.len());
+        assert_eq!(tuples[0], "4.50000000,52.00000000,120.00");
+        assert!(tuples.iter().all(|t| t.split(',').count() == 3));
+    }
+}
@@ -1,6 +1,23 @@
+pub mod autodiff;
+pub mod cache;
+pub mod compare;
 pub mod dynamic_models;
+pub mod estimation;
+pub mod frames;
+pub mod imm;
+pub mod intent;
+pub mod io;
+pub mod linear;
+pub mod math;
 pub mod plot;
 pub mod predict;
+#[cfg(feature = "rng")]
+pub mod rng;
 pub mod solvers;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod traits;
+pub mod trim;
 pub mod types;
+pub mod uncertainty;
+pub mod units;
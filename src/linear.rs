@@ -0,0 +1,263 @@
+use std::{fs, io, path::Path};
+
+use nalgebra::DMatrix;
+
+use crate::{
+    estimation::{Measurement, PositionMeasurement},
+    traits::LinearizableDynamics,
+    types::{ControlChannels, Position2D, StateVector},
+};
+
+/// Zero-order-hold discretization of a continuous-time system matrix:
+/// `A_d = exp(A * dt)`.
+pub fn discretize(a: &DMatrix<f64>, dt: f64) -> DMatrix<f64> {
+    assert!(a.is_square(), "system matrix must be square");
+    assert!(dt.is_finite(), "dt must be finite");
+    (a * dt).exp()
+}
+
+const SIGN_MAX_ITER: usize = 100;
+const SIGN_TOL: f64 = 1e-12;
+
+/// Continuous-time LQR gain `K = R⁻¹ Bᵀ P` for `x' = A x + B u`, minimizing
+/// `∫ xᵀQx + uᵀRu dt`, so that `u = -K x`. `P` solves the continuous algebraic Riccati
+/// equation through the stable invariant subspace of the Hamiltonian
+/// `[A, -B R⁻¹ Bᵀ; -Q, -Aᵀ]`, found with the scaled Newton iteration for its matrix
+/// sign function (which avoids a nonsymmetric eigenvector solve). `None` if `R` is
+/// singular or no stabilizing solution exists, e.g. `(A, B)` is not stabilizable or
+/// `(A, Q)` has unobservable modes on the imaginary axis.
+pub fn lqr_gain(
+    a: &DMatrix<f64>,
+    b: &DMatrix<f64>,
+    q: &DMatrix<f64>,
+    r: &DMatrix<f64>,
+) -> Option<DMatrix<f64>> {
+    let n = a.nrows();
+    assert!(a.is_square(), "A must be square");
+    assert!(b.nrows() == n, "B must have one row per state");
+    assert!(q.shape() == (n, n), "Q must be n × n");
+    assert!(r.shape() == (b.ncols(), b.ncols()), "R must be m × m");
+
+    let r_inv = r.clone().try_inverse()?;
+    let mut h = DMatrix::zeros(2 * n, 2 * n);
+    h.view_mut((0, 0), (n, n)).copy_from(a);
+    h.view_mut((0, n), (n, n))
+        .copy_from(&(-b * &r_inv * b.transpose()));
+    h.view_mut((n, 0), (n, n)).copy_from(&(-q));
+    h.view_mut((n, n), (n, n)).copy_from(&(-a.transpose()));
+
+    // sign(H) = lim Z_{k+1} = (Z_k / c + c Z_k⁻¹) / 2 with determinant scaling c
+    let mut z = h;
+    let mut converged = false;
+    for _ in 0..SIGN_MAX_ITER {
+        let z_inv = z.clone().try_inverse()?;
+        let c = z.determinant().abs().powf(1.0 / (2 * n) as f64);
+        if !(c.is_finite() && c > 0.0) {
+            return None;
+        }
+        let next = (&z / c + z_inv * c) * 0.5;
+        let change = (&next - &z).norm();
+        z = next;
+        if change <= SIGN_TOL * z.norm() {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+
+    // The stable subspace is the null space of sign(H) + I:
+    // [W12; W22 + I] P = -[W11 + I; W21]
+    let identity = DMatrix::<f64>::identity(n, n);
+    let mut lhs = DMatrix::zeros(2 * n, n);
+    lhs.view_mut((0, 0), (n, n))
+        .copy_from(&z.view((0, n), (n, n)));
+    lhs.view_mut((n, 0), (n, n))
+        .copy_from(&(z.view((n, n), (n, n)) + &identity));
+    let mut rhs = DMatrix::zeros(2 * n, n);
+    rhs.view_mut((0, 0), (n, n))
+        .copy_from(&(-(z.view((0, 0), (n, n)) + &identity)));
+    rhs.view_mut((n, 0), (n, n))
+        .copy_from(&(-z.view((n, 0), (n, n))));
+
+    // Inconsistent when the stable subspace is not a graph over the states, i.e. there
+    // is no stabilizing solution
+    let p = lhs.clone().svd(true, true).solve(&rhs, 1e-12).ok()?;
+    if (&lhs * &p - &rhs).amax() > 1e-6 * rhs.amax().max(1.0) {
+        return None;
+    }
+    let p = (&p + p.transpose()) * 0.5;
+    Some(r_inv * b.transpose() * p)
+}
+
+/// Continuous-time state-space bundle `x' = A x + B u`, `y = C x + D u`, for handing a
+/// linearization to external control tooling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearModel {
+    pub a: DMatrix<f64>,
+    pub b: DMatrix<f64>,
+    pub c: DMatrix<f64>,
+    pub d: DMatrix<f64>,
+}
+
+impl LinearModel {
+    /// Write `A.csv`, `B.csv`, `C.csv` and `D.csv` into `dir` (created if missing), one
+    /// matrix row per line, comma-separated at full precision, e.g. for
+    /// `numpy.loadtxt(path, delimiter=",", ndmin=2)`.
+    pub fn to_matrices_csv<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (name, m) in [
+            ("A", &self.a),
+            ("B", &self.b),
+            ("C", &self.c),
+            ("D", &self.d),
+        ] {
+            let mut csv = String::new();
+            for row in m.row_iter() {
+                let cells: Vec<String> = row.iter().map(|v| format!("{v:e}")).collect();
+                csv.push_str(&cells.join(","));
+                csv.push('\n');
+            }
+            fs::write(dir.join(format!("{name}.csv")), csv)?;
+        }
+        Ok(())
+    }
+}
+
+/// Linearize `model` at `(t, state, control)`: `A` from
+/// [`jacobian`](LinearizableDynamics::jacobian), `B` from
+/// [`control_jacobian`](LinearizableDynamics::control_jacobian). `measurement` supplies
+/// `(C, D)`; `None` outputs the XY position, with `C` read off
+/// [`PositionMeasurement`] and `D = 0`.
+pub fn linearize<M>(
+    model: &M,
+    t: f64,
+    state: &M::State,
+    control: &M::Control,
+    measurement: Option<(DMatrix<f64>, DMatrix<f64>)>,
+) -> LinearModel
+where
+    M: LinearizableDynamics,
+    M::State: StateVector + Position2D,
+    M::Control: ControlChannels,
+{
+    let a = model.jacobian(t, state, control);
+    let b = model.control_jacobian(t, state, control);
+    let (n, m) = (a.nrows(), b.ncols());
+
+    let (c, d) = measurement.unwrap_or_else(|| {
+        let c = PositionMeasurement.jacobian(state);
+        (c, DMatrix::zeros(2, m))
+    });
+    assert!(c.ncols() == n, "C must have one column per state");
+    assert!(d.shape() == (c.nrows(), m), "D must be p × m");
+
+    LinearModel { a, b, c, d }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        traits::{Dynamics, LinearizableDynamics},
+        types::DroneInput,
+    };
+
+    #[test]
+    fn linearize_shapes_and_default_position_output() {
+        let model = SimpleQuadcopter::new(0.2);
+        let state = SimpleQuadState::new(1.0, 2.0, 3.0, 1.0, 0.4);
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.05,
+            pitch_rad: 0.1,
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        });
+
+        let lin = linearize(&model, 0.0, &state, &control, None);
+        assert_eq!(lin.a, model.jacobian(0.0, &state, &control));
+        assert_eq!(
+            (lin.a.shape(), lin.b.shape(), lin.c.shape(), lin.d.shape()),
+            ((7, 7), (7, 4), (2, 7), (2, 4))
+        );
+        let mut expected_c = DMatrix::zeros(2, 7);
+        expected_c[(0, 0)] = 1.0;
+        expected_c[(1, 1)] = 1.0;
+        assert_eq!(lin.c, expected_c);
+        assert_eq!(lin.d, DMatrix::zeros(2, 4));
+
+        let custom = linearize(
+            &model,
+            0.0,
+            &state,
+            &control,
+            Some((DMatrix::identity(7, 7), DMatrix::zeros(7, 4))),
+        );
+        assert_eq!(custom.c.shape(), (7, 7));
+
+        let dir = std::env::temp_dir().join(format!("pilots_intent_{}_lin", std::process::id()));
+        lin.to_matrices_csv(&dir).unwrap();
+        let b_csv = std::fs::read_to_string(dir.join("B.csv")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let rows: Vec<Vec<f64>> = b_csv
+            .lines()
+            .map(|l| l.split(',').map(|v| v.parse().unwrap()).collect())
+            .collect();
+        assert_eq!((rows.len(), rows[0].len()), (7, 4));
+        assert_eq!(rows[2][0], lin.b[(2, 0)]);
+    }
+
+    #[test]
+    fn discretize_double_integrator_is_exact() {
+        // x'' = 0 in [position, velocity] form
+        let a = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 0.0, 0.0]);
+        let ad = discretize(&a, 0.5);
+        let expected = DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.0, 1.0]);
+        assert!((ad - expected).amax() < 1e-12);
+    }
+
+    #[test]
+    fn lqr_double_integrator_matches_closed_form() {
+        // Q = I, R = 1: K = [1, sqrt(3)]
+        let a = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 0.0, 0.0]);
+        let b = DMatrix::from_row_slice(2, 1, &[0.0, 1.0]);
+        let k = lqr_gain(&a, &b, &DMatrix::identity(2, 2), &DMatrix::identity(1, 1)).unwrap();
+        assert!((k[(0, 0)] - 1.0).abs() < 1e-9);
+        assert!((k[(0, 1)] - 3f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lqr_stabilizes_linearized_quadcopter() {
+        let model = SimpleQuadcopter::new(0.2);
+        let state = SimpleQuadState::new(0.0, 0.0, 3.0, 1.0, 0.4);
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 0.05,
+            pitch_rad: 0.1,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        });
+        let a = model.jacobian(0.0, &state, &control);
+        let b = model.control_jacobian(0.0, &state, &control);
+
+        // Positions and yaw are pure integrators: open loop is only marginally stable
+        assert!(a.complex_eigenvalues().iter().any(|l| l.re.abs() < 1e-12));
+
+        let k = lqr_gain(&a, &b, &DMatrix::identity(7, 7), &DMatrix::identity(4, 4)).unwrap();
+        let closed = &a - &b * &k;
+        for l in closed.complex_eigenvalues().iter() {
+            assert!(l.re < -1e-3, "closed-loop eigenvalue {l}");
+        }
+    }
+
+    #[test]
+    fn lqr_rejects_unstabilizable_pair() {
+        // Unstable mode the input cannot reach
+        let a = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, -1.0]);
+        let b = DMatrix::from_row_slice(2, 1, &[0.0, 1.0]);
+        let gain = lqr_gain(&a, &b, &DMatrix::identity(2, 2), &DMatrix::identity(1, 1));
+        assert_eq!(gain, None);
+    }
+}
@@ -11,6 +11,7 @@ fn main() {
         roll_rad: 20f64.to_radians(),
         pitch_rad: 10f64.to_radians(),
         yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
     };
 
     let t_final = 10.0;
@@ -25,8 +26,8 @@ fn main() {
         20.0, // yaw [rad] (0 = facing North)
     );
 
-    let model = SimpleQuadcopter { drag: 0.0 };
-    let mut solver = ForwardEuler::default();
+    let model = SimpleQuadcopter::new(0.0);
+    let mut solver = ForwardEuler;
     let prediction = predict(
         &input,
         initial_state,
@@ -0,0 +1,84 @@
+// Shared frame conventions. NED horizontal plane (x = North, y = East), body x-forward,
+// y-right, yaw = 0 facing North and positive clockwise seen from above.
+
+use nalgebra::{Matrix2, Matrix3, Rotation3};
+
+/// Rotation taking horizontal body-frame vectors `(forward, right)` to NED `(north, east)`.
+pub fn rotation_ned_from_body(yaw_rad: f64) -> Matrix2<f64> {
+    let (s, c) = yaw_rad.sin_cos();
+    Matrix2::new(c, -s, s, c)
+}
+
+/// Inverse (transpose) of [`rotation_ned_from_body`].
+pub fn rotation_body_from_ned(yaw_rad: f64) -> Matrix2<f64> {
+    rotation_ned_from_body(yaw_rad).transpose()
+}
+
+/// Derivative of [`rotation_ned_from_body`] with respect to yaw, for Jacobians.
+pub fn rotation_ned_from_body_dyaw(yaw_rad: f64) -> Matrix2<f64> {
+    let (s, c) = yaw_rad.sin_cos();
+    Matrix2::new(-s, -c, c, -s)
+}
+
+/// `angle` wrapped into `[-π, π)`, e.g. the short way round between two headings.
+pub fn wrap_pi(angle: f64) -> f64 {
+    use std::f64::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Full attitude rotation taking body `(forward, right, down)` to NED, in this crate's
+/// stick convention: roll right-wing-down positive, pitch nose-down positive (the
+/// opposite sign of aerospace Tait-Bryan pitch), yaw clockwise positive. Reduces to
+/// [`rotation_ned_from_body`] in the horizontal block when roll and pitch are zero.
+pub fn rotation_ned_from_body_3d(roll_rad: f64, pitch_rad: f64, yaw_rad: f64) -> Matrix3<f64> {
+    Rotation3::from_euler_angles(roll_rad, -pitch_rad, yaw_rad).into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector2;
+
+    #[test]
+    fn wrap_pi_takes_the_short_way_round() {
+        use std::f64::consts::{PI, TAU};
+        assert_eq!(wrap_pi(0.5), 0.5);
+        assert!((wrap_pi(TAU + 0.5) - 0.5).abs() < 1e-12);
+        assert!((wrap_pi(-6.0) - (TAU - 6.0)).abs() < 1e-12);
+        assert_eq!(wrap_pi(PI), -PI);
+    }
+
+    #[test]
+    fn yaw_east_maps_forward_to_east_and_right_to_south() {
+        let r = rotation_ned_from_body(std::f64::consts::FRAC_PI_2);
+        let forward = r * Vector2::new(1.0, 0.0);
+        let right = r * Vector2::new(0.0, 1.0);
+        assert!((forward - Vector2::new(0.0, 1.0)).amax() < 1e-15);
+        assert!((right - Vector2::new(-1.0, 0.0)).amax() < 1e-15);
+    }
+
+    #[test]
+    fn body_from_ned_inverts_and_derivative_matches_fd() {
+        let yaw = 0.7;
+        let roundtrip = rotation_body_from_ned(yaw) * rotation_ned_from_body(yaw);
+        assert!((roundtrip - Matrix2::identity()).amax() < 1e-15);
+
+        let h = 1e-6;
+        let fd = (rotation_ned_from_body(yaw + h) - rotation_ned_from_body(yaw - h)) / (2.0 * h);
+        assert!((fd - rotation_ned_from_body_dyaw(yaw)).amax() < 1e-9);
+    }
+
+    #[test]
+    fn rotation_3d_matches_planar_and_stick_signs() {
+        let yaw = -1.1;
+        let r = rotation_ned_from_body_3d(0.0, 0.0, yaw);
+        assert!((r.fixed_view::<2, 2>(0, 0) - rotation_ned_from_body(yaw)).amax() < 1e-15);
+
+        // Pitching forward tips the thrust axis (body -z) towards North
+        let up = rotation_ned_from_body_3d(0.0, 0.2, 0.0) * nalgebra::Vector3::new(0.0, 0.0, -1.0);
+        assert!(up.x > 0.0 && up.y.abs() < 1e-15);
+        // Rolling right tips it towards East
+        let up = rotation_ned_from_body_3d(0.2, 0.0, 0.0) * nalgebra::Vector3::new(0.0, 0.0, -1.0);
+        assert!(up.y > 0.0 && up.x.abs() < 1e-15);
+    }
+}
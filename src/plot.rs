@@ -1,56 +1,163 @@
 use plotters::prelude::*;
-use std::path::Path;
+use std::{ops::Range, path::Path};
 
+use nalgebra::DMatrix;
 use num_complex::Complex;
 
 use crate::{
     predict::Prediction,
     traits::LinearizableDynamics,
-    types::{Position2D, StateVector},
+    types::{ControlChannels, GroundVelocity, Position2D, StateVector},
+    uncertainty::confidence_ellipse,
 };
 
+/// Look of the [`plot_xy_opts`] path plot. The default is the [`plot_xy`] style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotOptions {
+    pub title: String,
+    pub line_color: RGBColor,
+    pub start_color: RGBColor,
+    pub end_color: RGBColor,
+    /// Radius of the start and end markers [px].
+    pub marker_size: u32,
+    /// Image size [px].
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            title: "Predicted Pilot Intent (XY)".to_owned(),
+            line_color: BLUE,
+            start_color: GREEN,
+            end_color: RED,
+            marker_size: 4,
+            width: 900,
+            height: 900,
+        }
+    }
+}
+
 pub fn plot_xy<S, U, P>(prediction: &Prediction<S, U>, filename: P)
 where
     S: Position2D,
     P: AsRef<Path>,
 {
-    let states = &prediction.states;
-    assert!(states.len() >= 2, "need at least 2 states");
+    plot_xy_opts(prediction, &PlotOptions::default(), filename);
+}
+
+/// [`plot_xy`] with the title, colors, marker size and image size from `opts`.
+pub fn plot_xy_opts<S, U, P>(prediction: &Prediction<S, U>, opts: &PlotOptions, filename: P)
+where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    assert!(!prediction.states.is_empty(), "states must not be empty");
+
+    let points = prediction.positions();
+    draw_xy_path(&points, opts, filename);
+}
+
+/// Like [`plot_xy`], but draws at most `max_points` actual samples (uniformly strided,
+/// always keeping the first and last) to keep large predictions fast and small.
+pub fn plot_xy_decimated<S, U, P>(prediction: &Prediction<S, U>, max_points: usize, filename: P)
+where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    assert!(!prediction.states.is_empty(), "states must not be empty");
+
+    let points = prediction.positions();
+    draw_xy_path(
+        &decimate(&points, max_points),
+        &PlotOptions::default(),
+        filename,
+    );
+}
+
+/// Uniformly subsample `items` to at most `max_points` (>= 2) elements, always keeping
+/// the first and last. Unlike resampling, the returned items are original samples.
+pub fn decimate<T: Clone>(items: &[T], max_points: usize) -> Vec<T> {
+    assert!(max_points >= 2, "max_points must be >= 2");
+    let n = items.len();
+    if n <= max_points {
+        return items.to_vec();
+    }
+
+    let last = (n - 1) as f64;
+    let spacing = (max_points - 1) as f64;
+    (0..max_points)
+        .map(|k| items[(k as f64 * last / spacing).round() as usize].clone())
+        .collect()
+}
+
+/// Half-width [m] of the axis window around a path with no extent along an axis.
+const DEGENERATE_HALF_SPAN_M: f64 = 1.0;
 
-    // Extract (x, y)
-    let mut points = Vec::with_capacity(states.len());
+/// Padded north/east axis ranges covering `points`. With `equal_aspect` both ranges get
+/// the span of the larger one (centered on the data), so on the square canvas 1 m
+/// north and 1 m east take the same screen length and circles stay circular.
+fn xy_ranges<'a>(
+    points: impl IntoIterator<Item = &'a (f64, f64)>,
+    equal_aspect: bool,
+) -> (Range<f64>, Range<f64>) {
     let mut x_min = f64::INFINITY;
     let mut x_max = f64::NEG_INFINITY;
     let mut y_min = f64::INFINITY;
     let mut y_max = f64::NEG_INFINITY;
 
-    for s in states {
-        let (x, y) = s.position();
-
+    for &(x, y) in points {
         x_min = x_min.min(x);
         x_max = x_max.max(x);
         y_min = y_min.min(y);
         y_max = y_max.max(y);
+    }
 
-        points.push((x, y));
+    if equal_aspect {
+        let half = 0.5 * (x_max - x_min).max(y_max - y_min);
+        let (x_mid, y_mid) = (0.5 * (x_min + x_max), 0.5 * (y_min + y_max));
+        (x_min, x_max) = (x_mid - half, x_mid + half);
+        (y_min, y_max) = (y_mid - half, y_mid + half);
     }
 
-    // Padding so line is not glued to border
-    let pad_x = ((x_max - x_min).abs() * 0.1).max(1e-3);
-    let pad_y = ((y_max - y_min).abs() * 0.1).max(1e-3);
+    // Padding so line is not glued to border; a single point (or a path that never
+    // moves) gets a small synthetic window around it instead of an empty range
+    let pad = |span: f64| {
+        if span > 0.0 {
+            span * 0.1
+        } else {
+            DEGENERATE_HALF_SPAN_M
+        }
+    };
+    let (pad_x, pad_y) = (pad(x_max - x_min), pad(y_max - y_min));
+    (
+        (x_min - pad_x)..(x_max + pad_x),
+        (y_min - pad_y)..(y_max + pad_y),
+    )
+}
 
-    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+fn draw_xy_path<P: AsRef<Path>>(points: &[(f64, f64)], opts: &PlotOptions, filename: P) {
+    // Equal aspect assumes a square canvas; other sizes stretch the axes to fit
+    draw_xy_path_in(points, xy_ranges(points, true), opts, filename);
+}
+
+/// [`draw_xy_path`] on fixed axis ranges.
+fn draw_xy_path_in<P: AsRef<Path>>(
+    points: &[(f64, f64)],
+    (x_range, y_range): (Range<f64>, Range<f64>),
+    opts: &PlotOptions,
+    filename: P,
+) {
+    let root = BitMapBackend::new(filename.as_ref(), (opts.width, opts.height)).into_drawing_area();
     root.fill(&WHITE).unwrap();
 
     let mut chart = ChartBuilder::on(&root)
         .margin(20)
-        .caption("Predicted Pilot Intent (XY)", ("sans-serif", 28))
+        .caption(&opts.title, ("sans-serif", 28))
         .x_label_area_size(40)
         .y_label_area_size(40)
-        .build_cartesian_2d(
-            (x_min - pad_x)..(x_max + pad_x),
-            (y_min - pad_y)..(y_max + pad_y),
-        )
+        .build_cartesian_2d(x_range, y_range)
         .unwrap();
 
     chart
@@ -62,23 +169,594 @@ where
 
     // Trajectory
     chart
-        .draw_series(LineSeries::new(points.clone(), &BLUE))
+        .draw_series(LineSeries::new(points.iter().copied(), &opts.line_color))
         .unwrap();
 
     // Start point
     chart
-        .draw_series(std::iter::once(Circle::new(points[0], 4, GREEN.filled())))
+        .draw_series(std::iter::once(Circle::new(
+            points[0],
+            opts.marker_size,
+            opts.start_color.filled(),
+        )))
         .unwrap();
 
     // End point
     chart
         .draw_series(std::iter::once(Circle::new(
             *points.last().unwrap(),
+            opts.marker_size,
+            opts.end_color.filled(),
+        )))
+        .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Write `n_frames` (>= 2) PNGs `frame_0000.png`, `frame_0001.png`, ... into `out_dir`
+/// (created if missing), frame `k` showing the path up to the sample nearest
+/// `k / (n_frames - 1)` of the horizon with a marker at the current position. Every
+/// frame uses the axes of the whole path, so the sequence can be assembled into an
+/// animation as is.
+pub fn plot_animation<S, U, P>(prediction: &Prediction<S, U>, out_dir: P, n_frames: usize)
+where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    assert!(!prediction.states.is_empty(), "states must not be empty");
+    assert!(n_frames >= 2, "n_frames must be >= 2");
+    let out_dir = out_dir.as_ref();
+    std::fs::create_dir_all(out_dir).expect("cannot create the frame directory");
+
+    let points = prediction.positions();
+    let ranges = xy_ranges(&points, true);
+    let last = points.len() - 1;
+    for k in 0..n_frames {
+        let i = (k as f64 * last as f64 / (n_frames - 1) as f64).round() as usize;
+        let opts = PlotOptions {
+            title: format!(
+                "Predicted Pilot Intent (XY), t = {:.2} s",
+                prediction.t_at(i)
+            ),
+            ..PlotOptions::default()
+        };
+        draw_xy_path_in(
+            &points[..=i],
+            ranges.clone(),
+            &opts,
+            out_dir.join(format!("frame_{k:04}.png")),
+        );
+    }
+}
+
+/// Plot several XY paths on shared, auto-ranged axes, one palette color each, e.g.
+/// a [`predict_yaw_fan`](crate::predict::predict_yaw_fan).
+pub fn plot_xy_multi<S, U, P>(predictions: &[Prediction<S, U>], filename: P)
+where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    assert!(!predictions.is_empty(), "need at least one prediction");
+    let paths: Vec<Vec<(f64, f64)>> = predictions.iter().map(|p| p.positions()).collect();
+    let (x_range, y_range) = xy_ranges(paths.iter().flatten(), true);
+
+    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption("Predicted Pilot Intents (XY)", ("sans-serif", 28))
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    for (k, points) in paths.iter().enumerate() {
+        let color = Palette99::pick(k).to_rgba();
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(2),
+            ))
+            .unwrap();
+        if let Some(&end) = points.last() {
+            chart
+                .draw_series(std::iter::once(Circle::new(end, 4, color.filled())))
+                .unwrap();
+        }
+    }
+
+    root.present().unwrap();
+}
+
+/// Overlay a `candidate` XY path on a `reference` one and shade the gap between
+/// time-aligned points, darker where the position error is larger. The candidate is
+/// sampled at the reference times with [`Prediction::state_at`], so the two may use
+/// different `dt`s (times past the candidate horizon are clamped to its end).
+pub fn plot_xy_diff<S, U, V, P>(
+    reference: &Prediction<S, U>,
+    candidate: &Prediction<S, V>,
+    filename: P,
+) where
+    S: Position2D + StateVector,
+    P: AsRef<Path>,
+{
+    assert!(!reference.states.is_empty(), "states must not be empty");
+
+    let ref_points = reference.positions();
+    let aligned: Vec<(f64, f64)> = (0..ref_points.len())
+        .map(|i| candidate.state_at(reference.t_at(i)).position())
+        .collect();
+    let errors: Vec<f64> = ref_points
+        .iter()
+        .zip(&aligned)
+        .map(|(r, c)| (r.0 - c.0).hypot(r.1 - c.1))
+        .collect();
+    let max_error = errors.iter().copied().fold(0.0, f64::max);
+    let cand_points = candidate.positions();
+    let (x_range, y_range) = xy_ranges(ref_points.iter().chain(&cand_points), true);
+
+    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption(
+            format!("Prediction diff (XY, max error {max_error:.2} m)"),
+            ("sans-serif", 28),
+        )
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    // One quad per sample interval between the two paths, opacity by mean error
+    if max_error > 0.0 {
+        chart
+            .draw_series((0..ref_points.len().saturating_sub(1)).map(|i| {
+                let intensity = 0.5 * (errors[i] + errors[i + 1]) / max_error;
+                Polygon::new(
+                    vec![ref_points[i], ref_points[i + 1], aligned[i + 1], aligned[i]],
+                    RED.mix(0.1 + 0.6 * intensity).filled(),
+                )
+            }))
+            .unwrap();
+    }
+
+    chart
+        .draw_series(LineSeries::new(ref_points.clone(), BLUE.stroke_width(2)))
+        .unwrap()
+        .label("reference")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+    chart
+        .draw_series(LineSeries::new(cand_points, BLACK.stroke_width(2)))
+        .unwrap()
+        .label("candidate")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
+
+    chart
+        .draw_series(std::iter::once(Circle::new(
+            ref_points[0],
             4,
-            RED.filled(),
+            GREEN.filled(),
         )))
         .unwrap();
 
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Plot the spatial envelope of an ensemble as a shaded intent cone around the nominal
+/// path. `predictions[0]` is the nominal: its samples set the time grid, every member
+/// is aligned to it with [`Prediction::state_at`], and the region swept by the convex
+/// hull of the member positions from one sample to the next is filled. A single-sample
+/// nominal has no cone to sweep and is drawn as a dot.
+pub fn plot_ensemble_envelope<S, U, P>(predictions: &[Prediction<S, U>], filename: P)
+where
+    S: Position2D + StateVector,
+    P: AsRef<Path>,
+{
+    assert!(!predictions.is_empty(), "need at least one prediction");
+    let nominal = &predictions[0];
+    let n = nominal.states.len();
+
+    // A single-sample member has nothing to interpolate and stays where it is
+    let position_at = |p: &Prediction<S, U>, t: f64| match p.states.len() {
+        1 => p.states[0].position(),
+        _ => p.state_at(t).position(),
+    };
+    let slices: Vec<Vec<(f64, f64)>> = (0..n)
+        .map(|i| {
+            let t = nominal.t_at(i);
+            predictions.iter().map(|p| position_at(p, t)).collect()
+        })
+        .collect();
+    let (x_range, y_range) = xy_ranges(slices.iter().flatten(), true);
+
+    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption(
+            format!("Predicted Intent Envelope ({} members)", predictions.len()),
+            ("sans-serif", 28),
+        )
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    // Opaque fill, so overlapping hulls do not darken where the cone is drawn twice
+    let fill = RGBColor(190, 210, 240).filled();
+    chart
+        .draw_series(slices.windows(2).map(|w| {
+            let corners: Vec<(f64, f64)> = w.iter().flatten().copied().collect();
+            Polygon::new(convex_hull(&corners), fill)
+        }))
+        .unwrap();
+
+    let path = nominal.positions();
+    chart
+        .draw_series(LineSeries::new(path.clone(), BLUE.stroke_width(2)))
+        .unwrap();
+    chart
+        .draw_series(std::iter::once(Circle::new(path[0], 4, GREEN.filled())))
+        .unwrap();
+    chart
+        .draw_series(std::iter::once(Circle::new(path[n - 1], 4, RED.filled())))
+        .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Cells per axis of the [`plot_endpoint_density`] grid.
+const DENSITY_GRID_CELLS: usize = 120;
+
+/// Plot where an ensemble ends up as a smooth heatmap: a 2D Gaussian kernel density
+/// estimate of the final positions with an isotropic `bandwidth_m` [m], evaluated on a
+/// grid over the endpoints padded by three bandwidths and colored on a viridis scale
+/// relative to its peak. The endpoints themselves are overlaid as dots.
+pub fn plot_endpoint_density<S, U, P>(
+    predictions: &[Prediction<S, U>],
+    bandwidth_m: f64,
+    filename: P,
+) where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    assert!(!predictions.is_empty(), "need at least one prediction");
+    assert!(
+        bandwidth_m.is_finite() && bandwidth_m > 0.0,
+        "bandwidth_m must be finite and > 0"
+    );
+
+    let endpoints: Vec<(f64, f64)> = predictions
+        .iter()
+        .map(|p| p.final_state().position())
+        .collect();
+    let (x_range, y_range) = xy_ranges(&endpoints, true);
+    let pad = 3.0 * bandwidth_m;
+    let x_range = (x_range.start - pad)..(x_range.end + pad);
+    let y_range = (y_range.start - pad)..(y_range.end + pad);
+
+    let n = DENSITY_GRID_CELLS;
+    let density = kernel_density(&endpoints, bandwidth_m, &x_range, &y_range, n);
+    let peak = density.max();
+    let dx = (x_range.end - x_range.start) / n as f64;
+    let dy = (y_range.end - y_range.start) / n as f64;
+
+    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption(
+            format!("Endpoint Density ({} members)", predictions.len()),
+            ("sans-serif", 28),
+        )
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range.clone(), y_range.clone())
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series((0..n).flat_map(|i| {
+            let x0 = x_range.start + i as f64 * dx;
+            let y_start = y_range.start;
+            let density = &density;
+            (0..n).map(move |j| {
+                let y0 = y_start + j as f64 * dy;
+                let color = viridis(density[(i, j)] / peak);
+                Rectangle::new([(x0, y0), (x0 + dx, y0 + dy)], color.filled())
+            })
+        }))
+        .unwrap();
+
+    chart
+        .draw_series(
+            endpoints
+                .iter()
+                .map(|&p| Circle::new(p, 2, WHITE.mix(0.8).filled())),
+        )
+        .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Gaussian kernel density estimate of `points` [1/m²] at the centers of an `n × n`
+/// grid spanning the ranges; entry `(i, j)` is the `i`-th cell along x, `j`-th along y.
+fn kernel_density(
+    points: &[(f64, f64)],
+    bandwidth_m: f64,
+    x_range: &Range<f64>,
+    y_range: &Range<f64>,
+    n: usize,
+) -> DMatrix<f64> {
+    let dx = (x_range.end - x_range.start) / n as f64;
+    let dy = (y_range.end - y_range.start) / n as f64;
+    let inv_two_h2 = 0.5 / (bandwidth_m * bandwidth_m);
+    let norm = inv_two_h2 / (std::f64::consts::PI * points.len() as f64);
+
+    DMatrix::from_fn(n, n, |i, j| {
+        let x = x_range.start + (i as f64 + 0.5) * dx;
+        let y = y_range.start + (j as f64 + 0.5) * dy;
+        let sum: f64 = points
+            .iter()
+            .map(|&(px, py)| (-((x - px).powi(2) + (y - py).powi(2)) * inv_two_h2).exp())
+            .sum();
+        norm * sum
+    })
+}
+
+/// Convex hull of `points` in counter-clockwise order (Andrew's monotone chain).
+fn convex_hull(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("positions must be finite"));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+    let mut hull: Vec<(f64, f64)> = Vec::with_capacity(2 * sorted.len());
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let floor = hull.len();
+        for p in pass {
+            while hull.len() >= floor + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        // The last point of each chain starts the other one
+        hull.pop();
+    }
+    hull
+}
+
+/// Plot the XY path with each segment colored by ground speed, plus a colorbar.
+pub fn plot_xy_speed<S, U, P>(prediction: &Prediction<S, U>, filename: P)
+where
+    S: Position2D + GroundVelocity,
+    P: AsRef<Path>,
+{
+    let states = &prediction.states;
+    assert!(!states.is_empty(), "states must not be empty");
+
+    let points = prediction.positions();
+    let speeds = prediction.speed_series();
+    let (x_range, y_range) = xy_ranges(&points, false);
+
+    let v_min = speeds.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut v_max = speeds.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if v_max - v_min < 1e-9 {
+        v_max = v_min + 1.0;
+    }
+    let normalize = |v: f64| (v - v_min) / (v_max - v_min);
+
+    let root = BitMapBackend::new(filename.as_ref(), (1040, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+    let (path_area, bar_area) = root.split_horizontally(880);
+
+    let mut chart = ChartBuilder::on(&path_area)
+        .margin(20)
+        .caption("Predicted Pilot Intent (XY, speed)", ("sans-serif", 28))
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    // One short line per segment, colored by the mean speed over the segment
+    for (seg, v) in points.windows(2).zip(speeds.windows(2)) {
+        let color = viridis(normalize(0.5 * (v[0] + v[1])));
+        chart
+            .draw_series(LineSeries::new(seg.to_vec(), color.stroke_width(3)))
+            .unwrap();
+    }
+    if let [point] = points[..] {
+        chart
+            .draw_series(std::iter::once(Circle::new(
+                point,
+                4,
+                viridis(normalize(speeds[0])).filled(),
+            )))
+            .unwrap();
+    }
+
+    // Colorbar
+    let mut bar = ChartBuilder::on(&bar_area)
+        .margin_top(60)
+        .margin_bottom(60)
+        .margin_right(50)
+        .y_label_area_size(70)
+        .build_cartesian_2d(0.0..1.0, v_min..v_max)
+        .unwrap();
+
+    bar.configure_mesh()
+        .disable_x_mesh()
+        .disable_x_axis()
+        .y_label_style(("sans-serif", 14))
+        .axis_desc_style(("sans-serif", 14))
+        .y_desc("speed [m/s]")
+        .draw()
+        .unwrap();
+
+    let n_bands = 64;
+    let dv = (v_max - v_min) / n_bands as f64;
+    bar.draw_series((0..n_bands).map(|k| {
+        let v0 = v_min + k as f64 * dv;
+        Rectangle::new(
+            [(0.0, v0), (1.0, v0 + dv)],
+            viridis((k as f64 + 0.5) / n_bands as f64).filled(),
+        )
+    }))
+    .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Viridis-like colormap for `t` in `[0, 1]`, interpolated between a few anchor colors.
+fn viridis(t: f64) -> RGBColor {
+    const ANCHORS: [(f64, f64, f64); 5] = [
+        (68.0, 1.0, 84.0),
+        (59.0, 82.0, 139.0),
+        (33.0, 145.0, 140.0),
+        (94.0, 201.0, 98.0),
+        (253.0, 231.0, 37.0),
+    ];
+
+    let x = t.clamp(0.0, 1.0) * (ANCHORS.len() - 1) as f64;
+    let k = (x.floor() as usize).min(ANCHORS.len() - 2);
+    let f = x - k as f64;
+    let (a, b) = (ANCHORS[k], ANCHORS[k + 1]);
+    let lerp = |p: f64, q: f64| (p + f * (q - p)).round() as u8;
+
+    RGBColor(lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+}
+
+/// Plot the XY path with 2σ position ellipses drawn at a stride along it, taken from the
+/// north/east block of each covariance (e.g. from `propagate_covariance`).
+pub fn plot_xy_uncertainty<S, U, P>(
+    prediction: &Prediction<S, U>,
+    covariances: &[DMatrix<f64>],
+    filename: P,
+) where
+    S: Position2D,
+    P: AsRef<Path>,
+{
+    const N_ELLIPSES: usize = 20;
+    const N_SIGMA: f64 = 2.0;
+
+    let states = &prediction.states;
+    assert!(!states.is_empty(), "states must not be empty");
+    assert!(
+        covariances.len() == states.len(),
+        "need one covariance per state"
+    );
+
+    let points = prediction.positions();
+
+    let stride = (points.len() / N_ELLIPSES).max(1);
+    let mut ellipses: Vec<Vec<(f64, f64)>> = (0..points.len())
+        .step_by(stride)
+        .map(|i| confidence_ellipse(points[i], &covariances[i], N_SIGMA, 72))
+        .collect();
+    let last = points.len() - 1;
+    if !last.is_multiple_of(stride) {
+        ellipses.push(confidence_ellipse(
+            points[last],
+            &covariances[last],
+            N_SIGMA,
+            72,
+        ));
+    }
+
+    // Auto-scale over both the path and the ellipse extents
+    let (x_range, y_range) = xy_ranges(points.iter().chain(ellipses.iter().flatten()), false);
+
+    let root = BitMapBackend::new(filename.as_ref(), (900, 900)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption("Predicted Pilot Intent (XY, 2σ)", ("sans-serif", 28))
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("North [m]")
+        .y_desc("East [m]")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(
+            ellipses
+                .into_iter()
+                .map(|e| Polygon::new(e, RED.mix(0.15).filled())),
+        )
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(points.clone(), &BLUE))
+        .unwrap();
+
+    chart
+        .draw_series(std::iter::once(Circle::new(points[0], 4, GREEN.filled())))
+        .unwrap();
+
+    chart
+        .draw_series(std::iter::once(Circle::new(points[last], 4, RED.filled())))
+        .unwrap();
+
     root.present().unwrap();
 }
 
@@ -139,6 +817,88 @@ pub fn plot_component<S, U, P>(
     root.present().unwrap();
 }
 
+/// Plot each control channel over the horizon. Constant-input predictions draw flat
+/// lines; predictions with several `controls` segments draw a staircase.
+pub fn plot_controls<S, U, P>(prediction: &Prediction<S, U>, filename: P)
+where
+    U: ControlChannels,
+    P: AsRef<Path>,
+{
+    assert!(
+        !prediction.controls.is_empty(),
+        "controls must not be empty"
+    );
+
+    let names = U::channel_names();
+    let t_start = prediction.t0;
+    let t_end = prediction.t_end();
+
+    // One staircase per channel: hold each value until the next segment starts
+    let mut series = vec![Vec::with_capacity(2 * prediction.controls.len()); names.len()];
+    let mut y_min = f64::INFINITY;
+    let mut y_max = f64::NEG_INFINITY;
+
+    for (k, (t, u)) in prediction.controls.iter().enumerate() {
+        let t_next = prediction
+            .controls
+            .get(k + 1)
+            .map_or(t_end, |(t_next, _)| *t_next);
+        let values = u.channels();
+        assert!(
+            values.len() == names.len(),
+            "control reported {} channels but names {} channels",
+            values.len(),
+            names.len()
+        );
+
+        for (c, v) in values.into_iter().enumerate() {
+            y_min = y_min.min(v);
+            y_max = y_max.max(v);
+            series[c].push((*t, v));
+            series[c].push((t_next, v));
+        }
+    }
+
+    let pad = ((y_max - y_min).abs() * 0.1).max(1e-3);
+    let y_range = (y_min - pad)..(y_max + pad);
+
+    let root = BitMapBackend::new(filename.as_ref(), (1200, 700)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption("Control inputs vs t", ("sans-serif", 28))
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(t_start..t_end, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("t [s]")
+        .y_desc("control")
+        .draw()
+        .unwrap();
+
+    for (c, (points, name)) in series.into_iter().zip(names).enumerate() {
+        let color = Palette99::pick(c).to_rgba();
+        chart
+            .draw_series(LineSeries::new(points, color.stroke_width(2)))
+            .unwrap()
+            .label(*name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root.present().unwrap();
+}
+
 /// Plot absolute stability region for a one-step method given its stability function R(z).
 pub fn plot_stability_region<P: AsRef<Path>, R>(
     stabfn: R,
@@ -202,15 +962,80 @@ pub fn plot_stability_region<P: AsRef<Path>, R>(
 
     chart
         .draw_series([
-            PathElement::new(vec![(0.0, y_min), (0.0, y_max)], &BLACK),
-            PathElement::new(vec![(x_min, 0.0), (x_max, 0.0)], &BLACK),
+            PathElement::new(vec![(0.0, y_min), (0.0, y_max)], BLACK),
+            PathElement::new(vec![(x_min, 0.0), (x_max, 0.0)], BLACK),
         ])
         .unwrap();
 
     root.present().unwrap();
 }
 
-/// Plot eigenvalues of df/dx · dt along the trajectory for linearizable models.
+/// Plot stability-region boundaries, e.g. from
+/// [`stability_boundary`](crate::solvers::stability_boundary), as labelled closed curves
+/// on shared, auto-ranged axes.
+pub fn plot_stability_boundaries<P: AsRef<Path>>(curves: &[(&str, Vec<(f64, f64)>)], filename: P) {
+    assert!(!curves.is_empty(), "need at least one curve");
+
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = (-0.5f64, 0.5f64, -0.5f64, 0.5f64);
+    for &(x, y) in curves.iter().flat_map(|(_, pts)| pts) {
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    let pad = 0.1 * (x_max - x_min).max(y_max - y_min);
+
+    let size = (900u32, 900u32);
+    let root = BitMapBackend::new(filename.as_ref(), size).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(20)
+        .caption("Stability boundaries (|R(z)| = 1)", ("sans-serif", 28))
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d((x_min - pad)..(x_max + pad), (y_min - pad)..(y_max + pad))
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("Re")
+        .y_desc("Im")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series([
+            PathElement::new(vec![(0.0, y_min - pad), (0.0, y_max + pad)], BLACK),
+            PathElement::new(vec![(x_min - pad, 0.0), (x_max + pad, 0.0)], BLACK),
+        ])
+        .unwrap();
+
+    for (k, (label, points)) in curves.iter().enumerate() {
+        let color = Palette99::pick(k).to_rgba();
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(2),
+            ))
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root.present().unwrap();
+}
+
+/// Plot eigenvalues of df/dx · dt along the trajectory for linearizable models, with
+/// `dt` the sample interval following each state (preceding it for the last one) and
+/// df/dx taken under the control active at that sample.
 pub fn plot_eigvals<M, P>(
     prediction: &Prediction<M::State, M::Control>,
     model: &M,
@@ -225,7 +1050,7 @@ where
     let m = prediction.states[0].to_dvector().len();
     assert!(m > 0, "state dimension must be > 0");
 
-    let dt = prediction.dt();
+    let last = prediction.states.len() - 1;
     let mut eigs: Vec<Vec<Complex<f64>>> = Vec::with_capacity(prediction.states.len());
 
     let mut x_min = f64::INFINITY;
@@ -235,7 +1060,13 @@ where
 
     for (i, state) in prediction.states.iter().enumerate() {
         let t = prediction.t_at(i);
-        let j = model.jacobian(t, state, &prediction.control);
+        let k = i.min(last.saturating_sub(1));
+        let dt = if last == 0 {
+            prediction.dt()
+        } else {
+            prediction.t_at(k + 1) - prediction.t_at(k)
+        };
+        let j = model.jacobian(t, state, prediction.control_at(t));
         assert!(
             j.nrows() == m && j.ncols() == m,
             "jacobian must be square with dimension matching the state"
@@ -294,8 +1125,8 @@ where
 
     chart
         .draw_series([
-            PathElement::new(vec![(0.0, y0), (0.0, y1)], &BLACK),
-            PathElement::new(vec![(x0, 0.0), (x1, 0.0)], &BLACK),
+            PathElement::new(vec![(0.0, y0), (0.0, y1)], BLACK),
+            PathElement::new(vec![(x0, 0.0), (x1, 0.0)], BLACK),
         ])
         .unwrap();
 
@@ -310,3 +1141,453 @@ where
     root.present().unwrap();
     eigs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{
+            Composed, DubinsCar, DubinsState, SimpleQuadControl, SimpleQuadState, SimpleQuadcopter,
+        },
+        predict::{predict, predict_sequence},
+        solvers::Rk4,
+        traits::DisturbanceModel,
+        types::DroneInput,
+    };
+
+    fn out_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("pilots_intent_{}_{name}", std::process::id()))
+    }
+
+    fn assert_non_empty_file(path: &Path) {
+        let len = std::fs::metadata(path).expect("plot file missing").len();
+        assert!(len > 0, "plot file is empty");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn single_state_prediction_plots_as_a_dot() {
+        let mut prediction = predict(
+            &DroneInput {
+                roll_rad: 0.0,
+                pitch_rad: 0.1,
+                yaw_rate_rps: 0.0,
+                thrust_norm: 0.5,
+            },
+            SimpleQuadState::new(3.0, -2.0, 1.0, 0.0, 0.0),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            1.0,
+            10,
+        );
+        prediction.states.truncate(1);
+
+        let (x, y) = xy_ranges(&prediction.positions(), true);
+        assert_eq!((x, y), (2.0..4.0, -3.0..-1.0));
+
+        let path = out_path("single_xy.png");
+        plot_xy(&prediction, &path);
+        assert_non_empty_file(&path);
+
+        let path = out_path("single_speed.png");
+        plot_xy_speed(&prediction, &path);
+        assert_non_empty_file(&path);
+
+        let path = out_path("single_uncertainty.png");
+        plot_xy_uncertainty(&prediction, &[DMatrix::zeros(2, 2)], &path);
+        assert_non_empty_file(&path);
+
+        let path = out_path("single_envelope.png");
+        plot_ensemble_envelope(&[prediction.clone(), prediction], &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn custom_plot_options_render_at_the_requested_size() {
+        let prediction = predict(
+            &DroneInput::try_new(0.1, 0.2, 0.3).unwrap(),
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            5.0,
+            50,
+        );
+        let opts = PlotOptions {
+            title: "Report theme".to_owned(),
+            line_color: RGBColor(40, 40, 40),
+            start_color: CYAN,
+            end_color: MAGENTA,
+            marker_size: 8,
+            width: 640,
+            height: 480,
+        };
+
+        let path = out_path("custom_opts.png");
+        plot_xy_opts(&prediction, &opts, &path);
+        // PNG IHDR: big-endian width and height at bytes 16..24
+        let bytes = std::fs::read(&path).unwrap();
+        let dim = |at: usize| u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap());
+        assert_eq!((dim(16), dim(20)), (640, 480));
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn animation_writes_one_numbered_frame_each() {
+        let prediction = predict(
+            &DroneInput::try_new(0.0, 0.2, 0.5).unwrap(),
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            6.0,
+            60,
+        );
+        let dir = out_path("animation");
+        plot_animation(&prediction, &dir, 5);
+
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            (0..5)
+                .map(|k| format!("frame_{k:04}.png"))
+                .collect::<Vec<_>>()
+        );
+        for name in &names {
+            assert_non_empty_file(&dir.join(name));
+        }
+        std::fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn diff_plot_aligns_predictions_with_different_dt() {
+        let input = DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let reference = predict(
+            &input,
+            start,
+            &SimpleQuadcopter::new(0.2),
+            &mut Rk4,
+            0.0,
+            10.0,
+            100,
+        );
+        let candidate = predict(
+            &input,
+            start,
+            &SimpleQuadcopter::new(0.25),
+            &mut Rk4,
+            0.0,
+            10.0,
+            170,
+        );
+
+        let path = out_path("diff.png");
+        plot_xy_diff(&reference, &candidate, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn convex_hull_drops_interior_points() {
+        let hull = convex_hull(&[
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (1.0, 0.0),
+        ]);
+        assert_eq!(hull, vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_eq!(convex_hull(&[(1.0, 1.0), (1.0, 1.0)]), vec![(1.0, 1.0)]);
+    }
+
+    #[test]
+    fn ensemble_envelope_renders_wind_varied_ensemble() {
+        /// Steady wind acting through the linear drag term.
+        struct Wind(f64, f64);
+
+        impl DisturbanceModel<SimpleQuadcopter> for Wind {
+            fn extra_derivative(
+                &self,
+                _t: f64,
+                _state: &SimpleQuadState,
+                _control: &SimpleQuadControl,
+            ) -> SimpleQuadState {
+                SimpleQuadState::new(0.0, 0.0, DRAG * self.0, DRAG * self.1, 0.0)
+            }
+        }
+
+        const DRAG: f64 = 0.2;
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.15,
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let ensemble: Vec<_> = [(0.0, 0.0), (3.0, 0.0), (-3.0, 1.0), (0.0, 4.0), (1.0, -4.0)]
+            .into_iter()
+            .map(|(wn, we)| {
+                let model = Composed::new(SimpleQuadcopter::new(DRAG), Wind(wn, we));
+                predict(&input, start, &model, &mut Rk4, 0.0, 8.0, 80)
+            })
+            .collect();
+
+        let path = out_path("envelope.png");
+        plot_ensemble_envelope(&ensemble, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn endpoint_density_renders_a_few_hundred_member_ensemble() {
+        let model = SimpleQuadcopter::new(0.2);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let ensemble: Vec<_> = (0..300)
+            .map(|k| {
+                let input = DroneInput {
+                    roll_rad: 0.0,
+                    pitch_rad: 0.02 * (k % 15) as f64,
+                    yaw_rate_rps: -0.3 + 0.03 * (k / 15) as f64,
+                    thrust_norm: 0.5,
+                };
+                predict(&input, start, &model, &mut Rk4, 0.0, 6.0, 30)
+            })
+            .collect();
+
+        let path = out_path("endpoint_density.png");
+        plot_endpoint_density(&ensemble, 2.0, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn kernel_density_integrates_to_one() {
+        let points = [(0.0, 0.0), (3.0, -1.0), (1.0, 2.0)];
+        // Odd cell count, so the middle cell is centered on the origin
+        let (x_range, y_range) = (-7.5..7.5, -7.5..7.5);
+        let n = 151;
+        let density = kernel_density(&points, 0.5, &x_range, &y_range, n);
+
+        let cell_area = (15.0 / n as f64).powi(2);
+        assert!((density.sum() * cell_area - 1.0).abs() < 1e-6);
+        let peak = density.iamax_full();
+        assert_eq!(peak, (75, 75), "densest cell should sit on the origin");
+    }
+
+    #[test]
+    fn plot_controls_draws_two_segment_staircase() {
+        let hover = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let dash = DroneInput {
+            pitch_rad: 15f64.to_radians(),
+            yaw_rate_rps: 0.3,
+            ..hover
+        };
+        let prediction = predict_sequence(
+            &[(0.0, hover), (1.0, dash)],
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            3.0,
+            300,
+        );
+        assert_eq!(prediction.controls.len(), 2);
+
+        let path = out_path("controls.png");
+        plot_controls(&prediction, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn plot_xy_speed_handles_accelerate_then_decelerate() {
+        let forward = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 15f64.to_radians(),
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        };
+        let brake = DroneInput {
+            pitch_rad: -15f64.to_radians(),
+            ..forward
+        };
+        let prediction = predict_sequence(
+            &[(0.0, forward), (3.0, brake)],
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.2),
+            &mut Rk4,
+            0.0,
+            6.0,
+            600,
+        );
+
+        let path = out_path("xy_speed.png");
+        plot_xy_speed(&prediction, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn plot_xy_uncertainty_draws_widening_ellipses() {
+        let model = SimpleQuadcopter::new(0.0);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let prediction = crate::predict::predict(
+            &input,
+            SimpleQuadState::new(0.0, 0.0, 5.0, 0.0, 0.0),
+            &model,
+            &mut Rk4,
+            0.0,
+            10.0,
+            500,
+        );
+        let p0 = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![
+            0.1, 0.1, 0.04, 0.09, 0.0, 0.0, 0.0,
+        ]));
+        let covs =
+            crate::uncertainty::propagate_covariance(&model, &prediction, p0, DMatrix::zeros(7, 7));
+        assert!(covs[500][(1, 1)] > covs[250][(1, 1)]);
+
+        let path = out_path("xy_uncertainty.png");
+        plot_xy_uncertainty(&prediction, &covs, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn decimate_keeps_endpoints_and_caps_count() {
+        let items: Vec<usize> = (0..30_001).collect();
+
+        let kept = decimate(&items, 500);
+        assert_eq!(kept.len(), 500);
+        assert_eq!(kept[0], 0);
+        assert_eq!(kept[499], 30_000);
+        assert!(kept.windows(2).all(|w| w[1] > w[0]));
+
+        assert_eq!(decimate(&items[..10], 500), items[..10].to_vec());
+        assert_eq!(decimate(&items, 2), vec![0, 30_000]);
+    }
+
+    #[test]
+    fn plot_xy_decimated_writes_file() {
+        let input = DroneInput {
+            roll_rad: 10f64.to_radians(),
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        };
+        let prediction = crate::predict::predict(
+            &input,
+            SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            10.0,
+            30_000,
+        );
+
+        let path = out_path("xy_decimated.png");
+        plot_xy_decimated(&prediction, 400, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn plot_stability_boundaries_writes_file() {
+        use crate::solvers::{BackwardEuler, ForwardEuler, stability_boundary};
+
+        let path = out_path("stability_boundaries.png");
+        plot_stability_boundaries(
+            &[
+                (
+                    "ForwardEuler",
+                    stability_boundary(ForwardEuler::stability, 128),
+                ),
+                (
+                    "BackwardEuler",
+                    stability_boundary(BackwardEuler::stability, 128),
+                ),
+            ],
+            &path,
+        );
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn viridis_spans_dark_to_yellow() {
+        assert_eq!(viridis(0.0), RGBColor(68, 1, 84));
+        assert_eq!(viridis(1.0), RGBColor(253, 231, 37));
+        assert_eq!(viridis(2.0), viridis(1.0));
+    }
+
+    #[test]
+    fn plot_xy_multi_writes_fan() {
+        let base = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let fan = crate::predict::predict_yaw_fan(
+            &base,
+            &[-0.3, 0.0, 0.3],
+            &SimpleQuadState::zero(),
+            &SimpleQuadcopter::new(0.1),
+            &mut Rk4,
+            0.0,
+            5.0,
+            100,
+        );
+        let path = out_path("fan.png");
+        plot_xy_multi(&fan, &path);
+        assert_non_empty_file(&path);
+    }
+
+    #[test]
+    fn equal_aspect_frames_circle_with_equal_spans() {
+        // Half a circle: the data spans differ 2:1, the framed spans must not
+        let car = DubinsCar {
+            speed_mps: 3.0,
+            max_yaw_rate_rps: 1.0,
+        };
+        let turn = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.5,
+            thrust_norm: 0.5,
+        };
+        let circle = crate::predict::predict(
+            &turn,
+            DubinsState::new(10.0, -4.0, 0.0),
+            &car,
+            &mut Rk4,
+            0.0,
+            0.5 * std::f64::consts::TAU / 0.5,
+            400,
+        );
+        let points = circle.positions();
+
+        let (x, y) = xy_ranges(&points, true);
+        assert!(((x.end - x.start) - (y.end - y.start)).abs() < 1e-9);
+        for &(n, e) in &points {
+            assert!(x.contains(&n) && y.contains(&e));
+        }
+
+        let (x, y) = xy_ranges(&points, false);
+        assert!(((x.end - x.start) - (y.end - y.start)).abs() > 1.0);
+    }
+}
@@ -1,17 +1,245 @@
-use std::time::{Duration, Instant};
+use std::{
+    fmt::{self, Debug},
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+
+use nalgebra::{DMatrix, DVector, Vector2};
 
+#[cfg(feature = "trace")]
+use crate::trace;
 use crate::{
-    traits::{Dynamics, Stepper},
-    types::DroneInput,
+    frames, math,
+    traits::{Dynamics, LinearizableDynamics, StepStats, Stepper},
+    types::{
+        ControlChannels, DroneInput, GroundVelocity, Heading, IntegrableState, Position2D,
+        RateLimiter, Saturation, StateVector,
+    },
 };
 
+/// Tolerance used when checking that two predictions meet at a common junction.
+const JUNCTION_TOL: f64 = 1e-9;
+
+/// Steps between wall-clock checks in [`predict_budgeted`]; reading the clock every
+/// step would cost more than a cheap model's derivative.
+const BUDGET_CHECK_INTERVAL: usize = 16;
+
 #[derive(Debug, Clone)]
 pub struct Prediction<S, U> {
     pub states: Vec<S>,
     pub control: U,
+    /// Piecewise-constant control schedule as `(start time, control)`, in time order.
+    /// Constant-input predictions hold a single entry equal to `control`.
+    pub controls: Vec<(f64, U)>,
     pub t0: f64,
     pub t_final: f64,
-    cpu_time: Duration,
+    pub(crate) cpu_time: Duration,
+    /// Running totals recorded during integration by [`predict_cfg_with_accumulators`];
+    /// `None` otherwise, and after operations such as [`slice`](Prediction::slice)
+    /// that reshape the prediction. [`append`](Prediction::append) continues them when
+    /// both parts carry totals and drops them otherwise.
+    pub accumulators: Option<Accumulators>,
+    /// Absolute time of every stored state when the samples are not on the uniform
+    /// grid of `t_final / n()` from `t0`, e.g. strided output (see
+    /// [`HorizonConfig::with_output_stride`]), whose last interval may be shorter;
+    /// `None` for the uniform grid. Read through [`t_at`](Prediction::t_at).
+    pub(crate) sample_times: Option<Vec<f64>>,
+}
+
+/// Per-sample cumulative scalars, element `i` covering the path up to `states[i]`, so
+/// dashboards can read progress without re-walking the states.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accumulators {
+    /// XY distance travelled [m], as [`Prediction::arc_length`] up to the sample.
+    pub arc_length_m: Vec<f64>,
+    /// Quadratic control effort `∫ Σ u_k² dt` over the [`ControlChannels`].
+    pub control_effort: Vec<f64>,
+}
+
+impl Accumulators {
+    /// Continue the series with `next`, a prediction starting at this one's last
+    /// sample: its junction entry is dropped and the rest offset by the totals so far.
+    fn extend_from(&mut self, next: &Accumulators) {
+        for (series, tail) in [
+            (&mut self.arc_length_m, &next.arc_length_m),
+            (&mut self.control_effort, &next.control_effort),
+        ] {
+            let offset = series.last().copied().unwrap_or(0.0);
+            series.extend(tail.iter().skip(1).map(|v| offset + v));
+        }
+    }
+}
+
+/// Reasons two predictions cannot be joined with [`Prediction::append`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppendError {
+    /// `other.t0` does not equal `self.t_end()`.
+    TimeGap { t_end: f64, other_t0: f64 },
+    /// The segments use different step sizes, so `t_at` would be ambiguous.
+    StepMismatch { dt: f64, other_dt: f64 },
+    /// The last state of `self` and the first state of `other` differ.
+    StateMismatch { max_abs_diff: f64 },
+}
+
+impl fmt::Display for AppendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimeGap { t_end, other_t0 } => {
+                write!(
+                    f,
+                    "prediction ends at t={t_end} but next starts at t={other_t0}"
+                )
+            }
+            Self::StepMismatch { dt, other_dt } => {
+                write!(f, "step size mismatch: dt={dt} vs dt={other_dt}")
+            }
+            Self::StateMismatch { max_abs_diff } => {
+                write!(f, "junction states differ by up to {max_abs_diff}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppendError {}
+
+/// Motion phase of a [`Segment`], see [`Prediction::segment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Stationary,
+    Straight,
+    Turning,
+}
+
+/// Maximal run of samples sharing one [`Phase`]. Consecutive segments share their
+/// boundary time, so the list covers the horizon without gaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub phase: Phase,
+    pub t_start: f64,
+    pub t_end: f64,
+}
+
+/// Classification thresholds for [`Prediction::segment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentThresholds {
+    /// Path speeds below this [m/s] count as stationary.
+    pub stationary_speed_mps: f64,
+    /// Absolute path curvatures above this [1/m] count as turning.
+    pub turning_curvature_per_m: f64,
+}
+
+impl Default for SegmentThresholds {
+    fn default() -> Self {
+        Self {
+            stationary_speed_mps: 0.2,
+            turning_curvature_per_m: 0.02,
+        }
+    }
+}
+
+/// Time grid of a fixed-step prediction: `steps` steps of `t_final / steps` from `t0`.
+/// Fields are private so that a config always passes [`HorizonConfig::new`]'s checks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HorizonConfig {
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+    stride: usize,
+}
+
+/// Reasons [`HorizonConfig::new`] rejects a horizon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HorizonError {
+    /// `t0` is NaN or infinite.
+    NonFiniteStart { t0: f64 },
+    /// `t_final` is not finite and > 0.
+    InvalidDuration { t_final: f64 },
+    /// `steps` is zero.
+    NoSteps,
+}
+
+impl fmt::Display for HorizonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFiniteStart { t0 } => write!(f, "t0 must be finite, got {t0}"),
+            Self::InvalidDuration { t_final } => {
+                write!(f, "t_final must be finite and > 0, got {t_final}")
+            }
+            Self::NoSteps => write!(f, "steps must be > 0"),
+        }
+    }
+}
+
+impl std::error::Error for HorizonError {}
+
+impl HorizonConfig {
+    pub fn new(t0: f64, t_final: f64, steps: usize) -> Result<Self, HorizonError> {
+        if !t0.is_finite() {
+            return Err(HorizonError::NonFiniteStart { t0 });
+        }
+        if !(t_final.is_finite() && t_final > 0.0) {
+            return Err(HorizonError::InvalidDuration { t_final });
+        }
+        if steps == 0 {
+            return Err(HorizonError::NoSteps);
+        }
+        Ok(Self {
+            t0,
+            t_final,
+            steps,
+            stride: 1,
+        })
+    }
+
+    /// Integrate every step but store only every `stride`-th state, plus always the
+    /// final one: `steps.div_ceil(stride) + 1` states instead of `steps + 1`, at the
+    /// accuracy of the full step. When `stride` does not divide `steps` the last
+    /// interval is shorter; the prediction records its sample times, so
+    /// [`Prediction::t_at`] and the interpolating helpers account for it.
+    pub fn with_output_stride(self, stride: usize) -> Self {
+        assert!(stride > 0, "stride must be > 0");
+        Self { stride, ..self }
+    }
+
+    pub fn output_stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn t0(&self) -> f64 {
+        self.t0
+    }
+
+    /// Horizon duration [s], not the end time.
+    pub fn t_final(&self) -> f64 {
+        self.t_final
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.t_final / self.steps as f64
+    }
+}
+
+impl<S, U: Clone> Prediction<S, U> {
+    /// Prediction assembled from externally computed `states` under a constant
+    /// `control`, on the uniform grid over `t_final` seconds from `t0`. The CPU time is
+    /// zero until set with [`Prediction::with_cpu_time`].
+    pub fn new(states: Vec<S>, control: U, t0: f64, t_final: f64) -> Self {
+        assert!(!states.is_empty(), "need at least the initial state");
+        Self {
+            states,
+            controls: vec![(t0, control.clone())],
+            control,
+            t0,
+            t_final,
+            cpu_time: Duration::ZERO,
+            accumulators: None,
+            sample_times: None,
+        }
+    }
 }
 
 impl<S, U> Prediction<S, U> {
@@ -19,11 +247,14 @@ impl<S, U> Prediction<S, U> {
         self.states.len().saturating_sub(1)
     }
 
+    /// Time between stored samples. On a non-uniform grid this is only the first
+    /// interval, e.g. `stride` solver steps for strided output; use
+    /// [`t_at`](Self::t_at) or [`times`](Self::times) for the actual instants.
     pub fn dt(&self) -> f64 {
-        if self.n() == 0 {
-            0.0
-        } else {
-            self.t_final / self.n() as f64
+        match &self.sample_times {
+            Some(times) if times.len() >= 2 => times[1] - times[0],
+            _ if self.n() == 0 => 0.0,
+            _ => self.t_final / self.n() as f64,
         }
     }
 
@@ -35,11 +266,744 @@ impl<S, U> Prediction<S, U> {
         self.cpu_time.as_secs_f64()
     }
 
+    /// This prediction with `cpu_time` zeroed, e.g. for snapshots and serialized
+    /// comparisons.
+    pub fn without_timing(self) -> Self {
+        self.with_cpu_time(Duration::ZERO)
+    }
+
+    /// This prediction with `cpu_time` replaced, for runs timed elsewhere (another
+    /// process or device) or batches that time the whole set rather than each run.
+    pub fn with_cpu_time(self, cpu_time: Duration) -> Self {
+        Self { cpu_time, ..self }
+    }
+
+    /// Absolute time of sample `i`. Past the last sample a uniform grid is extended,
+    /// while a non-uniform one stays at the last sample.
     pub fn t_at(&self, i: usize) -> f64 {
-        self.t0 + (i as f64) * self.dt()
+        match &self.sample_times {
+            Some(times) => times[i.min(times.len() - 1)],
+            None => self.t0 + (i as f64) * self.dt(),
+        }
+    }
+
+    /// Absolute time of every stored state.
+    pub fn times(&self) -> Vec<f64> {
+        (0..self.states.len()).map(|i| self.t_at(i)).collect()
+    }
+
+    /// Sample interval `i → i + 1` holding `t`, clamped to the horizon, and the fraction
+    /// `s ∈ [0, 1]` of the way through it.
+    fn locate(&self, t: f64) -> (usize, f64) {
+        let last = self.states.len() - 1;
+        match &self.sample_times {
+            Some(times) => {
+                let i = times.partition_point(|&ti| ti <= t).clamp(1, last) - 1;
+                let s = (t - times[i]) / (times[i + 1] - times[i]);
+                (i, s.clamp(0.0, 1.0))
+            }
+            None => {
+                let u = ((t - self.t0) / self.dt()).clamp(0.0, last as f64);
+                let i = (u.floor() as usize).min(last - 1);
+                (i, u - i as f64)
+            }
+        }
+    }
+
+    /// Absolute time of the last state (`t0 + t_final`).
+    pub fn t_end(&self) -> f64 {
+        self.t0 + self.t_final
+    }
+
+    pub fn final_state(&self) -> &S {
+        self.states
+            .last()
+            .expect("prediction must contain at least one state")
+    }
+
+    /// Control active at time `t` (the last schedule entry starting at or before `t`).
+    pub fn control_at(&self, t: f64) -> &U {
+        self.controls
+            .iter()
+            .take_while(|(t_start, _)| *t_start <= t)
+            .last()
+            .map_or(&self.control, |(_, u)| u)
+    }
+
+    /// Start a new prediction from this one's final state and end time, e.g. for
+    /// receding-horizon loops. The result is standalone: its `t0` is `self.t_end()`
+    /// and it covers only the `additional_t` seconds.
+    pub fn continue_from<M, St>(
+        &self,
+        input: &DroneInput,
+        model: &M,
+        solver: &mut St,
+        additional_t: f64,
+        steps: usize,
+    ) -> Prediction<S, U>
+    where
+        S: Clone,
+        M: Dynamics<State = S, Control = U>,
+        St: Stepper<M>,
+    {
+        predict(
+            input,
+            self.final_state().clone(),
+            model,
+            solver,
+            self.t_end(),
+            additional_t,
+            steps,
+        )
+    }
+}
+
+/// Structural equality over every field except `cpu_time`, which varies from run to
+/// run, so that repeated runs of the same scenario compare equal. The sample times and
+/// accumulators are compared too, so the same states on different grids differ.
+impl<S: PartialEq, U: PartialEq> PartialEq for Prediction<S, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.states == other.states
+            && self.control == other.control
+            && self.controls == other.controls
+            && self.t0 == other.t0
+            && self.t_final == other.t_final
+            && self.accumulators == other.accumulators
+            && self.sample_times == other.sample_times
+    }
+}
+
+/// One-line summary for logs; use `{:?}` for the full state dump.
+impl<S: Position2D + GroundVelocity, U> fmt::Display for Prediction<S, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (n0, e0) = self
+            .states
+            .first()
+            .map_or((f64::NAN, f64::NAN), |s| s.position());
+        let end = self.states.last();
+        let (n1, e1) = end.map_or((f64::NAN, f64::NAN), |s| s.position());
+        let speed = end.map_or(f64::NAN, |s| s.ground_speed());
+        write!(
+            f,
+            "{} steps, dt = {:.4} s, t = {:.3}..{:.3} s, XY ({n0:.3}, {e0:.3}) -> \
+             ({n1:.3}, {e1:.3}) m, final speed {speed:.3} m/s, cpu {:.6} s",
+            self.n(),
+            self.dt(),
+            self.t0,
+            self.t_end(),
+            self.cpu_seconds(),
+        )
+    }
+}
+
+impl<S: Position2D, U> Prediction<S, U> {
+    /// Path velocity `(v_north, v_east)` [m/s] by central differences of the positions,
+    /// so that it works for states without a velocity.
+    fn path_velocity(&self) -> Vec<(f64, f64)> {
+        differentiate(&self.positions(), &self.times())
+    }
+
+    /// Signed curvature [1/m] of the XY path at every sample, positive when turning
+    /// right (clockwise), from finite differences of the positions. Zero where the path
+    /// does not move.
+    pub fn curvature_series(&self) -> Vec<f64> {
+        let velocity = self.path_velocity();
+        let accel = differentiate(&velocity, &self.times());
+        velocity
+            .iter()
+            .zip(&accel)
+            .map(|(&(vn, ve), &(an, ae))| {
+                let speed = vn.hypot(ve);
+                if speed < 1e-9 {
+                    0.0
+                } else {
+                    (vn * ae - ve * an) / speed.powi(3)
+                }
+            })
+            .collect()
+    }
+
+    /// Split the path into stationary, straight and turning [`Segment`]s by the path
+    /// speed and [`curvature_series`](Self::curvature_series) at each sample.
+    pub fn segment(&self, thresholds: &SegmentThresholds) -> Vec<Segment> {
+        let velocity = self.path_velocity();
+        let curvature = self.curvature_series();
+        let phases = velocity.iter().zip(&curvature).map(|(&(vn, ve), k)| {
+            if vn.hypot(ve) < thresholds.stationary_speed_mps {
+                Phase::Stationary
+            } else if k.abs() > thresholds.turning_curvature_per_m {
+                Phase::Turning
+            } else {
+                Phase::Straight
+            }
+        });
+
+        let mut segments: Vec<Segment> = Vec::new();
+        for (i, phase) in phases.enumerate() {
+            let t = self.t_at(i);
+            match segments.last_mut() {
+                Some(last) if last.phase == phase => last.t_end = t,
+                Some(last) => {
+                    last.t_end = t;
+                    segments.push(Segment {
+                        phase,
+                        t_start: t,
+                        t_end: t,
+                    });
+                }
+                None => segments.push(Segment {
+                    phase,
+                    t_start: t,
+                    t_end: t,
+                }),
+            }
+        }
+        segments
+    }
+
+    /// `(north, east)` of every state, for consumers that only need the path.
+    pub fn positions(&self) -> Vec<(f64, f64)> {
+        self.positions_iter().collect()
+    }
+
+    /// Borrowing variant of [`positions`](Self::positions).
+    pub fn positions_iter(&self) -> impl ExactSizeIterator<Item = (f64, f64)> + '_ {
+        self.states.iter().map(Position2D::position)
+    }
+
+    /// `(along, cross)` track error of every state relative to the line from
+    /// `line_start` to `line_end`, see [`frames::cross_track_error`].
+    pub fn cross_track_series(
+        &self,
+        line_start: (f64, f64),
+        line_end: (f64, f64),
+    ) -> Vec<(f64, f64)> {
+        self.positions_iter()
+            .map(|p| frames::cross_track_error(p, line_start, line_end))
+            .collect()
+    }
+
+    /// `(range_m, bearing_rad)` of every state as seen from `origin` `(north, east)`,
+    /// with bearing clockwise from North in `(-π, π]`.
+    pub fn range_bearing_series(&self, origin: (f64, f64)) -> Vec<(f64, f64)> {
+        self.positions_iter()
+            .map(|(n, e)| {
+                let (dn, de) = (n - origin.0, e - origin.1);
+                (dn.hypot(de), de.atan2(dn))
+            })
+            .collect()
+    }
+
+    /// Length of the XY path, summed over straight segments between samples.
+    pub fn arc_length(&self) -> f64 {
+        self.states
+            .windows(2)
+            .map(|w| {
+                let (x0, y0) = w[0].position();
+                let (x1, y1) = w[1].position();
+                (x1 - x0).hypot(y1 - y0)
+            })
+            .sum()
+    }
+
+    /// XY path reduced with Ramer-Douglas-Peucker: samples are dropped while the kept
+    /// polyline stays within `epsilon` [m] of them, so corners survive and straight
+    /// runs collapse to their endpoints (unlike uniform
+    /// [`decimate`](crate::plot::decimate)). The first and last samples are always kept.
+    pub fn simplify(&self, epsilon: f64) -> Vec<(f64, f64)> {
+        assert!(
+            epsilon.is_finite() && epsilon >= 0.0,
+            "epsilon must be finite and >= 0"
+        );
+        let points = self.positions();
+        if points.len() <= 2 {
+            return points;
+        }
+
+        let mut keep = vec![false; points.len()];
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+        let mut spans = vec![(0, points.len() - 1)];
+        while let Some((first, last)) = spans.pop() {
+            let (a, b) = (points[first], points[last]);
+            let deviation = |p: (f64, f64)| {
+                if a == b {
+                    (p.0 - a.0).hypot(p.1 - a.1)
+                } else {
+                    frames::cross_track_error(p, a, b).1.abs()
+                }
+            };
+            let farthest = (first + 1..last)
+                .map(|k| (k, deviation(points[k])))
+                .max_by(|x, y| x.1.total_cmp(&y.1));
+            if let Some((k, d)) = farthest
+                && d > epsilon
+            {
+                keep[k] = true;
+                spans.push((first, k));
+                spans.push((k, last));
+            }
+        }
+
+        points
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(p, kept)| kept.then_some(p))
+            .collect()
+    }
+
+    /// Where the XY path first enters `polygon` (vertices in order, implicitly closed),
+    /// as `(segment index, interpolated time, crossing point)`. Segment `i` joins
+    /// `states[i]` and `states[i + 1]`. A path that starts inside reports its start.
+    pub fn first_intersection(&self, polygon: &[(f64, f64)]) -> Option<(usize, f64, (f64, f64))> {
+        assert!(polygon.len() >= 3, "polygon needs at least 3 vertices");
+
+        self.first_entry(
+            |p| point_in_polygon(p, polygon),
+            |a, b| {
+                (0..polygon.len())
+                    .filter_map(|k| {
+                        segment_intersection(a, b, polygon[k], polygon[(k + 1) % polygon.len()])
+                    })
+                    .min_by(|x, y| x.total_cmp(y))
+            },
+        )
+    }
+
+    /// Like [`Prediction::first_intersection`] for a circular geofence.
+    pub fn first_circle_intersection(
+        &self,
+        center: (f64, f64),
+        radius: f64,
+    ) -> Option<(usize, f64, (f64, f64))> {
+        assert!(radius > 0.0, "radius must be > 0");
+
+        let inside = |(x, y): (f64, f64)| (x - center.0).hypot(y - center.1) <= radius;
+        self.first_entry(inside, |(ax, ay), (bx, by)| {
+            // |a + s (b - a) - c|² = r², smallest root in [0, 1]
+            let (dx, dy) = (bx - ax, by - ay);
+            let (fx, fy) = (ax - center.0, ay - center.1);
+            let a = dx * dx + dy * dy;
+            let b = 2.0 * (fx * dx + fy * dy);
+            let c = fx * fx + fy * fy - radius * radius;
+            let disc = b * b - 4.0 * a * c;
+            if a == 0.0 || disc < 0.0 {
+                return None;
+            }
+            let s = (-b - disc.sqrt()) / (2.0 * a);
+            (0.0..=1.0).contains(&s).then_some(s)
+        })
+    }
+
+    /// Closest approach of the XY path to `target` as `(segment index, time, distance)`.
+    /// Each segment `states[i] → states[i + 1]` is refined by projecting `target` onto
+    /// it, so the time is interpolated within the bracketing segment.
+    pub fn closest_approach(&self, target: (f64, f64)) -> (usize, f64, f64) {
+        assert!(!self.states.is_empty(), "prediction has no states");
+
+        let start = self.states[0].position();
+        let mut best = (0, self.t0, (start.0 - target.0).hypot(start.1 - target.1));
+
+        for (i, w) in self.states.windows(2).enumerate() {
+            let (a, b) = (w[0].position(), w[1].position());
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let len2 = dx * dx + dy * dy;
+            let s = if len2 > 0.0 {
+                (((target.0 - a.0) * dx + (target.1 - a.1) * dy) / len2).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let d = (a.0 + s * dx - target.0).hypot(a.1 + s * dy - target.1);
+            if d < best.2 {
+                best = (i, self.t_at(i) + s * (self.t_at(i + 1) - self.t_at(i)), d);
+            }
+        }
+        best
+    }
+
+    /// Shared scan: `inside` tests a point, `crossing` returns the first boundary
+    /// parameter `s ∈ [0, 1]` along a segment.
+    fn first_entry<I, C>(&self, inside: I, crossing: C) -> Option<(usize, f64, (f64, f64))>
+    where
+        I: Fn((f64, f64)) -> bool,
+        C: Fn((f64, f64), (f64, f64)) -> Option<f64>,
+    {
+        let start = self.states.first()?.position();
+        if inside(start) {
+            return Some((0, self.t0, start));
+        }
+
+        self.states.windows(2).enumerate().find_map(|(i, w)| {
+            let (a, b) = (w[0].position(), w[1].position());
+            crossing(a, b).map(|s| {
+                let point = (a.0 + s * (b.0 - a.0), a.1 + s * (b.1 - a.1));
+                (
+                    i,
+                    self.t_at(i) + s * (self.t_at(i + 1) - self.t_at(i)),
+                    point,
+                )
+            })
+        })
+    }
+}
+
+/// Even-odd ray-casting test.
+fn point_in_polygon((x, y): (f64, f64), polygon: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[j];
+        if (yi > y) != (yj > y) && x < xi + (y - yi) * (xj - xi) / (yj - yi) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Parameter `s ∈ [0, 1]` along `a → b` where it crosses `p → q`, if it does.
+fn segment_intersection(a: (f64, f64), b: (f64, f64), p: (f64, f64), q: (f64, f64)) -> Option<f64> {
+    let r = (b.0 - a.0, b.1 - a.1);
+    let e = (q.0 - p.0, q.1 - p.1);
+    let denom = r.0 * e.1 - r.1 * e.0;
+    if denom.abs() < f64::EPSILON {
+        return None; // parallel or degenerate
+    }
+    let ap = (p.0 - a.0, p.1 - a.1);
+    let s = (ap.0 * e.1 - ap.1 * e.0) / denom;
+    let u = (ap.0 * r.1 - ap.1 * r.0) / denom;
+    ((0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&u)).then_some(s)
+}
+
+impl<S: GroundVelocity, U> Prediction<S, U> {
+    /// Course over ground [rad] at every state.
+    pub fn track_series(&self) -> Vec<f64> {
+        self.states.iter().map(|s| s.ground_track_rad()).collect()
+    }
+
+    /// Ground speed [m/s] at every state.
+    pub fn speed_series(&self) -> Vec<f64> {
+        self.states.iter().map(|s| s.ground_speed()).collect()
+    }
+
+    /// Kinetic energy per unit mass of every state, see
+    /// [`GroundVelocity::kinetic_energy`]; check it with [`is_dissipative`].
+    pub fn energy_series(&self) -> Vec<f64> {
+        self.states
+            .iter()
+            .map(GroundVelocity::kinetic_energy)
+            .collect()
+    }
+}
+
+impl<S: GroundVelocity, U> Prediction<S, U> {
+    /// NED ground acceleration `(a_north, a_east)` [m/s²] at every sample, by central
+    /// differences of the ground velocity (one-sided at the ends).
+    pub fn accel_series(&self) -> Vec<(f64, f64)> {
+        let velocities: Vec<_> = self.states.iter().map(|s| s.ground_velocity()).collect();
+        differentiate(&velocities, &self.times())
+    }
+}
+
+impl<S: GroundVelocity + Heading, U> Prediction<S, U> {
+    /// Horizontal acceleration in the body frame, `(forward, right)` [m/s²]: the
+    /// [`accel_series`](Self::accel_series) rotated by each sample's yaw.
+    pub fn body_accel_series(&self) -> Vec<(f64, f64)> {
+        self.accel_series()
+            .into_iter()
+            .zip(&self.states)
+            .map(|((an, ae), s)| {
+                let a = math::rotation_body_from_ned(s.yaw_rad()) * Vector2::new(an, ae);
+                (a.x, a.y)
+            })
+            .collect()
+    }
+
+    /// Body-frame jerk `(forward, right)` [m/s³]: the time derivative of
+    /// [`body_accel_series`](Self::body_accel_series), differenced the same way.
+    pub fn jerk_series(&self) -> Vec<(f64, f64)> {
+        differentiate(&self.body_accel_series(), &self.times())
+    }
+}
+
+/// Central differences of a 2D series sampled at `times`, one-sided at the ends. On
+/// unequal intervals the two one-sided slopes are weighted so the estimate stays
+/// second-order at the middle sample.
+fn differentiate(series: &[(f64, f64)], times: &[f64]) -> Vec<(f64, f64)> {
+    let n = series.len();
+    if n < 2 {
+        return vec![(0.0, 0.0); n];
+    }
+    let slope = |a: usize, b: usize| {
+        let span = times[b] - times[a];
+        (
+            (series[b].0 - series[a].0) / span,
+            (series[b].1 - series[a].1) / span,
+        )
+    };
+    (0..n)
+        .map(|i| match i {
+            0 => slope(0, 1),
+            i if i == n - 1 => slope(n - 2, n - 1),
+            i => {
+                let (h1, h2) = (times[i] - times[i - 1], times[i + 1] - times[i]);
+                let (w1, w2) = (h2 / (h1 + h2), h1 / (h1 + h2));
+                let (before, after) = (slope(i - 1, i), slope(i, i + 1));
+                (w1 * before.0 + w2 * after.0, w1 * before.1 + w2 * after.1)
+            }
+        })
+        .collect()
+}
+
+impl<S: StateVector, U> Prediction<S, U> {
+    /// States as a matrix with one row per sample and one column per state component.
+    /// An empty prediction gives a `0 × 0` matrix.
+    pub fn to_matrix(&self) -> DMatrix<f64> {
+        let Some(first) = self.states.first() else {
+            return DMatrix::zeros(0, 0);
+        };
+        let dim = first.to_dvector().len();
+
+        let mut m = DMatrix::zeros(self.states.len(), dim);
+        for (i, s) in self.states.iter().enumerate() {
+            let v = s.to_dvector();
+            assert!(
+                v.len() == dim,
+                "state dimension changed along the prediction"
+            );
+            m.row_mut(i).copy_from(&v.transpose());
+        }
+        m
+    }
+
+    /// Time series of one state component (by `StateVector` index).
+    pub fn component_series(&self, component: usize) -> Vec<f64> {
+        self.states
+            .iter()
+            .map(|s| {
+                let v = s.to_dvector();
+                assert!(
+                    component < v.len(),
+                    "component {component} out of bounds for state of dim {}",
+                    v.len()
+                );
+                v[component]
+            })
+            .collect()
+    }
+
+    /// Step-response settling time [s, measured from `t0`] of a state component: the
+    /// time after which it stays within `tolerance` (a fraction of the total change,
+    /// e.g. 0.02) of its steady-state value, taken as the final sample.
+    pub fn settling_time(&self, component: usize, tolerance: f64) -> f64 {
+        assert!(tolerance > 0.0, "tolerance must be > 0");
+        let series = self.component_series(component);
+        let (initial, steady) = (series[0], series[series.len() - 1]);
+        let band = tolerance * (steady - initial).abs();
+
+        series
+            .iter()
+            .rposition(|v| (v - steady).abs() > band)
+            .map_or(0.0, |i| self.t_at(i + 1) - self.t0)
+    }
+
+    /// Peak fractional overshoot of a state component past its steady-state value (the
+    /// final sample), relative to the total change. Zero for monotone responses.
+    pub fn overshoot(&self, component: usize) -> f64 {
+        let series = self.component_series(component);
+        let (initial, steady) = (series[0], series[series.len() - 1]);
+        let change = steady - initial;
+        if change == 0.0 {
+            return 0.0;
+        }
+
+        series
+            .iter()
+            .map(|v| (v - steady) / change)
+            .fold(0.0, f64::max)
+    }
+
+    /// State at time `t`, clamped to the horizon. Between samples each component is
+    /// cubic Hermite interpolated, with finite-difference tangents from the neighbouring
+    /// samples, so interpolated paths stay smooth across sample boundaries. The
+    /// tangents are taken per unit time on the actual sample times, so unequal
+    /// intervals (e.g. the short last one of strided output) are interpolated
+    /// correctly.
+    pub fn state_at(&self, t: f64) -> S {
+        assert!(self.states.len() >= 2, "need at least 2 samples");
+        let last = self.states.len() - 1;
+        let (i, s) = self.locate(t);
+        let h = self.t_at(i + 1) - self.t_at(i);
+
+        let sample = |k: usize| self.states[k].to_dvector();
+        let tangent = |k: usize| {
+            let (lo, hi) = (k.saturating_sub(1), (k + 1).min(last));
+            (sample(hi) - sample(lo)) * (h / (self.t_at(hi) - self.t_at(lo)))
+        };
+
+        let (s2, s3) = (s * s, s * s * s);
+        let x = sample(i) * (2.0 * s3 - 3.0 * s2 + 1.0)
+            + tangent(i) * (s3 - 2.0 * s2 + s)
+            + sample(i + 1) * (3.0 * s2 - 2.0 * s3)
+            + tangent(i + 1) * (s3 - s2);
+        S::from_dvector(x)
+    }
+
+    /// Frames at a fixed real-time rate: `(t, state)` every `1 / fps` seconds from `t0`
+    /// up to the end of the horizon (backwards for backward predictions), independent
+    /// of the solver's `dt`, interpolated with [`state_at`](Self::state_at).
+    pub fn sample_fps(&self, fps: f64) -> Vec<(f64, S)> {
+        assert!(fps.is_finite() && fps > 0.0, "fps must be finite and > 0");
+
+        let frame_dt = self.t_final.signum() / fps;
+        let frames = (self.t_final.abs() * fps * (1.0 + 1e-12)).floor() as usize;
+        (0..=frames)
+            .map(|k| {
+                let t = self.t0 + k as f64 * frame_dt;
+                (t, self.state_at(t))
+            })
+            .collect()
+    }
+}
+
+impl<S: StateVector, U: Clone> Prediction<S, U> {
+    /// Drop the initial transient: the sub-prediction starting at the first sample where
+    /// `component` is within `tolerance` (a fraction of the total change, as in
+    /// [`settling_time`](Self::settling_time)) of its final value, with `t0` and the
+    /// control schedule shifted accordingly. Returns the whole prediction if the
+    /// component only gets there at the final sample.
+    pub fn trim_to_steady(&self, component: usize, tolerance: f64) -> Prediction<S, U> {
+        assert!(tolerance > 0.0, "tolerance must be > 0");
+        let series = self.component_series(component);
+        let (initial, steady) = (series[0], series[series.len() - 1]);
+        let band = tolerance * (steady - initial).abs();
+
+        let k = match series.iter().position(|v| (v - steady).abs() <= band) {
+            Some(k) if k + 1 < series.len() => k,
+            _ => return self.clone(),
+        };
+
+        let t0 = self.t_at(k);
+        let mut controls = vec![(t0, self.control_at(t0).clone())];
+        controls.extend(self.controls.iter().filter(|(t, _)| *t > t0).cloned());
+
+        Prediction {
+            states: self.states[k..].to_vec(),
+            control: self.control.clone(),
+            controls,
+            t0,
+            t_final: self.t_final - (t0 - self.t0),
+            cpu_time: self.cpu_time,
+            accumulators: None,
+            sample_times: self.sample_times.as_ref().map(|times| times[k..].to_vec()),
+        }
+    }
+
+    /// The part of a forward prediction within `[t_start, t_end]`, clamped to the
+    /// horizon. The stored samples inside the window are kept as they are, between
+    /// boundary states from [`state_at`](Self::state_at), so the window starts and ends
+    /// on exact interpolants; the result records its own sample times. A window wholly
+    /// outside the horizon clamps to the nearer end, a single state with zero duration.
+    /// `control` and `cpu_time` are kept; the control schedule is cut to the window.
+    pub fn slice(&self, t_start: f64, t_end: f64) -> Prediction<S, U> {
+        assert!(self.t_final > 0.0, "slice needs a forward prediction");
+        assert!(t_start <= t_end, "t_start must not be after t_end");
+        let a = t_start.clamp(self.t0, self.t_end());
+        let b = t_end.clamp(self.t0, self.t_end());
+
+        let (states, sample_times) = if b > a {
+            // Samples within rounding of a boundary are replaced by the boundary
+            let tol = JUNCTION_TOL * (b - a);
+            let times = self.times();
+            let inside = (0..times.len()).filter(|&i| times[i] > a + tol && times[i] < b - tol);
+            let mut states = vec![self.state_at(a)];
+            let mut window_times = vec![a];
+            for i in inside {
+                states.push(self.states[i].clone());
+                window_times.push(times[i]);
+            }
+            states.push(self.state_at(b));
+            window_times.push(b);
+            (states, Some(window_times))
+        } else {
+            (vec![self.state_at(a)], None)
+        };
+
+        let mut controls = vec![(a, self.control_at(a).clone())];
+        controls.extend(
+            self.controls
+                .iter()
+                .filter(|(t, _)| *t > a && *t < b)
+                .cloned(),
+        );
+
+        Prediction {
+            states,
+            control: self.control.clone(),
+            controls,
+            t0: a,
+            t_final: b - a,
+            cpu_time: self.cpu_time,
+            accumulators: None,
+            sample_times,
+        }
+    }
+
+    /// Extend this prediction with `other`, which must start where this one ends
+    /// (same time, same state, and the same `dt` if both are on uniform grids;
+    /// otherwise the joined prediction keeps both sets of sample times). The
+    /// duplicated junction state is dropped,
+    /// `t_final` and `cpu_time` accumulate, `control` keeps this prediction's value and
+    /// `other`'s schedule is appended to `controls`.
+    pub fn append(&mut self, other: &Prediction<S, U>) -> Result<(), AppendError> {
+        let t_end = self.t_end();
+        if (other.t0 - t_end).abs() > JUNCTION_TOL * t_end.abs().max(1.0) {
+            return Err(AppendError::TimeGap {
+                t_end,
+                other_t0: other.t0,
+            });
+        }
+
+        let uniform = self.sample_times.is_none() && other.sample_times.is_none();
+        let (dt, other_dt) = (self.dt(), other.dt());
+        if uniform && (dt - other_dt).abs() > JUNCTION_TOL * dt.abs().max(1.0) {
+            return Err(AppendError::StepMismatch { dt, other_dt });
+        }
+
+        let last = self.final_state().to_dvector();
+        let first = other.states[0].to_dvector();
+        let max_abs_diff = (last - first).amax();
+        if max_abs_diff > JUNCTION_TOL {
+            return Err(AppendError::StateMismatch { max_abs_diff });
+        }
+
+        if !uniform {
+            let mut times = self.times();
+            times.extend(other.times().into_iter().skip(1));
+            self.sample_times = Some(times);
+        }
+        self.accumulators = match (self.accumulators.take(), &other.accumulators) {
+            (Some(mut acc), Some(next)) => {
+                acc.extend_from(next);
+                Some(acc)
+            }
+            _ => None,
+        };
+        self.states.extend(other.states.iter().skip(1).cloned());
+        self.controls.extend(other.controls.iter().cloned());
+        self.t_final += other.t_final;
+        self.cpu_time += other.cpu_time;
+        Ok(())
     }
 }
 
+/// Whether `series` (e.g. an [`energy_series`](Prediction::energy_series)) never
+/// increases by more than `tolerance` from one sample to the next.
+pub fn is_dissipative(series: &[f64], tolerance: f64) -> bool {
+    series.windows(2).all(|w| w[1] <= w[0] + tolerance)
+}
+
 /// Predict future states assuming constant input over the horizon
 pub fn predict<M, S>(
     input: &DroneInput,
@@ -54,34 +1018,2122 @@ where
     M: Dynamics,
     S: Stepper<M>,
 {
-    assert!(steps > 0, "steps must be > 0");
-    assert!(
-        t_final.is_finite() && t_final > 0.0,
-        "t_final must be finite and > 0"
-    );
+    predict_with_observer(
+        input,
+        initial_state,
+        model,
+        solver,
+        t0,
+        t_final,
+        steps,
+        |_, _, _| {},
+    )
+}
 
-    let dt = t_final / steps as f64;
-    let start = Instant::now();
+/// [`predict`] with the time grid given as a validated [`HorizonConfig`], storing
+/// states at the config's output stride.
+pub fn predict_cfg<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    cfg: &HorizonConfig,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    predict_strided(
+        input,
+        initial_state,
+        model,
+        solver,
+        cfg.t0,
+        cfg.t_final,
+        cfg.steps,
+        cfg.stride,
+        |_, _, _| {},
+    )
+}
 
-    let control = model.input_to_control(input);
+/// [`predict_cfg`], also recording [`Accumulators`] at the stored samples, summed over
+/// every step.
+pub fn predict_cfg_with_accumulators<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    cfg: &HorizonConfig,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    M::State: Position2D,
+    M::Control: ControlChannels,
+    S: Stepper<M>,
+{
+    let recorded = |i: usize| i.is_multiple_of(cfg.stride) || i == cfg.steps;
+    let mut arc_length_m = Vec::with_capacity(cfg.steps.div_ceil(cfg.stride) + 1);
+    let mut travelled = 0.0;
+    let mut last = initial_state.position();
+    arc_length_m.push(0.0);
 
-    let mut states = Vec::with_capacity(steps + 1);
-    let mut state = initial_state;
+    let mut prediction = predict_strided(
+        input,
+        initial_state,
+        model,
+        solver,
+        cfg.t0,
+        cfg.t_final,
+        cfg.steps,
+        cfg.stride,
+        |i, _, state| {
+            let (n, e) = state.position();
+            travelled += (n - last.0).hypot(e - last.1);
+            last = (n, e);
+            if recorded(i) {
+                arc_length_m.push(travelled);
+            }
+        },
+    );
 
-    states.push(state.clone());
+    // The control is constant, so the effort grows by the same amount every step
+    let power: f64 = prediction.control.channels().iter().map(|u| u * u).sum();
+    let control_effort = (0..=cfg.steps)
+        .filter(|&i| recorded(i))
+        .map(|i| power * cfg.dt() * i as f64)
+        .collect();
+    prediction.accumulators = Some(Accumulators {
+        arc_length_m,
+        control_effort,
+    });
+    prediction
+}
 
-    for i in 0..steps {
-        let t = t0 + i as f64 * dt;
-        model.validate_state(&state);
-        state = solver.step(model, t, &state, &control, dt);
-        states.push(state.clone());
-    }
+/// [`predict`] straight to a states matrix, see [`Prediction::to_matrix`].
+pub fn predict_to_matrix<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> DMatrix<f64>
+where
+    M: Dynamics,
+    M::State: StateVector,
+    S: Stepper<M>,
+{
+    predict(input, initial_state, model, solver, t0, t_final, steps).to_matrix()
+}
 
-    Prediction {
-        states,
-        control,
-        t0,
-        t_final,
-        cpu_time: start.elapsed(),
+/// Same as [`predict`], but calls `observer(i, t, state)` after every accepted step,
+/// where `i` is the index of the new state in `Prediction::states` and `t` its time.
+/// The observer only sees a shared reference, so it cannot alter the integration.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_with_observer<M, S, O>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+    observer: O,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+    O: FnMut(usize, f64, &M::State),
+{
+    predict_strided(
+        input,
+        initial_state,
+        model,
+        solver,
+        t0,
+        t_final,
+        steps,
+        1,
+        observer,
+    )
+}
+
+/// Fixed-step driver behind [`predict_with_observer`] and [`predict_cfg`]: integrates
+/// every step but stores only every `stride`-th state plus the final one. The observer
+/// sees every step, with `i` the solver step index.
+#[allow(clippy::too_many_arguments)]
+fn predict_strided<M, S, O>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+    stride: usize,
+    mut observer: O,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+    O: FnMut(usize, f64, &M::State),
+{
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        t_final.is_finite() && t_final > 0.0,
+        "t_final must be finite and > 0"
+    );
+    assert!(stride > 0, "stride must be > 0");
+
+    let dt = t_final / steps as f64;
+    let start = Instant::now();
+
+    let mut control = model.input_to_control(input);
+
+    let mut states = Vec::with_capacity(steps.div_ceil(stride) + 1);
+    let mut times = Vec::with_capacity(steps.div_ceil(stride) + 1);
+    states.push(initial_state.clone());
+    times.push(t0);
+
+    drive(
+        model,
+        solver,
+        StepGrid::Uniform { t0, dt, steps },
+        initial_state,
+        &mut control,
+        hold_control,
+        |i, t, state| {
+            observer(i, t, state);
+            if i.is_multiple_of(stride) || i == steps {
+                states.push(state.clone());
+                times.push(if i == steps { t0 + t_final } else { t });
+            }
+        },
+    );
+
+    Prediction {
+        states,
+        controls: vec![(t0, control.clone())],
+        control,
+        t0,
+        t_final,
+        cpu_time: start.elapsed(),
+        accumulators: None,
+        sample_times: (stride > 1).then_some(times),
+    }
+}
+
+/// Panic with full context if `state` (reached at step `step`, time `t`) has a
+/// non-finite component, instead of failing later inside the model with only a field
+/// name.
+fn check_finite_step<S, U>(step: usize, t: f64, previous: &S, state: &S, control: &U)
+where
+    S: IntegrableState + Debug,
+    U: Debug,
+{
+    if let Some(field) = state.non_finite_field() {
+        panic!(
+            "non-finite state at step {step} (t = {t}): field `{field}` is not finite\n  \
+             control: {control:?}\n  previous state: {previous:?}\n  state: {state:?}"
+        );
+    }
+}
+
+/// Step sizes of a [`drive`] run: `steps` equal steps of `dt` from `t0` (negative `dt`
+/// runs backward), or the intervals of an explicit time grid.
+#[derive(Clone, Copy)]
+enum StepGrid<'a> {
+    Uniform { t0: f64, dt: f64, steps: usize },
+    Explicit(&'a [f64]),
+}
+
+impl StepGrid<'_> {
+    fn steps(&self) -> usize {
+        match self {
+            Self::Uniform { steps, .. } => *steps,
+            Self::Explicit(times) => times.len() - 1,
+        }
+    }
+
+    /// Start time and size of step `i`.
+    fn step(&self, i: usize) -> (f64, f64) {
+        match self {
+            Self::Uniform { t0, dt, .. } => (t0 + i as f64 * dt, *dt),
+            Self::Explicit(times) => (times[i], times[i + 1] - times[i]),
+        }
+    }
+}
+
+/// The step loop behind every fixed-step driver. Before step `i` (at time `t`),
+/// `before(i, t, control)` may update the control or stop the run; the state is then
+/// validated, stepped, projected and checked for non-finite components, and
+/// `after(i + 1, t + dt, state)` sees the state reached. Returns the last state and
+/// the number of steps taken. Under the `trace` feature it emits the run's start,
+/// strided step and end events.
+fn drive<M, S, B, A>(
+    model: &M,
+    solver: &mut S,
+    grid: StepGrid<'_>,
+    initial_state: M::State,
+    control: &mut M::Control,
+    mut before: B,
+    mut after: A,
+) -> (M::State, usize)
+where
+    M: Dynamics,
+    S: Stepper<M>,
+    B: FnMut(usize, f64, &mut M::Control) -> ControlFlow<()>,
+    A: FnMut(usize, f64, &M::State),
+{
+    #[cfg(feature = "trace")]
+    let start = {
+        let (t0, dt) = grid.step(0);
+        let steps = grid.steps();
+        trace::emit(trace::TraceEvent::PredictStart { t0, dt, steps });
+        Instant::now()
+    };
+
+    let mut state = initial_state;
+    let mut taken = grid.steps();
+    for i in 0..grid.steps() {
+        let (t, dt) = grid.step(i);
+        if before(i, t, control).is_break() {
+            taken = i;
+            break;
+        }
+        model.validate_state(&state);
+        let mut next = solver.step(model, t, &state, control, dt);
+        model.project_state(&mut next);
+        check_finite_step(i + 1, t + dt, &state, &next, control);
+        state = next;
+        after(i + 1, t + dt, &state);
+        #[cfg(feature = "trace")]
+        if trace::wants_step(i + 1) {
+            trace::emit(trace::TraceEvent::Step {
+                step: i + 1,
+                t: t + dt,
+                state: &state,
+            });
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    trace::emit(trace::TraceEvent::PredictEnd {
+        steps: taken,
+        cpu_time: start.elapsed(),
+    });
+    (state, taken)
+}
+
+/// `before` hook of [`drive`] for a control held over the whole run.
+fn hold_control<U>(_: usize, _: f64, _: &mut U) -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+/// Predict on an explicit, possibly non-uniform time grid (e.g. telemetry instants),
+/// stepping from `times[i]` to `times[i + 1]` and storing one state per grid point.
+/// The result spans `times[0]..=times.last()` and keeps the grid, so
+/// [`Prediction::t_at`] and the interpolating helpers use the exact instants;
+/// [`Prediction::dt`] is only the first interval.
+pub fn predict_on_grid<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    times: &[f64],
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(times.len() >= 2, "time grid needs at least 2 points");
+    assert!(
+        times.iter().all(|t| t.is_finite()),
+        "time grid must be finite"
+    );
+    assert!(
+        times.windows(2).all(|w| w[1] > w[0]),
+        "time grid must be strictly increasing"
+    );
+
+    let start = Instant::now();
+    let mut control = model.input_to_control(input);
+
+    let mut states = Vec::with_capacity(times.len());
+    states.push(initial_state.clone());
+    drive(
+        model,
+        solver,
+        StepGrid::Explicit(times),
+        initial_state,
+        &mut control,
+        hold_control,
+        |_, _, state| states.push(state.clone()),
+    );
+
+    let t0 = times[0];
+    Prediction {
+        states,
+        controls: vec![(t0, control.clone())],
+        control,
+        t0,
+        t_final: times[times.len() - 1] - t0,
+        cpu_time: start.elapsed(),
+        accumulators: None,
+        sample_times: Some(times.to_vec()),
+    }
+}
+
+/// Same as [`predict`], but also returns the solver's [`StepStats`] for every step, e.g.
+/// to see whether Backward Euler's Newton iteration is struggling.
+pub fn predict_with_stats<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> (Prediction<M::State, M::Control>, Vec<StepStats>)
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    let mut recording = RecordingStepper {
+        inner: solver,
+        stats: Vec::with_capacity(steps),
+    };
+    let prediction = predict(
+        input,
+        initial_state,
+        model,
+        &mut recording,
+        t0,
+        t_final,
+        steps,
+    );
+    (prediction, recording.stats)
+}
+
+/// Forwards to `inner`, collecting its stats after each step.
+struct RecordingStepper<'a, S> {
+    inner: &'a mut S,
+    stats: Vec<StepStats>,
+}
+
+impl<M: Dynamics, S: Stepper<M>> Stepper<M> for RecordingStepper<'_, S> {
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        let next = self.inner.step(model, t, state, control, dt);
+        self.stats.push(self.inner.last_step_stats());
+        next
+    }
+
+    fn order(&self) -> usize {
+        self.inner.order()
+    }
+
+    fn last_step_stats(&self) -> StepStats {
+        self.inner.last_step_stats()
+    }
+}
+
+/// Integrate backward in time from `t0` over `duration` seconds, e.g. to recover a
+/// past state for data assimilation. `states[i]` is the state at `t0 - i * duration /
+/// steps`; the returned `t_final` is `-duration`, so `dt`, `t_at` and `t_end` all run
+/// into the past. Only solvers accepting negative `dt` can be used (ForwardEuler, Rk4).
+pub fn predict_backward<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    duration: f64,
+    steps: usize,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        duration.is_finite() && duration > 0.0,
+        "duration must be finite and > 0"
+    );
+
+    let dt = -duration / steps as f64;
+    let start = Instant::now();
+
+    let mut control = model.input_to_control(input);
+
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(initial_state.clone());
+    drive(
+        model,
+        solver,
+        StepGrid::Uniform { t0, dt, steps },
+        initial_state,
+        &mut control,
+        hold_control,
+        |_, _, state| states.push(state.clone()),
+    );
+
+    Prediction {
+        states,
+        controls: vec![(t0, control.clone())],
+        control,
+        t0,
+        t_final: -duration,
+        cpu_time: start.elapsed(),
+        accumulators: None,
+        sample_times: None,
+    }
+}
+
+/// Result of [`predict_with_sensitivity`].
+#[derive(Debug, Clone)]
+pub struct SensitivityPrediction<S, U> {
+    pub prediction: Prediction<S, U>,
+    /// `∂x(t_i)/∂(roll, pitch, yaw rate)` per state, as `n × 3` matrices; the first is
+    /// zero since the initial state does not depend on the input.
+    pub sensitivities: Vec<DMatrix<f64>>,
+}
+
+/// [`predict`] with RK4, also integrating the forward sensitivity of the trajectory to
+/// the stick channels through the variational equation `S' = J S + ∂f/∂u · ∂u/∂input`,
+/// with `J` from [`LinearizableDynamics::jacobian`], `∂f/∂u` from
+/// [`LinearizableDynamics::control_jacobian`] and `∂u/∂input` by central differences
+/// of `input_to_control`. State and sensitivity share the RK4 stages, so the
+/// sensitivities are exact derivatives of the returned states up to the integration
+/// error. Post-step projections ([`Dynamics::project_state`]) are not applied.
+pub fn predict_with_sensitivity<M>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> SensitivityPrediction<M::State, M::Control>
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+    M::Control: ControlChannels,
+{
+    const FD_STEP: f64 = 1e-6;
+
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        t_final.is_finite() && t_final > 0.0,
+        "t_final must be finite and > 0"
+    );
+
+    let dt = t_final / steps as f64;
+    let start = Instant::now();
+    let control = model.input_to_control(input);
+
+    // ∂u/∂(roll, pitch, yaw rate); the control is constant over the horizon
+    let control_channels = |h: f64, k: usize| {
+        let mut p = *input;
+        match k {
+            0 => p.roll_rad += h,
+            1 => p.pitch_rad += h,
+            _ => p.yaw_rate_rps += h,
+        }
+        DVector::from_vec(model.input_to_control(&p).channels())
+    };
+    let du_dinput =
+        DMatrix::from_columns(&[0, 1, 2].map(|k| {
+            (control_channels(FD_STEP, k) - control_channels(-FD_STEP, k)) / (2.0 * FD_STEP)
+        }));
+
+    // Augmented derivative (x', S') at (t, x, S)
+    let rates = |t: f64, x: &DVector<f64>, s: &DMatrix<f64>| {
+        let state = M::State::from_dvector(x.clone());
+        let dx = model.derivative(t, &state, &control).to_dvector();
+        let ds = model.jacobian(t, &state, &control) * s
+            + model.control_jacobian(t, &state, &control) * &du_dinput;
+        (dx, ds)
+    };
+
+    let mut x = initial_state.to_dvector();
+    let mut s = DMatrix::zeros(x.len(), 3);
+    let mut states = Vec::with_capacity(steps + 1);
+    let mut sensitivities = Vec::with_capacity(steps + 1);
+    states.push(initial_state);
+    sensitivities.push(s.clone());
+
+    for i in 0..steps {
+        let t = t0 + i as f64 * dt;
+        let (k1x, k1s) = rates(t, &x, &s);
+        let (k2x, k2s) = rates(
+            t + 0.5 * dt,
+            &(&x + &k1x * (0.5 * dt)),
+            &(&s + &k1s * (0.5 * dt)),
+        );
+        let (k3x, k3s) = rates(
+            t + 0.5 * dt,
+            &(&x + &k2x * (0.5 * dt)),
+            &(&s + &k2s * (0.5 * dt)),
+        );
+        let (k4x, k4s) = rates(t + dt, &(&x + &k3x * dt), &(&s + &k3s * dt));
+
+        x += (k1x + k2x * 2.0 + k3x * 2.0 + k4x) * (dt / 6.0);
+        s += (k1s + k2s * 2.0 + k3s * 2.0 + k4s) * (dt / 6.0);
+
+        let next = M::State::from_dvector(x.clone());
+        check_finite_step(i + 1, t + dt, states.last().unwrap(), &next, &control);
+        states.push(next);
+        sensitivities.push(s.clone());
+    }
+
+    SensitivityPrediction {
+        prediction: Prediction {
+            states,
+            controls: vec![(t0, control.clone())],
+            control,
+            t0,
+            t_final,
+            cpu_time: start.elapsed(),
+            accumulators: None,
+            sample_times: None,
+        },
+        sensitivities,
+    }
+}
+
+/// Result of [`predict_budgeted`].
+#[derive(Debug, Clone)]
+pub struct BudgetedPrediction<S, U> {
+    /// The simulated part of the horizon; `t_final` is the time actually reached.
+    pub prediction: Prediction<S, U>,
+    /// `true` when the budget ran out before the requested horizon was reached.
+    pub truncated: bool,
+}
+
+/// Same as [`predict`], but stops early once `budget` of wall-clock time has elapsed.
+/// The clock is checked every 16 steps, so the budget may be
+/// overrun by at most that many steps. The step size is that of the full horizon.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_budgeted<M, S>(
+    input: &DroneInput,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+    budget: Duration,
+) -> BudgetedPrediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        t_final.is_finite() && t_final > 0.0,
+        "t_final must be finite and > 0"
+    );
+
+    let dt = t_final / steps as f64;
+    let start = Instant::now();
+
+    let mut control = model.input_to_control(input);
+
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(initial_state.clone());
+    let (_, simulated) = drive(
+        model,
+        solver,
+        StepGrid::Uniform { t0, dt, steps },
+        initial_state,
+        &mut control,
+        |i, _, _| {
+            if i > 0 && i.is_multiple_of(BUDGET_CHECK_INTERVAL) && start.elapsed() >= budget {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+        |_, _, state| states.push(state.clone()),
+    );
+    let truncated = simulated < steps;
+
+    BudgetedPrediction {
+        prediction: Prediction {
+            states,
+            controls: vec![(t0, control.clone())],
+            control,
+            t0,
+            t_final: if truncated {
+                simulated as f64 * dt
+            } else {
+                t_final
+            },
+            cpu_time: start.elapsed(),
+            accumulators: None,
+            sample_times: None,
+        },
+        truncated,
+    }
+}
+
+/// Predict under a time-stamped input schedule `(start time, input)`, sorted by time.
+/// The input active at the start of each step is held for that step; before the first
+/// entry the first input applies. The grid is uniform as in [`predict`].
+pub fn predict_sequence<M, S>(
+    schedule: &[(f64, DroneInput)],
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    predict_schedule(
+        schedule,
+        None,
+        initial_state,
+        model,
+        solver,
+        t0,
+        t_final,
+        steps,
+    )
+}
+
+/// Like [`predict_sequence`], but the scheduled inputs are slew-rate limited by
+/// `limiter` before driving the model. The command at `t0` is the limiter's previous
+/// input; at every later grid point it moves toward the active scheduled input by at
+/// most one step's worth of slew. The limiter is left at the last applied command.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_sequence_rate_limited<M, S>(
+    schedule: &[(f64, DroneInput)],
+    limiter: &mut RateLimiter,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    predict_schedule(
+        schedule,
+        Some(limiter),
+        initial_state,
+        model,
+        solver,
+        t0,
+        t_final,
+        steps,
+    )
+}
+
+/// Channels `model` clamps for any input of `schedule`, i.e. whether the commands of a
+/// [`predict_sequence`] asked for more than the model can deliver over the horizon.
+pub fn schedule_saturation<M: Dynamics>(model: &M, schedule: &[(f64, DroneInput)]) -> Saturation {
+    schedule
+        .iter()
+        .map(|(_, input)| model.input_to_control_checked(input).1)
+        .fold(Saturation::default(), Saturation::union)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn predict_schedule<M, S>(
+    schedule: &[(f64, DroneInput)],
+    mut limiter: Option<&mut RateLimiter>,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(!schedule.is_empty(), "schedule must not be empty");
+    assert!(
+        schedule.windows(2).all(|w| w[0].0 <= w[1].0),
+        "schedule must be sorted by time"
+    );
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        t_final.is_finite() && t_final > 0.0,
+        "t_final must be finite and > 0"
+    );
+
+    let dt = t_final / steps as f64;
+    let start = Instant::now();
+
+    let mut active = 0;
+    let mut input = match limiter.as_deref_mut() {
+        Some(limiter) => limiter.previous(),
+        None => schedule[0].1,
+    };
+    let mut control = model.input_to_control(&input);
+    let mut controls = vec![(t0, control.clone())];
+
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(initial_state.clone());
+
+    drive(
+        model,
+        solver,
+        StepGrid::Uniform { t0, dt, steps },
+        initial_state,
+        &mut control,
+        |i, t, control| {
+            let next = active
+                + schedule[active + 1..]
+                    .iter()
+                    .take_while(|(t_switch, _)| *t_switch <= t)
+                    .count();
+            let switched = next != active;
+            active = next;
+
+            // Unlimited inputs change only at schedule switches; limited ones while
+            // slewing
+            let (target, changed) = match limiter.as_deref_mut() {
+                Some(limiter) => {
+                    let target = match i {
+                        0 => limiter.previous(),
+                        _ => limiter.apply(&schedule[active].1, dt),
+                    };
+                    (target, target != input)
+                }
+                None => (schedule[active].1, switched),
+            };
+            if changed {
+                input = target;
+                *control = model.input_to_control(&input);
+                controls.push((t, control.clone()));
+            }
+            ControlFlow::Continue(())
+        },
+        |_, _, state| states.push(state.clone()),
+    );
+
+    Prediction {
+        states,
+        control: controls[0].1.clone(),
+        controls,
+        t0,
+        t_final,
+        cpu_time: start.elapsed(),
+        accumulators: None,
+        sample_times: None,
+    }
+}
+
+/// Final state for each candidate input, integrating from `t = 0` without storing the
+/// trajectories. Equivalent to `predict(...).final_state()` per input, but far lighter
+/// when sweeping many candidates.
+pub fn predict_endpoints<M, S>(
+    inputs: &[DroneInput],
+    initial_state: &M::State,
+    model: &M,
+    solver: &mut S,
+    t_final: f64,
+    steps: usize,
+) -> Vec<M::State>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(steps > 0, "steps must be > 0");
+    assert!(
+        t_final.is_finite() && t_final > 0.0,
+        "t_final must be finite and > 0"
+    );
+
+    let dt = t_final / steps as f64;
+
+    inputs
+        .iter()
+        .map(|input| {
+            let mut control = model.input_to_control(input);
+            let grid = StepGrid::Uniform { t0: 0.0, dt, steps };
+            let (state, _) = drive(
+                model,
+                solver,
+                grid,
+                initial_state.clone(),
+                &mut control,
+                hold_control,
+                |_, _, _| {},
+            );
+            state
+        })
+        .collect()
+}
+
+/// One prediction per yaw rate, all sharing `base_input`'s roll, pitch and throttle:
+/// a "spread of turns" for [`plot_xy_multi`](crate::plot::plot_xy_multi).
+#[allow(clippy::too_many_arguments)]
+pub fn predict_yaw_fan<M, S>(
+    base_input: &DroneInput,
+    yaw_rates: &[f64],
+    initial_state: &M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> Vec<Prediction<M::State, M::Control>>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    yaw_rates
+        .iter()
+        .map(|&yaw_rate_rps| {
+            let input = DroneInput {
+                yaw_rate_rps,
+                ..*base_input
+            };
+            predict(
+                &input,
+                initial_state.clone(),
+                model,
+                solver,
+                t0,
+                t_final,
+                steps,
+            )
+        })
+        .collect()
+}
+
+/// Interpolation profile used by [`predict_ramp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RampShape {
+    Linear,
+    /// `3s² - 2s³`: zero stick velocity at both ends of the ramp.
+    SmoothStep,
+}
+
+impl RampShape {
+    fn apply(self, s: f64) -> f64 {
+        let s = s.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => s,
+            Self::SmoothStep => s * s * (3.0 - 2.0 * s),
+        }
+    }
+}
+
+/// Predict while the pilot moves the stick from `from` to `to` over `ramp_time` seconds,
+/// then holds `to`. The input is updated at every step, so `controls` holds one entry
+/// per step of the ramp.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_ramp<M, S>(
+    from: &DroneInput,
+    to: &DroneInput,
+    ramp_time: f64,
+    shape: RampShape,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut S,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> Prediction<M::State, M::Control>
+where
+    M: Dynamics,
+    S: Stepper<M>,
+{
+    assert!(
+        ramp_time.is_finite() && ramp_time >= 0.0,
+        "ramp_time must be finite and >= 0"
+    );
+    assert!(steps > 0, "steps must be > 0");
+
+    let dt = t_final / steps as f64;
+    let mut schedule = vec![(t0, *from)];
+    for i in 1..=steps {
+        let elapsed = i as f64 * dt;
+        if elapsed >= ramp_time {
+            schedule.push((t0 + elapsed, *to));
+            break;
+        }
+        let s = shape.apply(elapsed / ramp_time);
+        schedule.push((t0 + elapsed, from.lerp(to, s)));
+    }
+
+    predict_sequence(&schedule, initial_state, model, solver, t0, t_final, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{DubinsCar, DubinsState, SimpleQuadState, SimpleQuadcopter},
+        solvers::{ForwardEuler, Rk4},
+        types::SlewRates,
+    };
+
+    const STRAIGHT: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    fn input() -> DroneInput {
+        DroneInput {
+            roll_rad: 5f64.to_radians(),
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.2,
+            thrust_norm: 0.5,
+        }
+    }
+
+    #[test]
+    fn externally_built_prediction_keeps_the_given_cpu_time() {
+        let states: Vec<_> = (0..5)
+            .map(|k| SimpleQuadState::new(k as f64, 0.0, 1.0, 0.0, 0.0))
+            .collect();
+        let control = SimpleQuadcopter::new(0.1).input_to_control(&STRAIGHT);
+        let prediction =
+            Prediction::new(states, control, 2.0, 4.0).with_cpu_time(Duration::from_micros(1500));
+
+        assert_eq!(prediction.cpu_time(), Duration::from_micros(1500));
+        assert_eq!(prediction.cpu_seconds(), 0.0015);
+        assert_eq!(prediction.dt(), 1.0);
+        assert_eq!(prediction.t_at(4), 6.0);
+        assert_eq!(prediction.controls, vec![(2.0, control)]);
+        assert_eq!(prediction.without_timing().cpu_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn predict_cfg_matches_positional_predict() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, -2.0, 0.5, 0.0, 0.3);
+        let cfg = HorizonConfig::new(2.0, 4.0, 80).unwrap();
+        assert_eq!(cfg.dt(), 0.05);
+
+        let positional = predict(&input(), start, &model, &mut Rk4, 2.0, 4.0, 80);
+        let configured = predict_cfg(&input(), start, &model, &mut Rk4, &cfg);
+        assert_eq!(configured, positional);
+
+        assert_eq!(
+            HorizonConfig::new(0.0, 0.0, 10),
+            Err(HorizonError::InvalidDuration { t_final: 0.0 })
+        );
+        assert_eq!(HorizonConfig::new(0.0, 1.0, 0), Err(HorizonError::NoSteps));
+        assert!(configured.accumulators.is_none());
+        assert!(matches!(
+            HorizonConfig::new(f64::NAN, 1.0, 10),
+            Err(HorizonError::NonFiniteStart { .. })
+        ));
+    }
+
+    #[test]
+    fn accumulated_arc_length_matches_post_hoc() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let cfg = HorizonConfig::new(0.0, 6.0, 120).unwrap();
+        let prediction = predict_cfg_with_accumulators(&input(), start, &model, &mut Rk4, &cfg);
+
+        let acc = prediction.accumulators.as_ref().unwrap();
+        assert_eq!(acc.arc_length_m.len(), prediction.states.len());
+        assert!(acc.arc_length_m.windows(2).all(|w| w[1] >= w[0]));
+        assert!((acc.arc_length_m[120] - prediction.arc_length()).abs() < 1e-9);
+
+        let power: f64 = prediction.control.channels().iter().map(|u| u * u).sum();
+        assert_eq!(acc.control_effort[0], 0.0);
+        assert!((acc.control_effort[120] - power * 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn simplify_keeps_the_corner_of_an_l_shaped_path() {
+        // 10 m North, then 10 m East, 1 m apart, with sub-centimetre wobble
+        let states = (0..=20)
+            .map(|k| {
+                let wobble = 0.004 * (k as f64 * 1.7).sin();
+                let (n, e) = if k <= 10 {
+                    (k as f64, wobble)
+                } else {
+                    (10.0 + wobble, (k - 10) as f64)
+                };
+                SimpleQuadState::new(n, e, 0.0, 0.0, 0.0)
+            })
+            .collect();
+        let prediction = Prediction {
+            states,
+            control: SimpleQuadcopter::new(0.1).input_to_control(&STRAIGHT),
+            controls: Vec::new(),
+            t0: 0.0,
+            t_final: 20.0,
+            cpu_time: Duration::ZERO,
+            accumulators: None,
+            sample_times: None,
+        };
+
+        let kept = prediction.simplify(0.1);
+        assert_eq!(kept.len(), 3, "{kept:?}");
+        assert_eq!(kept[0], (0.0, 0.0));
+        assert_eq!(kept[1], prediction.positions()[10]);
+        assert_eq!(kept[2], prediction.positions()[20]);
+        assert_eq!(prediction.simplify(0.0).len(), 21);
+    }
+
+    #[test]
+    fn output_stride_stores_sparse_states_with_full_step_accuracy() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let full = predict(&input(), start, &model, &mut Rk4, 0.0, 10.0, 1000);
+        let cfg = HorizonConfig::new(0.0, 10.0, 1000)
+            .unwrap()
+            .with_output_stride(30);
+        let sparse = predict_cfg_with_accumulators(&input(), start, &model, &mut Rk4, &cfg);
+
+        // 0, 30, ..., 990, then the final step 1000
+        assert_eq!(sparse.states.len(), 1000usize.div_ceil(30) + 1);
+        assert_eq!(sparse.final_state(), full.final_state());
+        assert_eq!(sparse.states[5], full.states[150]);
+        assert!((sparse.dt() - 0.3).abs() < 1e-12);
+        assert!((sparse.t_at(5) - full.t_at(150)).abs() < 1e-12);
+        assert_eq!(sparse.t_at(sparse.n()), 10.0);
+
+        let acc = sparse.accumulators.as_ref().unwrap();
+        assert_eq!(acc.arc_length_m.len(), sparse.states.len());
+        assert!((acc.arc_length_m[sparse.n()] - full.arc_length()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn strided_helpers_interpolate_on_the_sample_times() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let full = predict(&input(), start, &model, &mut Rk4, 0.0, 10.0, 1000);
+        let cfg = HorizonConfig::new(0.0, 10.0, 1000)
+            .unwrap()
+            .with_output_stride(30);
+        let sparse = predict_cfg(&input(), start, &model, &mut Rk4, &cfg);
+
+        // The last interval is 9.9 → 10.0, a third of the others
+        assert!((sparse.t_at(sparse.n() - 1) - 9.9).abs() < 1e-12);
+        assert_eq!(sparse.state_at(10.0), full.final_state().clone());
+        assert_eq!(sparse.state_at(3.0), full.states[300]);
+        let mid = sparse.state_at(9.95);
+        assert!((mid.north_m - full.states[995].north_m).abs() < 5e-3);
+
+        let window = sparse.slice(9.0, 10.0);
+        let expected = [9.0, 9.3, 9.6, 9.9, 10.0];
+        assert_eq!(window.states.len(), expected.len());
+        for (t, expected) in window.times().iter().zip(expected) {
+            assert!((t - expected).abs() < 1e-12);
+        }
+        assert_eq!(window.final_state(), full.final_state());
+
+        let (vn, ve) = sparse.path_velocity()[sparse.n() - 1];
+        let (full_vn, full_ve) = full.path_velocity()[990];
+        assert!((vn - full_vn).abs() < 5e-3 && (ve - full_ve).abs() < 5e-3);
+    }
+
+    #[test]
+    fn segment_labels_straight_turn_straight() {
+        let car = DubinsCar {
+            speed_mps: 5.0,
+            max_yaw_rate_rps: 1.0,
+        };
+        let straight = DroneInput {
+            yaw_rate_rps: 0.0,
+            ..STRAIGHT
+        };
+        let turn = DroneInput {
+            yaw_rate_rps: 0.5,
+            ..STRAIGHT
+        };
+        let prediction = predict_sequence(
+            &[(0.0, straight), (3.0, turn), (6.0, straight)],
+            DubinsState::new(0.0, 0.0, 0.0),
+            &car,
+            &mut Rk4,
+            0.0,
+            10.0,
+            200,
+        );
+
+        // Turning at 0.5 rad/s and 5 m/s is a curvature of 0.1 / m
+        let curvature = prediction.curvature_series();
+        assert!((curvature[90] - 0.1).abs() < 1e-3, "{}", curvature[90]);
+
+        let segments = prediction.segment(&SegmentThresholds::default());
+        let phases: Vec<Phase> = segments.iter().map(|s| s.phase).collect();
+        assert_eq!(phases, [Phase::Straight, Phase::Turning, Phase::Straight]);
+        assert_eq!((segments[0].t_start, segments[2].t_end), (0.0, 10.0));
+        assert!((segments[1].t_start - 3.0).abs() <= 0.1, "{segments:?}");
+        assert!((segments[1].t_end - 6.0).abs() <= 0.1, "{segments:?}");
+    }
+
+    #[test]
+    fn sample_fps_spaces_frames_at_the_frame_rate() {
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, -1.0, 0.3);
+        let prediction = predict(&STRAIGHT, start, &model, &mut Rk4, 0.5, 1.05, 7);
+
+        let frames = prediction.sample_fps(30.0);
+        assert_eq!(frames.len(), 32);
+        assert!(
+            frames
+                .windows(2)
+                .all(|w| (w[1].0 - w[0].0 - 1.0 / 30.0).abs() < 1e-12)
+        );
+        assert_eq!(frames[0], (0.5, start));
+        assert!(frames.last().unwrap().0 <= prediction.t_end());
+
+        // Constant velocity is reproduced exactly between the solver samples
+        for (t, state) in &frames {
+            let elapsed = t - 0.5;
+            let (n, e) = state.position();
+            assert!((n - 2.0 * elapsed).abs() < 1e-9 && (e + elapsed).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn constant_pitch_jerk_dies_out_after_the_transient() {
+        let model = SimpleQuadcopter::new(0.5);
+        let pitch = DroneInput {
+            pitch_rad: 0.2,
+            ..STRAIGHT
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 0.0, 0.0, 0.7);
+        let prediction = predict(&pitch, start, &model, &mut Rk4, 0.0, 12.0, 1200);
+        let forward = model.input_to_control(&pitch).ax_body_mps2;
+
+        // Yawed 0.7 rad, yet the acceleration is purely forward in the body frame
+        let accel = prediction.body_accel_series();
+        assert!(
+            (accel[1].0 - forward).abs() < 0.02 * forward,
+            "{:?}",
+            accel[1]
+        );
+        assert!(accel.iter().all(|(_, right)| right.abs() < 1e-9));
+
+        // Drag decays the acceleration exponentially, so jerk starts at -drag * accel
+        // (away from the one-sided first samples) and dies out with it
+        let jerk = prediction.jerk_series();
+        assert_eq!(jerk.len(), prediction.states.len());
+        assert!((jerk[5].0 + 0.5 * forward).abs() < 0.02 * forward);
+        assert!(
+            jerk[1000..]
+                .iter()
+                .all(|(f, r)| f.abs() < 1e-2 && r.abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn straight_outbound_path_keeps_its_bearing() {
+        // Heading South-East away from the origin along the 135° radial
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(-1.0, 1.0, -2.0, 2.0, 0.0);
+        let prediction = predict(&STRAIGHT, start, &model, &mut Rk4, 0.0, 5.0, 50);
+
+        let series = prediction.range_bearing_series((0.0, 0.0));
+        assert!(series.windows(2).all(|w| w[1].0 > w[0].0));
+        assert!((series[0].0 - 2f64.sqrt()).abs() < 1e-12);
+        let radial = 135f64.to_radians();
+        assert!(series.iter().all(|(_, b)| (b - radial).abs() < 1e-12));
+    }
+
+    #[test]
+    fn slice_interpolates_the_window_boundaries() {
+        let model = SimpleQuadcopter::new(0.3);
+        let turn = DroneInput {
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.4,
+            ..STRAIGHT
+        };
+        let schedule = [(0.0, STRAIGHT), (2.0, turn)];
+        let prediction = predict_sequence(
+            &schedule,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            5.0,
+            50,
+        );
+
+        let window = prediction.slice(1.23, 3.57);
+        assert_eq!(window.states[0], prediction.state_at(1.23));
+        assert_eq!(*window.final_state(), prediction.state_at(3.57));
+        assert!((window.t_final - 2.34).abs() < 1e-12 && window.t0 == 1.23);
+        assert!(window.dt() <= prediction.dt());
+        assert_eq!(window.controls.len(), 2);
+        assert_eq!(window.controls[1].0, 2.0);
+
+        // The solver's samples inside the window are kept, not re-interpolated
+        assert_eq!(window.states.len(), 25);
+        assert_eq!(window.states[1], prediction.states[3]);
+        assert_eq!(window.t_at(1), prediction.t_at(3));
+        assert_eq!(window.states[23], prediction.states[25]);
+
+        // Out-of-range windows clamp to the horizon
+        let all = prediction.slice(-10.0, 10.0);
+        assert_eq!((all.t0, all.t_end()), (1.0, 6.0));
+        assert_eq!(all.n(), 50);
+        assert_eq!(&all.states[1..50], &prediction.states[1..50]);
+        assert!(
+            (all.final_state().to_dvector() - prediction.final_state().to_dvector()).amax() < 1e-12
+        );
+        let after = prediction.slice(8.0, 9.0);
+        assert_eq!((after.t0, after.t_final, after.n()), (6.0, 0.0, 0));
+        assert!(
+            (after.states[0].to_dvector() - prediction.final_state().to_dvector()).amax() < 1e-12
+        );
+    }
+
+    #[test]
+    fn cross_track_is_constant_along_a_parallel_path() {
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(0.0, 5.0, 2.0, 0.0, 0.0);
+        let prediction = predict(&STRAIGHT, start, &model, &mut Rk4, 0.0, 3.0, 30);
+
+        let series = prediction.cross_track_series((-1.0, 0.0), (10.0, 0.0));
+        assert_eq!(series.len(), prediction.states.len());
+        assert!(series.iter().all(|(_, cross)| (cross - 5.0).abs() < 1e-9));
+        assert!(series.windows(2).all(|w| w[1].0 > w[0].0));
+        assert!((series[0].0 - 1.0).abs() < 1e-12);
+        assert!((series.last().unwrap().0 - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn observer_sees_every_step_in_time_order() {
+        let model = SimpleQuadcopter::new(0.1);
+        let steps = 200;
+
+        let mut calls = Vec::new();
+        let prediction = predict_with_observer(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            2.0,
+            steps,
+            |i, t, s: &SimpleQuadState| calls.push((i, t, *s)),
+        );
+
+        assert_eq!(calls.len(), steps);
+        assert!(calls.windows(2).all(|w| w[1].1 > w[0].1));
+        for (i, t, s) in &calls {
+            assert!((t - prediction.t_at(*i)).abs() < 1e-12);
+            assert_eq!(*s, prediction.states[*i]);
+        }
+    }
+
+    #[test]
+    fn continue_from_matches_single_long_run() {
+        let model = SimpleQuadcopter::new(0.1);
+
+        let first = predict(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            1.0,
+            100,
+        );
+        let second = first.continue_from(&input(), &model, &mut Rk4, 1.0, 100);
+        let full = predict(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            2.0,
+            200,
+        );
+
+        assert_eq!(second.t0, 1.0);
+        assert_eq!(second.t_final, 1.0);
+        assert_eq!(second.t_end(), full.t_end());
+        assert_eq!(second.dt(), full.dt());
+        assert_eq!(second.states[0], full.states[100]);
+        assert_eq!(second.final_state(), full.final_state());
+    }
+
+    #[test]
+    fn append_joins_turn_then_straight() {
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 5.0, 0.0, 0.0);
+        let turn = DroneInput {
+            yaw_rate_rps: 0.5,
+            ..STRAIGHT
+        };
+
+        let mut path = predict(&turn, start, &model, &mut Rk4, 0.0, 2.0, 200);
+        let straight = path.continue_from(&STRAIGHT, &model, &mut Rk4, 3.0, 300);
+        let (turn_len, straight_len) = (path.arc_length(), straight.arc_length());
+
+        path.append(&straight).unwrap();
+
+        assert_eq!(path.states.len(), 501);
+        assert_eq!(path.t_end(), 5.0);
+        assert_eq!(path.states[200], straight.states[0]);
+        assert_eq!(path.final_state(), straight.final_state());
+        assert!((path.arc_length() - (turn_len + straight_len)).abs() < 1e-9);
+        // No accelerations and no drag: speed is constant, so length = speed * time
+        assert!((path.arc_length() - 25.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn append_continues_the_accumulators() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let first_cfg = HorizonConfig::new(0.0, 2.0, 40).unwrap();
+        let mut path = predict_cfg_with_accumulators(&input(), start, &model, &mut Rk4, &first_cfg);
+        let second_cfg = HorizonConfig::new(2.0, 3.0, 60).unwrap();
+        let next = *path.final_state();
+        let second = predict_cfg_with_accumulators(&input(), next, &model, &mut Rk4, &second_cfg);
+        let (first_arc, first_effort) = {
+            let acc = path.accumulators.as_ref().unwrap();
+            (acc.arc_length_m[40], acc.control_effort[40])
+        };
+
+        let mut bare = path.clone();
+        bare.accumulators = None;
+        path.append(&second).unwrap();
+        bare.append(&second).unwrap();
+
+        let acc = path.accumulators.as_ref().unwrap();
+        assert_eq!(acc.arc_length_m.len(), path.states.len());
+        assert_eq!(acc.control_effort.len(), path.states.len());
+        assert!((acc.arc_length_m[100] - path.arc_length()).abs() < 1e-9);
+        let tail = second.accumulators.as_ref().unwrap();
+        assert_eq!(
+            acc.control_effort[100],
+            first_effort + tail.control_effort[60]
+        );
+        assert_eq!(acc.arc_length_m[40], first_arc);
+        // Totals that only one side carries cannot continue
+        assert!(bare.accumulators.is_none());
+    }
+
+    #[test]
+    fn append_rejects_mismatched_junction() {
+        let model = SimpleQuadcopter::new(0.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 5.0, 0.0, 0.0);
+        let mut first = predict(&STRAIGHT, start, &model, &mut Rk4, 0.0, 1.0, 100);
+
+        let late = predict(&STRAIGHT, start, &model, &mut Rk4, 2.0, 1.0, 100);
+        assert!(matches!(
+            first.append(&late),
+            Err(AppendError::TimeGap { .. })
+        ));
+
+        let coarse = first.continue_from(&STRAIGHT, &model, &mut Rk4, 1.0, 10);
+        assert!(matches!(
+            first.append(&coarse),
+            Err(AppendError::StepMismatch { .. })
+        ));
+
+        let jumped = predict(&STRAIGHT, start, &model, &mut Rk4, 1.0, 1.0, 100);
+        assert!(matches!(
+            first.append(&jumped),
+            Err(AppendError::StateMismatch { .. })
+        ));
+        assert_eq!(first.states.len(), 101);
+    }
+
+    #[test]
+    fn rate_limited_sequence_slews_into_a_step() {
+        let model = SimpleQuadcopter::new(0.1);
+        let bank = DroneInput {
+            roll_rad: 0.3,
+            ..STRAIGHT
+        };
+        let schedule = [(0.0, STRAIGHT), (1.0, bank)];
+        let rates = SlewRates {
+            tilt_rps: 0.5,
+            yaw_rate_rps2: 1.0,
+            thrust_per_s: 1.0,
+        };
+        let mut limiter = RateLimiter::new(STRAIGHT, rates);
+
+        let limited = predict_sequence_rate_limited(
+            &schedule,
+            &mut limiter,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            3.0,
+            30,
+        );
+        let raw = predict_sequence(
+            &schedule,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            3.0,
+            30,
+        );
+
+        // 0.3 rad at 0.5 rad/s takes six 0.1 s steps instead of one switch
+        assert_eq!(raw.controls.len(), 2);
+        assert_eq!(limited.controls.len(), 7);
+        let switch_times: Vec<f64> = limited.controls[1..].iter().map(|(t, _)| *t).collect();
+        for (k, t) in switch_times.iter().enumerate() {
+            assert!(
+                (t - (1.0 + 0.1 * k as f64)).abs() < 1e-9,
+                "{switch_times:?}"
+            );
+        }
+        assert_eq!(limited.controls.last().unwrap().1, raw.controls[1].1);
+        assert_eq!(limiter.previous(), bank);
+
+        let (_, e_limited) = limited.final_state().position();
+        let (_, e_raw) = raw.final_state().position();
+        assert!(e_limited > 0.0 && e_limited < e_raw);
+    }
+
+    #[test]
+    fn predict_sequence_switches_inputs_on_the_grid() {
+        let model = SimpleQuadcopter::new(0.1);
+        let turn = DroneInput {
+            yaw_rate_rps: 0.5,
+            ..STRAIGHT
+        };
+        let schedule = [(0.0, turn), (2.0, STRAIGHT)];
+
+        let seq = predict_sequence(
+            &schedule,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            5.0,
+            500,
+        );
+
+        let mut joined = predict(
+            &turn,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            2.0,
+            200,
+        );
+        joined
+            .append(&joined.continue_from(&STRAIGHT, &model, &mut Rk4, 3.0, 300))
+            .unwrap();
+
+        assert_eq!(seq.controls.len(), 2);
+        assert_eq!(seq.controls[1].0, 2.0);
+        assert_eq!(seq.control_at(1.99).yaw_rate_rps, 0.5);
+        assert_eq!(seq.control_at(2.0).yaw_rate_rps, 0.0);
+        assert_eq!(seq.controls, joined.controls);
+        assert_eq!(seq.final_state(), joined.final_state());
+    }
+
+    #[test]
+    fn schedule_saturation_collects_clamped_channels_over_the_schedule() {
+        let model = SimpleQuadcopter::new(0.1).with_max_tilt(60f64.to_radians());
+        let steep = DroneInput {
+            pitch_rad: 70f64.to_radians(),
+            ..STRAIGHT
+        };
+        let full_throttle = DroneInput {
+            thrust_norm: 1.2,
+            ..STRAIGHT
+        };
+
+        assert!(!schedule_saturation(&model, &[(0.0, STRAIGHT)]).any());
+        let saturation = schedule_saturation(
+            &model,
+            &[(0.0, STRAIGHT), (1.0, steep), (2.0, full_throttle)],
+        );
+        assert_eq!(
+            saturation,
+            Saturation {
+                pitch: true,
+                thrust: true,
+                ..Saturation::default()
+            }
+        );
+    }
+
+    #[test]
+    fn ramp_raises_acceleration_gradually() {
+        let model = SimpleQuadcopter::new(0.0);
+        let to = DroneInput {
+            pitch_rad: 10f64.to_radians(),
+            ..STRAIGHT
+        };
+        let (ramp_time, dt) = (1.0, 0.01);
+
+        let prediction = predict_ramp(
+            &STRAIGHT,
+            &to,
+            ramp_time,
+            RampShape::Linear,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            3.0,
+            300,
+        );
+
+        let accel: Vec<f64> = prediction
+            .states
+            .windows(2)
+            .map(|w| (w[1].v_north_mps - w[0].v_north_mps) / dt)
+            .collect();
+        let full = model.input_to_control(&to).ax_body_mps2;
+
+        assert_eq!(accel[0], 0.0);
+        assert!(accel[..100].windows(2).all(|w| w[1] > w[0]));
+        assert!((accel[50] - 0.5 * full).abs() < 0.01 * full);
+        assert!(accel[100..].iter().all(|a| (a - full).abs() < 1e-9));
+        assert_eq!(prediction.control_at(ramp_time).ax_body_mps2, full);
+    }
+
+    #[test]
+    fn smoothstep_ramp_starts_and_ends_flat() {
+        let shape = RampShape::SmoothStep;
+        assert_eq!(shape.apply(0.0), 0.0);
+        assert_eq!(shape.apply(0.5), 0.5);
+        assert_eq!(shape.apply(1.0), 1.0);
+        assert!(shape.apply(0.01) < RampShape::Linear.apply(0.01));
+    }
+
+    #[test]
+    fn sideways_drift_separates_track_from_yaw() {
+        let model = SimpleQuadcopter::new(0.0);
+        let forward = DroneInput {
+            pitch_rad: 10f64.to_radians(),
+            ..STRAIGHT
+        };
+        // Facing North while drifting East at 3 m/s
+        let start = SimpleQuadState::new(0.0, 0.0, 0.0, 3.0, 0.0);
+
+        let prediction = predict(&forward, start, &model, &mut Rk4, 0.0, 4.0, 400);
+        let track = prediction.track_series();
+        let speed = prediction.speed_series();
+
+        assert!((track[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((speed[0] - 3.0).abs() < 1e-12);
+        // Accelerating North turns the track toward the (constant) yaw of 0
+        assert!(track.windows(2).all(|w| w[1] < w[0]));
+        let end = prediction.final_state();
+        assert!(end.yaw_rad.abs() < 1e-12);
+        assert!(track[400] > 0.1);
+        assert!((track[400] - end.v_east_mps.atan2(end.v_north_mps)).abs() < 1e-12);
+        assert!((speed[400] - end.v_north_mps.hypot(end.v_east_mps)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn first_order_velocity_response_metrics() {
+        let drag = 0.5;
+        let model = SimpleQuadcopter::new(drag);
+        let forward = DroneInput {
+            pitch_rad: 10f64.to_radians(),
+            ..STRAIGHT
+        };
+        let prediction = predict(
+            &forward,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            30.0,
+            3000,
+        );
+
+        // v_north is component 2; a 2% band settles after ln(50)/drag ≈ 4/drag
+        let settling = prediction.settling_time(2, 0.02);
+        assert!((settling - 50f64.ln() / drag).abs() < 0.05);
+        assert!((settling - 4.0 / drag).abs() / (4.0 / drag) < 0.05);
+        assert_eq!(prediction.overshoot(2), 0.0);
+        assert_eq!(prediction.settling_time(4, 0.02), 0.0);
+    }
+
+    #[test]
+    fn trim_to_steady_drops_first_order_transient() {
+        let drag = 0.5;
+        let model = SimpleQuadcopter::new(drag);
+        let forward = DroneInput {
+            pitch_rad: 10f64.to_radians(),
+            ..STRAIGHT
+        };
+        let prediction = predict(
+            &forward,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            30.0,
+            3000,
+        );
+
+        // Monotone response: first entry into the 2% band ≈ 4/drag after the start
+        let trimmed = prediction.trim_to_steady(2, 0.02);
+        assert!(((trimmed.t0 - 1.0) - 4.0 / drag).abs() / (4.0 / drag) < 0.05);
+        assert!((trimmed.t_end() - prediction.t_end()).abs() < 1e-9);
+        assert!((trimmed.dt() - prediction.dt()).abs() < 1e-12);
+        assert_eq!(trimmed.final_state(), prediction.final_state());
+        assert_eq!(trimmed.controls.len(), 1);
+        assert_eq!(trimmed.controls[0].0, trimmed.t0);
+
+        // Yaw never changes: already steady at the first sample
+        assert_eq!(prediction.trim_to_steady(4, 0.02).states.len(), 3001);
+    }
+
+    #[test]
+    fn endpoints_match_full_predictions() {
+        let model = SimpleQuadcopter::new(0.2);
+        let start = SimpleQuadState::new(1.0, 2.0, 3.0, -1.0, 0.4);
+        let inputs: Vec<DroneInput> = (0..12)
+            .map(|k| DroneInput {
+                roll_rad: (k as f64 - 6.0).to_radians(),
+                pitch_rad: (2.0 * k as f64).to_radians(),
+                yaw_rate_rps: 0.05 * k as f64,
+                thrust_norm: 0.5,
+            })
+            .collect();
+
+        let endpoints = predict_endpoints(&inputs, &start, &model, &mut Rk4, 3.0, 300);
+
+        assert_eq!(endpoints.len(), inputs.len());
+        for (input, end) in inputs.iter().zip(&endpoints) {
+            let full = predict(input, start, &model, &mut Rk4, 0.0, 3.0, 300);
+            assert_eq!(end, full.final_state());
+        }
+    }
+
+    #[test]
+    fn budget_truncates_or_completes() {
+        let model = SimpleQuadcopter::new(0.1);
+        let steps = 200_000;
+
+        let short = predict_budgeted(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            20.0,
+            steps,
+            Duration::from_nanos(1),
+        );
+        let p = &short.prediction;
+        assert!(short.truncated);
+        assert!(p.states.len() < steps + 1);
+        assert!((p.dt() - 20.0 / steps as f64).abs() < 1e-15);
+        assert!((p.t_final - (p.n() as f64) * p.dt()).abs() < 1e-12);
+
+        let full = predict(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            20.0,
+            2000,
+        );
+        let done = predict_budgeted(
+            &input(),
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            1.0,
+            20.0,
+            2000,
+            Duration::from_secs(60),
+        );
+        assert!(!done.truncated);
+        assert_eq!(done.prediction.t_final, 20.0);
+        assert_eq!(done.prediction.states, full.states);
+    }
+
+    #[test]
+    fn first_intersection_finds_square_entry() {
+        let model = SimpleQuadcopter::new(0.0);
+        // Coast north at 2 m/s from (0, 1) for 10 s, over a 1 s grid
+        let prediction = predict(
+            &STRAIGHT,
+            SimpleQuadState::new(0.0, 1.0, 2.0, 0.0, 0.0),
+            &model,
+            &mut Rk4,
+            5.0,
+            10.0,
+            10,
+        );
+
+        let square = [(5.0, 0.0), (5.0, 3.0), (8.0, 3.0), (8.0, 0.0)];
+        let (i, t, (n, e)) = prediction.first_intersection(&square).unwrap();
+        assert_eq!(i, 2);
+        assert!((t - 7.5).abs() < 1e-12);
+        assert!((n - 5.0).abs() < 1e-12 && (e - 1.0).abs() < 1e-12);
+
+        let elsewhere = [(5.0, 4.0), (5.0, 6.0), (8.0, 6.0), (8.0, 4.0)];
+        assert_eq!(prediction.first_intersection(&elsewhere), None);
+
+        let around_start = [(-1.0, 0.0), (-1.0, 2.0), (1.0, 2.0), (1.0, 0.0)];
+        assert_eq!(
+            prediction.first_intersection(&around_start),
+            Some((0, 5.0, (0.0, 1.0)))
+        );
+    }
+
+    #[test]
+    fn first_circle_intersection_finds_entry() {
+        let model = SimpleQuadcopter::new(0.0);
+        let prediction = predict(
+            &STRAIGHT,
+            SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0),
+            &model,
+            &mut Rk4,
+            0.0,
+            10.0,
+            10,
+        );
+
+        let (i, t, (n, e)) = prediction
+            .first_circle_intersection((10.0, 0.0), 3.0)
+            .unwrap();
+        assert_eq!(i, 3);
+        assert!((t - 3.5).abs() < 1e-12);
+        assert!((n - 7.0).abs() < 1e-12 && e.abs() < 1e-12);
+        assert_eq!(prediction.first_circle_intersection((10.0, 5.0), 3.0), None);
+    }
+
+    #[test]
+    fn to_matrix_has_one_row_per_state() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, 2.0, 3.0, 4.0, 0.5);
+
+        let m = predict_to_matrix(&input(), start, &model, &mut Rk4, 0.0, 2.0, 40);
+        assert_eq!(m.shape(), (41, 7));
+        assert_eq!(m.row(0).transpose(), start.to_dvector());
+
+        let prediction = predict(&input(), start, &model, &mut Rk4, 0.0, 2.0, 40);
+        assert_eq!(m.row(40).transpose(), prediction.final_state().to_dvector());
+
+        let empty = Prediction {
+            states: Vec::<SimpleQuadState>::new(),
+            ..prediction
+        };
+        assert_eq!(empty.to_matrix().shape(), (0, 0));
+    }
+
+    #[test]
+    fn unstable_run_reports_step_and_field() {
+        use crate::solvers::ForwardEuler;
+
+        // drag * dt = 10: Forward Euler multiplies the velocity by -9 every step
+        let model = SimpleQuadcopter::new(1000.0);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let dt = 0.01;
+
+        let mut v = 1.0f64;
+        let mut blowup_step = 0;
+        while v.is_finite() {
+            v += dt * (-model.drag * v);
+            blowup_step += 1;
+        }
+
+        let err = std::panic::catch_unwind(|| {
+            predict(&STRAIGHT, start, &model, &mut ForwardEuler, 0.0, 10.0, 1000)
+        })
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+
+        assert!(msg.contains(&format!("step {blowup_step} ")), "{msg}");
+        assert!(msg.contains("`v_north_mps`"), "{msg}");
+        assert!(msg.contains("previous state: SimpleQuadState"), "{msg}");
+        assert!(msg.contains("control: SimpleQuadControl"), "{msg}");
+    }
+
+    #[test]
+    fn closest_approach_on_straight_pass() {
+        let model = SimpleQuadcopter::new(0.0);
+        // Coast north at 3 m/s from (-30, 0); the point (6, 4) is passed at t0 + 12 s
+        let prediction = predict(
+            &STRAIGHT,
+            SimpleQuadState::new(-30.0, 0.0, 3.0, 0.0, 0.0),
+            &model,
+            &mut Rk4,
+            1.0,
+            20.0,
+            7,
+        );
+
+        let (i, t, d) = prediction.closest_approach((6.0, 4.0));
+        assert!((d - 4.0).abs() < 1e-9);
+        assert!((t - 13.0).abs() < 1e-9);
+        assert!(prediction.t_at(i) <= t && t <= prediction.t_at(i + 1));
+
+        // Target behind the start: closest at the first sample
+        let (i, t, d) = prediction.closest_approach((-33.0, 4.0));
+        assert_eq!((i, t), (0, 1.0));
+        assert!((d - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn backward_integration_returns_to_start() {
+        let model = SimpleQuadcopter::new(0.2);
+        let start = SimpleQuadState::new(3.0, -1.0, 2.0, 0.5, 0.1);
+
+        let forward = predict(&input(), start, &model, &mut Rk4, 1.0, 5.0, 500);
+        let back = predict_backward(
+            &input(),
+            *forward.final_state(),
+            &model,
+            &mut Rk4,
+            forward.t_end(),
+            5.0,
+            500,
+        );
+
+        assert!((back.t_end() - 1.0).abs() < 1e-12);
+        assert!(back.dt() < 0.0);
+        let err = (back.final_state().to_dvector() - start.to_dvector()).amax();
+        assert!(err < 1e-9, "round-trip error {err}");
+
+        // Intermediate samples retrace the forward path in reverse
+        let mid = (back.states[250].to_dvector() - forward.states[250].to_dvector()).amax();
+        assert!(mid < 1e-9);
+    }
+
+    #[test]
+    fn positions_match_state_fields() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, 2.0, 0.5, -0.5, 0.3);
+        let pred = predict(&input(), start, &model, &mut Rk4, 0.0, 2.0, 20);
+
+        let positions = pred.positions();
+        assert_eq!(positions.len(), pred.states.len());
+        assert_eq!(pred.positions_iter().len(), pred.states.len());
+        for ((p, q), s) in positions
+            .iter()
+            .zip(pred.positions_iter())
+            .zip(&pred.states)
+        {
+            assert_eq!(*p, (s.north_m, s.east_m));
+            assert_eq!(q, (s.north_m, s.east_m));
+        }
+    }
+
+    #[test]
+    fn grid_prediction_matches_uniform_predict() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, 2.0, 0.5, -0.5, 0.3);
+        let uniform = predict(&input(), start, &model, &mut Rk4, 2.0, 3.0, 30);
+
+        let times: Vec<f64> = (0..=30).map(|i| uniform.t_at(i)).collect();
+        let grid = predict_on_grid(&input(), start, &model, &mut Rk4, &times);
+
+        assert_eq!(grid.t0, 2.0);
+        assert!((grid.t_end() - 5.0).abs() < 1e-12);
+        assert_eq!(grid.states.len(), uniform.states.len());
+        for (g, u) in grid.states.iter().zip(&uniform.states) {
+            assert!((g.to_dvector() - u.to_dvector()).amax() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn grid_prediction_steps_exactly_on_non_uniform_instants() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let times = [0.0, 0.1, 0.35, 0.4, 1.0];
+        let grid = predict_on_grid(&input(), start, &model, &mut Rk4, &times);
+        assert_eq!(grid.states.len(), times.len());
+
+        // Piecewise predict between consecutive instants lands on the same states
+        let mut state = start;
+        for (w, g) in times.windows(2).zip(&grid.states[1..]) {
+            let seg = predict(&input(), state, &model, &mut Rk4, w[0], w[1] - w[0], 1);
+            state = *seg.final_state();
+            assert_eq!(state.to_dvector(), g.to_dvector());
+        }
+
+        // Sample times and interpolation follow the grid rather than t_final / n
+        assert_eq!(grid.times(), times);
+        assert_eq!(grid.t_at(2), 0.35);
+        assert_eq!(grid.state_at(0.4), grid.states[3]);
+        let (_, t, d) = grid.closest_approach(grid.states[2].position());
+        assert_eq!((t, d), (0.35, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-finite state at step 2 (t = 2)")]
+    fn endpoint_sweep_reports_a_blow_up_with_its_step() {
+        // Explicit Euler far past its stability limit overflows on the second step
+        let model = SimpleQuadcopter::new(1e200);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        predict_endpoints(&[STRAIGHT], &start, &model, &mut ForwardEuler, 4.0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly increasing")]
+    fn grid_prediction_rejects_non_increasing_grid() {
+        let model = SimpleQuadcopter::new(0.1);
+        let times = [0.0, 0.5, 0.5, 1.0];
+        predict_on_grid(&input(), SimpleQuadState::zero(), &model, &mut Rk4, &times);
+    }
+
+    #[test]
+    fn sensitivity_matches_central_differences_at_endpoint() {
+        let model = SimpleQuadcopter::new(0.2);
+        let start = SimpleQuadState::new(1.0, -1.0, 2.0, 0.5, 0.3);
+        let (t_final, steps) = (4.0, 400);
+
+        let result = predict_with_sensitivity(&input(), start, &model, 0.0, t_final, steps);
+        let rk4 = predict(&input(), start, &model, &mut Rk4, 0.0, t_final, steps);
+        assert_eq!(result.prediction.states, rk4.states);
+        assert_eq!(result.sensitivities.len(), steps + 1);
+        assert_eq!(result.sensitivities[0], DMatrix::zeros(7, 3));
+
+        let h = 1e-6;
+        let endpoint = |input: &DroneInput| {
+            predict(input, start, &model, &mut Rk4, 0.0, t_final, steps)
+                .final_state()
+                .to_dvector()
+        };
+        let analytic = &result.sensitivities[steps];
+        for k in 0..3 {
+            let perturbed = |h: f64| {
+                let mut p = input();
+                match k {
+                    0 => p.roll_rad += h,
+                    1 => p.pitch_rad += h,
+                    _ => p.yaw_rate_rps += h,
+                }
+                p
+            };
+            let (hi, lo) = (perturbed(h), perturbed(-h));
+            let numeric = (endpoint(&hi) - endpoint(&lo)) / (2.0 * h);
+            let err = (numeric - analytic.column(k)).amax();
+            assert!(err < 1e-5 * analytic.amax(), "channel {k}: {err}");
+        }
+    }
+
+    #[test]
+    fn yaw_fan_has_one_prediction_per_rate() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let rates = [-0.4, -0.2, 0.0, 0.2, 0.4];
+        let fan = predict_yaw_fan(&input(), &rates, &start, &model, &mut Rk4, 0.0, 5.0, 50);
+        assert_eq!(fan.len(), rates.len());
+
+        let straight = DroneInput {
+            yaw_rate_rps: 0.0,
+            ..input()
+        };
+        let plain = predict(&straight, start, &model, &mut Rk4, 0.0, 5.0, 50);
+        assert_eq!(fan[2].states, plain.states);
+        for (p, rate) in fan.iter().zip(rates) {
+            assert!((p.final_state().yaw_rad - 5.0 * rate).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn newton_works_harder_at_larger_steps() {
+        use crate::dynamic_models::{Composed, GroundEffect};
+        use crate::solvers::{BackwardEuler, NewtonOpts};
+
+        // Ground effect makes the vertical channel nonlinear in altitude
+        let model = Composed::new(SimpleQuadcopter::new(0.1), GroundEffect::new(0.0, 0.5, 0.3));
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 1.0, 0.0).with_vertical(-0.2, 1.0);
+        let mean_iters = |steps: usize| {
+            let mut be = BackwardEuler::new(NewtonOpts {
+                iter_max: 15,
+                min_error: 1e-13,
+                ..NewtonOpts::default()
+            });
+            let (_, stats) = predict_with_stats(&input(), start, &model, &mut be, 0.0, 10.0, steps);
+            assert_eq!(stats.len(), steps);
+            assert!(stats.iter().all(|s| s.converged));
+            stats.iter().map(|s| s.newton_iters).sum::<usize>() as f64 / steps as f64
+        };
+        let (fine, coarse) = (mean_iters(1000), mean_iters(10));
+        assert!(coarse > fine, "coarse {coarse} vs fine {fine}");
+
+        // Explicit solvers report trivial stats
+        let (_, stats) = predict_with_stats(&input(), start, &model, &mut Rk4, 0.0, 1.0, 10);
+        assert!(stats.iter().all(|s| *s == StepStats::default()));
+    }
+
+    #[test]
+    fn energy_is_dissipated_unless_the_step_is_unstable() {
+        use crate::solvers::ForwardEuler;
+
+        let drag = 0.1;
+        let model = SimpleQuadcopter::new(drag);
+        let start = SimpleQuadState::new(0.0, 0.0, 5.0, -3.0, 0.4);
+
+        let rk4 = predict(&STRAIGHT, start, &model, &mut Rk4, 0.0, 50.0, 500);
+        let energy = rk4.energy_series();
+        assert_eq!(energy[0], 0.5 * (25.0 + 9.0));
+        assert!(is_dissipative(&energy, 0.0));
+
+        // FE amplifies velocity by |1 - drag dt| = 1.5 per step beyond dt = 2 / drag
+        let fe = predict(&STRAIGHT, start, &model, &mut ForwardEuler, 0.0, 250.0, 10);
+        assert!(!is_dissipative(&fe.energy_series(), 1e-9));
+    }
+
+    #[test]
+    fn display_is_a_one_line_summary() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, 2.0, 3.0, 0.0, 0.0);
+        let pred = predict(&STRAIGHT, start, &model, &mut Rk4, 0.0, 2.0, 40);
+
+        let summary = pred.to_string();
+        let end = pred.final_state();
+        assert!(!summary.contains('\n'));
+        assert!(summary.starts_with("40 steps, dt = 0.0500 s, t = 0.000..2.000 s"));
+        assert!(summary.contains("XY (1.000, 2.000) -> "));
+        assert!(summary.contains(&format!("({:.3}, {:.3}) m", end.north_m, end.east_m)));
+        assert!(summary.contains(&format!("final speed {:.3} m/s", end.ground_speed())));
+    }
+
+    #[test]
+    fn equality_ignores_cpu_time() {
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(1.0, 2.0, 3.0, 0.0, 0.2);
+        let a = predict(&input(), start, &model, &mut Rk4, 0.0, 2.0, 200);
+        let mut b = predict(&input(), start, &model, &mut Rk4, 0.0, 2.0, 200);
+        b.cpu_time += Duration::from_secs(1);
+        assert_eq!(a, b);
+
+        let b = b.without_timing();
+        assert_eq!(b.cpu_time(), Duration::ZERO);
+        assert_eq!(a, b);
+
+        let longer = predict(&input(), start, &model, &mut Rk4, 0.0, 2.0, 201);
+        assert_ne!(a, longer);
+
+        // Same states, different grid or accumulators
+        let mut regridded = a.clone();
+        regridded.sample_times = Some(a.times());
+        assert_ne!(a, regridded);
+        let cfg = HorizonConfig::new(0.0, 2.0, 200).unwrap();
+        let accumulated = predict_cfg_with_accumulators(&input(), start, &model, &mut Rk4, &cfg);
+        assert_eq!(accumulated.states, a.states);
+        assert_ne!(a, accumulated);
     }
 }
@@ -0,0 +1,124 @@
+/// Source of randomness consumed by every stochastic feature of the crate. Only
+/// `next_u64` is required; implement it to plug in a platform entropy source, another
+/// RNG crate or a deterministic test sequence. Override the samplers to control the
+/// drawn values directly.
+pub trait Rng {
+    /// Next 64 uniformly distributed random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform sample in the open interval `(0, 1)`.
+    fn uniform(&mut self) -> f64 {
+        // 53 random mantissa bits, shifted off zero by half a step
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller, discarding the second value).
+    fn standard_normal(&mut self) -> f64 {
+        let r = (-2.0 * self.uniform().ln()).sqrt();
+        r * (std::f64::consts::TAU * self.uniform()).cos()
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+
+    fn uniform(&mut self) -> f64 {
+        (**self).uniform()
+    }
+
+    fn standard_normal(&mut self) -> f64 {
+        (**self).standard_normal()
+    }
+}
+
+/// Small, fast, seedable pseudo-random generator (SplitMix64). Not cryptographically
+/// secure; intended for reproducible simulation noise.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+    spare_normal: Option<f64>,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+            spare_normal: None,
+        }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Standard normal sample (Box-Muller, caching the second value).
+    fn standard_normal(&mut self) -> f64 {
+        if let Some(z) = self.spare_normal.take() {
+            return z;
+        }
+        let r = (-2.0 * self.uniform().ln()).sqrt();
+        let theta = std::f64::consts::TAU * self.uniform();
+        self.spare_normal = Some(r * theta.sin());
+        r * theta.cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(SplitMix64::new(1).next_u64(), SplitMix64::new(2).next_u64());
+    }
+
+    #[test]
+    fn standard_normal_moments() {
+        let mut rng = SplitMix64::new(7);
+        let n = 200_000;
+        let samples: Vec<f64> = (0..n).map(|_| rng.standard_normal()).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!(mean.abs() < 0.01);
+        assert!((var - 1.0).abs() < 0.02);
+    }
+
+    /// Replays a fixed bit sequence, cycling when exhausted.
+    struct Fixed(Vec<u64>, usize);
+
+    impl Rng for Fixed {
+        fn next_u64(&mut self) -> u64 {
+            let v = self.0[self.1 % self.0.len()];
+            self.1 += 1;
+            v
+        }
+    }
+
+    #[test]
+    fn default_samplers_are_deterministic_in_the_bits() {
+        let mut rng = Fixed(vec![0, u64::MAX, 1 << 63], 0);
+        let half_step = 0.5 / (1u64 << 53) as f64;
+        assert_eq!(rng.uniform(), half_step);
+        assert_eq!(rng.uniform(), 1.0 - half_step);
+        assert_eq!(rng.uniform(), 0.5 + half_step);
+
+        // u1 = u2 = 0.5 + half_step -> r = sqrt(2 ln 2), theta just past pi
+        let mut rng = Fixed(vec![1 << 63], 0);
+        let z = rng.standard_normal();
+        assert!((z + (2.0 * 2f64.ln()).sqrt()).abs() < 1e-12);
+        assert_eq!(z, Fixed(vec![1 << 63], 0).standard_normal());
+    }
+}
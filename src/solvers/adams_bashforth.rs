@@ -0,0 +1,142 @@
+use crate::{
+    traits::{Dynamics, Stepper},
+    types::IntegrableState,
+};
+
+use super::Rk4;
+
+/// Two-step Adams–Bashforth: `x_{n+1} = x_n + dt * (3/2 f_n - 1/2 f_{n-1})`.
+///
+/// One derivative evaluation per step once started. The previous derivative is cached
+/// internally and reused only when the call continues the last step (same `dt`, `t`
+/// and state as the last output); otherwise, including the first call of every new
+/// prediction, the step self-starts with [`Rk4`].
+#[derive(Clone, Debug)]
+pub struct AdamsBashforth2<S> {
+    history: Option<History<S>>,
+}
+
+#[derive(Clone, Debug)]
+struct History<S> {
+    f_prev: S,
+    last: S,
+    t_last: f64,
+    dt: f64,
+}
+
+impl<S> AdamsBashforth2<S> {
+    pub fn new() -> Self {
+        Self { history: None }
+    }
+
+    /// Forget the cached derivative so the next step self-starts again.
+    pub fn reset(&mut self) {
+        self.history = None;
+    }
+}
+
+impl<S> Default for AdamsBashforth2<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Stepper<M> for AdamsBashforth2<M::State>
+where
+    M: Dynamics,
+    M::State: PartialEq,
+{
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        assert!(dt.is_finite() && dt > 0.0, "dt must be finite and > 0");
+
+        let f_n = model.derivative(t, state, control);
+        let continues = self.history.as_ref().is_some_and(|h| {
+            (h.t_last - t).abs() <= 1e-9 * dt && (h.dt - dt).abs() <= 1e-12 * dt && h.last == *state
+        });
+
+        let next = match self.history.take() {
+            Some(h) if continues => state
+                .add_scaled(&f_n, 1.5 * dt)
+                .add_scaled(&h.f_prev, -0.5 * dt),
+            _ => Rk4.step(model, t, state, control, dt),
+        };
+
+        self.history = Some(History {
+            f_prev: f_n,
+            last: next.clone(),
+            t_last: t + dt,
+            dt,
+        });
+        next
+    }
+
+    fn order(&self) -> usize {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::ForwardEuler,
+        types::DroneInput,
+    };
+
+    const FORWARD: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.2,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    /// North position error at `t_end` against the closed form for a straight dash.
+    fn straight_line_error<S: Stepper<SimpleQuadcopter>>(solver: &mut S, steps: usize) -> f64 {
+        let model = SimpleQuadcopter::new(0.4);
+        let t_end = 5.0;
+        let p = predict(
+            &FORWARD,
+            SimpleQuadState::zero(),
+            &model,
+            solver,
+            0.0,
+            t_end,
+            steps,
+        );
+
+        let a = model.input_to_control(&FORWARD).ax_body_mps2;
+        let d = model.drag;
+        let exact = a / d * (t_end - (1.0 - (-d * t_end).exp()) / d);
+        (p.final_state().north_m - exact).abs()
+    }
+
+    #[test]
+    fn beats_forward_euler_on_straight_line() {
+        let ab2 = straight_line_error(&mut AdamsBashforth2::new(), 100);
+        let fe = straight_line_error(&mut ForwardEuler, 100);
+        assert!(ab2 < fe / 20.0, "ab2 {ab2} vs fe {fe}");
+
+        let order = (straight_line_error(&mut AdamsBashforth2::new(), 50) / ab2).log2();
+        assert!((order - 2.0).abs() < 0.15, "observed order {order}");
+    }
+
+    #[test]
+    fn reuse_across_predictions_restarts_cleanly() {
+        let mut solver = AdamsBashforth2::new();
+        let first = straight_line_error(&mut solver, 100);
+        let second = straight_line_error(&mut solver, 100);
+        assert_eq!(first, second);
+
+        solver.reset();
+        assert!(solver.history.is_none());
+    }
+}
@@ -1,24 +1,51 @@
 use nalgebra::{DMatrix, DVector};
 
 use crate::{
-    traits::{LinearizableDynamics, Stepper},
+    traits::{LinearizableDynamics, StepStats, Stepper, check_jacobian_shape},
     types::StateVector,
 };
 
-use super::newton::{NewtonOpts, newton};
+use super::newton::{
+    JacobianReuse, NewtonOpts, NewtonWorkspace, newton_into, newton_modified_into,
+};
 
 /// Backward Euler implicit integrator using Newton's method.
-#[derive(Clone, Debug)]
+/// Newton buffers are kept between steps, so reusing one stepper over a long horizon
+/// avoids reallocating them every step.
+#[derive(Clone, Debug, Default)]
 pub struct BackwardEuler {
     pub newton_opts: NewtonOpts,
+    /// Debug mode: keep the Newton iterates of each step and report their residuals
+    /// through [`last_newton_residuals`](Self::last_newton_residuals). Off by default,
+    /// as it allocates and costs one extra derivative call per iterate.
+    pub record_newton_history: bool,
+    /// Jacobian refresh schedule. Reuse cuts Jacobian evaluations and factorizations
+    /// when `dt` is small; the converged steps agree with full Newton to within
+    /// `newton_opts`' tolerance.
+    pub jacobian_reuse: JacobianReuse,
+    workspace: NewtonWorkspace,
+    /// `dt` the factors in `workspace` were built for; `I - dt J` changes with it.
+    /// Other changes (control, time) are left to the stall check of
+    /// [`newton_modified_into`].
+    factored_dt: Option<f64>,
+    last_stats: StepStats,
+    newton_history: Vec<DVector<f64>>,
+    newton_residuals: Vec<f64>,
 }
 
-impl Default for BackwardEuler {
-    fn default() -> Self {
+impl BackwardEuler {
+    pub fn new(newton_opts: NewtonOpts) -> Self {
         Self {
-            newton_opts: NewtonOpts::default(),
+            newton_opts,
+            ..Self::default()
         }
     }
+
+    /// Max-norm residual `|F(x_k)|` of each Newton iterate of the last step, starting
+    /// with the initial guess. Empty unless `record_newton_history` is set.
+    pub fn last_newton_residuals(&self) -> &[f64] {
+        &self.newton_residuals
+    }
 }
 
 impl<M> Stepper<M> for BackwardEuler
@@ -41,27 +68,71 @@ where
         assert!(m > 0, "state dimension must be > 0");
 
         // F(x) = x - u_i - dt * f(t+dt, x)
-        let f_newton = |x: &DVector<f64>| -> DVector<f64> {
+        let f_newton = |x: &DVector<f64>, out: &mut DVector<f64>| {
             let x_state = M::State::from_dvector(x.clone());
             let fx = model.derivative(t + dt, &x_state, control);
-            let fx_vec = fx.to_dvector();
-            x - &u_prev - fx_vec * dt
+            out.copy_from(x);
+            *out -= &u_prev;
+            out.axpy(-dt, &fx.to_dvector(), 1.0);
         };
 
         // J(x) = I - dt * df/dx
-        let j_newton = |x: &DVector<f64>| -> DMatrix<f64> {
+        let j_newton = |x: &DVector<f64>, out: &mut DMatrix<f64>| {
             let x_state = M::State::from_dvector(x.clone());
             let j = model.jacobian(t + dt, &x_state, control);
-            assert!(
-                j.nrows() == m && j.ncols() == m,
-                "jacobian must be square with dimension matching the state"
-            );
-            DMatrix::<f64>::identity(m, m) - j * dt
+            if let Err(err) = check_jacobian_shape::<M>(&j, m) {
+                panic!("{err}");
+            }
+            out.copy_from(&j);
+            *out *= -dt;
+            for k in 0..m {
+                out[(k, k)] += 1.0;
+            }
         };
 
-        let (x_next, _) = newton(f_newton, j_newton, u_prev.clone(), self.newton_opts);
+        let mut x_next = u_prev.clone();
+        self.newton_history.clear();
+        self.newton_residuals.clear();
+        let history = self
+            .record_newton_history
+            .then_some(&mut self.newton_history);
+        self.last_stats = match self.jacobian_reuse {
+            JacobianReuse::EveryIteration => newton_into(
+                &f_newton,
+                j_newton,
+                &mut x_next,
+                self.newton_opts,
+                &mut self.workspace,
+                history,
+            ),
+            reuse => newton_modified_into(
+                &f_newton,
+                j_newton,
+                &mut x_next,
+                self.newton_opts,
+                &mut self.workspace,
+                reuse == JacobianReuse::AcrossSteps && self.factored_dt == Some(dt),
+                history,
+            ),
+        };
+        self.factored_dt = Some(dt);
+        if self.record_newton_history {
+            let mut residual = DVector::zeros(m);
+            for x in &self.newton_history {
+                f_newton(x, &mut residual);
+                self.newton_residuals.push(residual.amax());
+            }
+        }
         M::State::from_dvector(x_next)
     }
+
+    fn last_step_stats(&self) -> StepStats {
+        self.last_stats
+    }
+
+    fn order(&self) -> usize {
+        1
+    }
 }
 
 impl BackwardEuler {
@@ -69,3 +140,181 @@ impl BackwardEuler {
         num_complex::Complex::new(1.0, 0.0) / (num_complex::Complex::new(1.0, 0.0) - z)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        traits::Dynamics,
+        types::DroneInput,
+    };
+
+    /// Wrong-shaped Jacobian, as a model author might return by forgetting a row.
+    struct Misshaped(SimpleQuadcopter);
+
+    impl Dynamics for Misshaped {
+        type State = SimpleQuadState;
+        type Control = <SimpleQuadcopter as Dynamics>::Control;
+
+        fn input_to_control(&self, input: &DroneInput) -> Self::Control {
+            self.0.input_to_control(input)
+        }
+
+        fn derivative(
+            &self,
+            t: f64,
+            state: &SimpleQuadState,
+            control: &Self::Control,
+        ) -> SimpleQuadState {
+            self.0.derivative(t, state, control)
+        }
+    }
+
+    impl LinearizableDynamics for Misshaped {
+        fn jacobian(
+            &self,
+            _t: f64,
+            _state: &SimpleQuadState,
+            _control: &Self::Control,
+        ) -> DMatrix<f64> {
+            DMatrix::zeros(6, 7)
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "backward_euler::tests::Misshaped returned a 6×7 Jacobian but state dimension is 7"
+    )]
+    fn misshaped_jacobian_names_the_model() {
+        let model = Misshaped(SimpleQuadcopter::new(0.1));
+        let control = model.input_to_control(&DroneInput::try_new(0.0, 0.1, 0.0).unwrap());
+        let state = SimpleQuadState::zero();
+
+        let err = check_jacobian_shape::<Misshaped>(&model.jacobian(0.0, &state, &control), 7)
+            .unwrap_err();
+        assert_eq!((err.rows, err.cols, err.state_dim), (6, 7, 7));
+
+        BackwardEuler::default().step(&model, 0.0, &state, &control, 0.01);
+    }
+
+    /// `v_north' = -c v_north³`, stiff and nonlinear enough to need several Newton
+    /// iterations per step.
+    struct CubicDrag(f64);
+
+    impl Dynamics for CubicDrag {
+        type State = SimpleQuadState;
+        type Control = ();
+
+        fn input_to_control(&self, _input: &DroneInput) {}
+
+        fn derivative(&self, _t: f64, state: &SimpleQuadState, _control: &()) -> SimpleQuadState {
+            SimpleQuadState::new(0.0, 0.0, -self.0 * state.v_north_mps.powi(3), 0.0, 0.0)
+        }
+    }
+
+    impl LinearizableDynamics for CubicDrag {
+        fn jacobian(&self, _t: f64, state: &SimpleQuadState, _control: &()) -> DMatrix<f64> {
+            let mut j = DMatrix::zeros(7, 7);
+            j[(2, 2)] = -3.0 * self.0 * state.v_north_mps.powi(2);
+            j
+        }
+    }
+
+    #[test]
+    fn newton_history_shows_decreasing_residuals() {
+        let model = CubicDrag(1.0);
+        let state = SimpleQuadState::new(0.0, 0.0, 10.0, 0.0, 0.0);
+
+        let mut lean = BackwardEuler::default();
+        lean.step(&model, 0.0, &state, &(), 0.1);
+        assert!(lean.last_newton_residuals().is_empty());
+
+        let mut solver = BackwardEuler {
+            record_newton_history: true,
+            ..BackwardEuler::default()
+        };
+        let next = solver.step(&model, 0.0, &state, &(), 0.1);
+        assert_eq!(next, lean.step(&model, 0.0, &state, &(), 0.1));
+
+        let residuals = solver.last_newton_residuals();
+        assert_eq!(
+            residuals.len(),
+            Stepper::<CubicDrag>::last_step_stats(&solver).newton_iters + 1
+        );
+        assert!(residuals.len() >= 5, "{residuals:?}");
+        assert!(residuals.windows(2).all(|w| w[1] < w[0]), "{residuals:?}");
+        assert!(*residuals.last().unwrap() < solver.newton_opts.min_error);
+    }
+
+    #[test]
+    fn reused_factors_follow_a_control_change() {
+        let model = SimpleQuadcopter::new(5.0);
+        let control = |pitch_deg: f64, yaw_rate_rps| {
+            model.input_to_control(&DroneInput {
+                roll_rad: 0.0,
+                pitch_rad: pitch_deg.to_radians(),
+                yaw_rate_rps,
+                thrust_norm: 0.5,
+            })
+        };
+        let (gentle, hard) = (control(2.0, 0.1), control(-30.0, -2.0));
+        let dt = 0.05;
+
+        let run = |jacobian_reuse| {
+            let mut solver = BackwardEuler {
+                jacobian_reuse,
+                ..BackwardEuler::default()
+            };
+            let mut state = SimpleQuadState::new(0.0, 0.0, 3.0, 0.0, 0.0);
+            for i in 0..100 {
+                let u = if i < 50 { &gentle } else { &hard };
+                state = solver.step(&model, i as f64 * dt, &state, u, dt);
+                assert!(Stepper::<SimpleQuadcopter>::last_step_stats(&solver).converged);
+            }
+            state
+        };
+
+        let full = run(JacobianReuse::EveryIteration);
+        let across = run(JacobianReuse::AcrossSteps);
+        assert!((across.to_dvector() - full.to_dvector()).amax() < 1e-8);
+    }
+
+    #[test]
+    fn reused_jacobians_match_full_newton_with_fewer_evaluations() {
+        let model = CubicDrag(1.0);
+        let (dt, steps) = (0.005, 400);
+
+        let run = |jacobian_reuse| {
+            let mut solver = BackwardEuler {
+                jacobian_reuse,
+                ..BackwardEuler::default()
+            };
+            let mut state = SimpleQuadState::new(0.0, 0.0, 3.0, 0.0, 0.0);
+            let mut evals = 0;
+            for i in 0..steps {
+                state = solver.step(&model, i as f64 * dt, &state, &(), dt);
+                let stats = Stepper::<CubicDrag>::last_step_stats(&solver);
+                assert!(stats.converged, "{jacobian_reuse:?} step {i}");
+                evals += stats.jacobian_evals;
+            }
+            (state.v_north_mps, evals)
+        };
+
+        let (full, full_evals) = run(JacobianReuse::EveryIteration);
+        let (per_step, per_step_evals) = run(JacobianReuse::PerStep);
+        let (across, across_evals) = run(JacobianReuse::AcrossSteps);
+
+        // Exact solution 1 / sqrt(1/9 + 2t), up to Backward Euler's own error; the
+        // variants differ only by the per-step Newton tolerance accumulated over the run
+        let exact = 1.0 / (1.0 / 9.0 + 2.0 * dt * steps as f64).sqrt();
+        assert!((full - exact).abs() < 1e-2);
+        assert!((per_step - full).abs() < 1e-7, "{per_step} vs {full}");
+        assert!((across - full).abs() < 1e-7, "{across} vs {full}");
+        assert_eq!(per_step_evals, steps);
+        assert!(
+            per_step_evals < full_evals && 4 * across_evals < per_step_evals,
+            "evaluations: full {full_evals}, per step {per_step_evals}, across {across_evals}"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    traits::{LinearizableDynamics, StepStats, Stepper, check_jacobian_shape},
+    types::StateVector,
+};
+
+use super::newton::{NewtonOpts, NewtonWorkspace, newton_into};
+
+/// Two-step backward differentiation formula:
+/// `x_{n+1} = (4/3) x_n - (1/3) x_{n-1} + (2/3) dt f(t + dt, x_{n+1})`, solved with Newton.
+///
+/// `Stepper::step` only receives the current state, so the previous one is kept
+/// internally. The history is used only when the call continues the last step (same
+/// `dt`, `t` and state as the last output); otherwise, including the very first call,
+/// the step self-starts with Backward Euler.
+#[derive(Clone, Debug, Default)]
+pub struct Bdf2 {
+    pub newton_opts: NewtonOpts,
+    workspace: NewtonWorkspace,
+    history: Option<History>,
+    last_stats: StepStats,
+}
+
+#[derive(Clone, Debug)]
+struct History {
+    prev: DVector<f64>,
+    last: DVector<f64>,
+    t_last: f64,
+    dt: f64,
+}
+
+impl Bdf2 {
+    pub fn new(newton_opts: NewtonOpts) -> Self {
+        Self {
+            newton_opts,
+            ..Self::default()
+        }
+    }
+
+    /// Forget the stored history so the next step self-starts again.
+    pub fn reset(&mut self) {
+        self.history = None;
+    }
+
+    /// Previous state if `(t, x, dt)` continues the last step.
+    fn previous(&self, t: f64, x: &DVector<f64>, dt: f64) -> Option<&DVector<f64>> {
+        self.history
+            .as_ref()
+            .filter(|h| {
+                (h.t_last - t).abs() <= 1e-9 * dt && (h.dt - dt).abs() <= 1e-12 * dt && h.last == *x
+            })
+            .map(|h| &h.prev)
+    }
+}
+
+impl<M> Stepper<M> for Bdf2
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+{
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        assert!(dt.is_finite() && dt > 0.0, "dt must be finite and > 0");
+
+        let x_n = state.to_dvector();
+        let m = x_n.len();
+        assert!(m > 0, "state dimension must be > 0");
+
+        // Solve x - rhs - gamma * f(t + dt, x) = 0, where Backward Euler has
+        // rhs = x_n, gamma = dt and BDF2 rhs = (4 x_n - x_{n-1}) / 3, gamma = 2 dt / 3.
+        let (rhs, gamma) = match self.previous(t, &x_n, dt) {
+            Some(x_prev) => ((&x_n * 4.0 - x_prev) / 3.0, 2.0 * dt / 3.0),
+            None => (x_n.clone(), dt),
+        };
+
+        let f_newton = |x: &DVector<f64>, out: &mut DVector<f64>| {
+            let x_state = M::State::from_dvector(x.clone());
+            let fx = model.derivative(t + dt, &x_state, control);
+            out.copy_from(x);
+            *out -= &rhs;
+            out.axpy(-gamma, &fx.to_dvector(), 1.0);
+        };
+
+        let j_newton = |x: &DVector<f64>, out: &mut DMatrix<f64>| {
+            let x_state = M::State::from_dvector(x.clone());
+            let j = model.jacobian(t + dt, &x_state, control);
+            if let Err(err) = check_jacobian_shape::<M>(&j, m) {
+                panic!("{err}");
+            }
+            out.copy_from(&j);
+            *out *= -gamma;
+            for k in 0..m {
+                out[(k, k)] += 1.0;
+            }
+        };
+
+        let mut x_next = x_n.clone();
+        self.last_stats = newton_into(
+            f_newton,
+            j_newton,
+            &mut x_next,
+            self.newton_opts,
+            &mut self.workspace,
+            None,
+        );
+
+        self.history = Some(History {
+            prev: x_n,
+            last: x_next.clone(),
+            t_last: t + dt,
+            dt,
+        });
+        M::State::from_dvector(x_next)
+    }
+
+    fn last_step_stats(&self) -> StepStats {
+        self.last_stats
+    }
+
+    fn order(&self) -> usize {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        solvers::BackwardEuler,
+        traits::Dynamics,
+        types::DroneInput,
+    };
+
+    const COAST: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    fn drag_error<S: Stepper<SimpleQuadcopter>>(solver: &mut S, steps: usize) -> f64 {
+        let drag = 0.8;
+        let t_end = 2.0;
+        let model = SimpleQuadcopter::new(drag);
+        let control = model.input_to_control(&COAST);
+        let dt = t_end / steps as f64;
+
+        let mut state = SimpleQuadState::new(0.0, 0.0, 3.0, -1.0, 0.0);
+        for i in 0..steps {
+            state = solver.step(&model, i as f64 * dt, &state, &control, dt);
+        }
+
+        let decay = (-drag * t_end).exp();
+        let exact = SimpleQuadState::new(
+            3.0 * (1.0 - decay) / drag,
+            -(1.0 - decay) / drag,
+            3.0 * decay,
+            -decay,
+            0.0,
+        );
+        (state.to_dvector() - exact.to_dvector()).amax()
+    }
+
+    #[test]
+    fn second_order_on_drag_only_case() {
+        let coarse = drag_error(&mut Bdf2::default(), 50);
+        let fine = drag_error(&mut Bdf2::default(), 100);
+        let order = (coarse / fine).log2();
+        assert!((order - 2.0).abs() < 0.1, "observed order {order}");
+
+        let be = drag_error(&mut BackwardEuler::default(), 100);
+        assert!(fine < be / 10.0);
+    }
+
+    #[test]
+    fn self_starts_with_backward_euler_on_discontinuity() {
+        let model = SimpleQuadcopter::new(0.5);
+        let control = model.input_to_control(&COAST);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 1.0, 0.0);
+
+        let mut bdf = Bdf2::default();
+        let first = bdf.step(&model, 0.0, &start, &control, 0.1);
+        let be = BackwardEuler::default().step(&model, 0.0, &start, &control, 0.1);
+        assert_eq!(first, be);
+
+        // An unrelated state restarts from scratch instead of reusing stale history
+        let other = SimpleQuadState::new(5.0, 5.0, -1.0, 0.0, 0.0);
+        let restarted = bdf.step(&model, 0.1, &other, &control, 0.1);
+        let be = BackwardEuler::default().step(&model, 0.1, &other, &control, 0.1);
+        assert_eq!(restarted, be);
+    }
+}
@@ -0,0 +1,160 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    rng::{Rng, SplitMix64},
+    traits::{Dynamics, Stepper},
+    types::{IntegrableState, StateVector},
+};
+
+/// Euler-Maruyama integrator for `dx = f(t, x, u) dt + G dW`, with `G` a constant
+/// `m × k` diffusion matrix and `dW` a `k`-dimensional Wiener increment drawn from the
+/// owned RNG: `x_{n+1} = x_n + dt * f + sqrt(dt) * G * ξ`, `ξ ~ N(0, I_k)`.
+#[derive(Clone, Debug)]
+pub struct EulerMaruyama<R = SplitMix64> {
+    pub diffusion: DMatrix<f64>,
+    rng: R,
+}
+
+impl EulerMaruyama {
+    /// Seeded with the crate's [`SplitMix64`].
+    pub fn new(diffusion: DMatrix<f64>, seed: u64) -> Self {
+        Self::with_rng(diffusion, SplitMix64::new(seed))
+    }
+}
+
+impl<R: Rng> EulerMaruyama<R> {
+    pub fn with_rng(diffusion: DMatrix<f64>, rng: R) -> Self {
+        Self { diffusion, rng }
+    }
+}
+
+impl<M, R> Stepper<M> for EulerMaruyama<R>
+where
+    M: Dynamics,
+    M::State: StateVector,
+    R: Rng,
+{
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        assert!(dt.is_finite() && dt > 0.0, "dt must be finite and > 0");
+
+        let dx = model.derivative(t, state, control);
+        let drift = state.add_scaled(&dx, dt);
+
+        let k = self.diffusion.ncols();
+        let xi = DVector::from_fn(k, |_, _| self.rng.standard_normal());
+        let noise = &self.diffusion * xi * dt.sqrt();
+
+        let x = drift.to_dvector();
+        assert!(
+            noise.len() == x.len(),
+            "diffusion matrix must have one row per state component"
+        );
+        M::State::from_dvector(x + noise)
+    }
+
+    /// Strong order 1 for the additive (state-independent) diffusion used here.
+    fn order(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::ForwardEuler,
+        types::DroneInput,
+    };
+
+    fn input() -> DroneInput {
+        DroneInput {
+            roll_rad: 3f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.3,
+            thrust_norm: 0.5,
+        }
+    }
+
+    #[test]
+    fn zero_diffusion_reproduces_forward_euler() {
+        let model = SimpleQuadcopter::new(0.1);
+        let x0 = SimpleQuadState::new(1.0, -2.0, 3.0, 0.5, 0.1);
+
+        let mut em = EulerMaruyama::new(DMatrix::zeros(7, 2), 3);
+        let stochastic = predict(&input(), x0, &model, &mut em, 0.0, 2.0, 200);
+        let explicit = predict(&input(), x0, &model, &mut ForwardEuler, 0.0, 2.0, 200);
+
+        assert_eq!(stochastic.states, explicit.states);
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let model = SimpleQuadcopter::new(0.1);
+        let mut g = DMatrix::zeros(7, 2);
+        g[(2, 0)] = 0.5;
+        g[(3, 1)] = 0.5;
+
+        let run = |seed| {
+            let mut em = EulerMaruyama::new(g.clone(), seed);
+            predict(
+                &input(),
+                SimpleQuadState::zero(),
+                &model,
+                &mut em,
+                0.0,
+                2.0,
+                200,
+            )
+        };
+
+        assert_eq!(run(11).states, run(11).states);
+        assert_ne!(run(11).final_state(), run(12).final_state());
+    }
+
+    /// Yields the same normal sample on every draw.
+    struct ConstantNormal(f64);
+
+    impl Rng for ConstantNormal {
+        fn next_u64(&mut self) -> u64 {
+            unreachable!("only normals are drawn")
+        }
+
+        fn standard_normal(&mut self) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn injected_rng_drives_the_wiener_increments() {
+        let model = SimpleQuadcopter::new(0.1);
+        let mut g = DMatrix::zeros(7, 1);
+        g[(2, 0)] = 0.5;
+
+        let (dt, xi) = (0.01, 1.5);
+        let mut em = EulerMaruyama::with_rng(g, ConstantNormal(xi));
+        let hover = DroneInput {
+            pitch_rad: 0.0,
+            roll_rad: 0.0,
+            ..input()
+        };
+        let control = model.input_to_control(&hover);
+        let x0 = SimpleQuadState::zero();
+
+        // Zero drift at rest, so each step adds exactly G * xi * sqrt(dt)
+        let x1 = em.step(&model, 0.0, &x0, &control, dt);
+        assert_eq!(x1.v_north_mps, 0.5 * xi * dt.sqrt());
+        let x2 = em.step(&model, dt, &x1, &control, dt);
+        let expected = x1.v_north_mps * (1.0 - dt * 0.1) + 0.5 * xi * dt.sqrt();
+        assert!((x2.v_north_mps - expected).abs() < 1e-15);
+        assert_eq!(x2.v_east_mps, 0.0);
+    }
+}
@@ -22,9 +22,57 @@ impl<M: Dynamics> Stepper<M> for ForwardEuler {
         dt: f64,
     ) -> M::State {
         assert!(dt.is_finite(), "dt must be finite");
-        assert!(dt > 0.0, "dt must be > 0");
+        assert!(dt != 0.0, "dt must be non-zero");
 
         let dx = model.derivative(t, state, control);
         state.add_scaled(&dx, dt)
     }
+
+    fn order(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        types::DroneInput,
+    };
+
+    #[test]
+    fn drives_predict_for_simple_quadcopter() {
+        let model = SimpleQuadcopter::new(0.1);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 10f64.to_radians(),
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let (t_final, steps) = (2.0, 4);
+        let dt = t_final / steps as f64;
+
+        let prediction = predict(
+            &input,
+            SimpleQuadState::zero(),
+            &model,
+            &mut ForwardEuler,
+            0.0,
+            t_final,
+            steps,
+        );
+
+        // Straight-line first-order velocity recursion: v_{k+1} = v_k + dt * (a - drag * v_k)
+        let a = model.input_to_control(&input).ax_body_mps2;
+        let (mut north, mut v) = (0.0, 0.0);
+        for (k, s) in prediction.states.iter().enumerate() {
+            assert_eq!(s.north_m, north, "north at step {k}");
+            assert_eq!(s.v_north_mps, v, "v_north at step {k}");
+            north += dt * v;
+            v += dt * (a - model.drag * v);
+        }
+        assert_eq!(prediction.states.len(), steps + 1);
+    }
 }
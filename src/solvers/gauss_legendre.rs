@@ -0,0 +1,201 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    traits::{LinearizableDynamics, StepStats, Stepper, check_jacobian_shape},
+    types::StateVector,
+};
+
+use super::newton::{NewtonOpts, NewtonWorkspace, newton_into};
+
+const SQRT3_6: f64 = 0.288_675_134_594_812_9; // √3 / 6
+
+/// Butcher tableau of the 2-stage Gauss–Legendre method.
+const A: [[f64; 2]; 2] = [[0.25, 0.25 - SQRT3_6], [0.25 + SQRT3_6, 0.25]];
+const B: [f64; 2] = [0.5, 0.5];
+const C: [f64; 2] = [0.5 - SQRT3_6, 0.5 + SQRT3_6];
+
+/// 2-stage Gauss–Legendre implicit Runge–Kutta integrator: 4th order and A-stable.
+/// The coupled stage derivatives `K = [k1; k2]` (dimension `2m`) are solved together
+/// with Newton's method on a block Jacobian assembled from the model Jacobian.
+#[derive(Clone, Debug, Default)]
+pub struct GaussLegendre2 {
+    pub newton_opts: NewtonOpts,
+    workspace: NewtonWorkspace,
+    last_stats: StepStats,
+}
+
+impl GaussLegendre2 {
+    pub fn new(newton_opts: NewtonOpts) -> Self {
+        Self {
+            newton_opts,
+            workspace: NewtonWorkspace::default(),
+            last_stats: StepStats::default(),
+        }
+    }
+
+    /// Stability function `R(z) = (1 + z/2 + z²/12) / (1 - z/2 + z²/12)`.
+    pub fn stability(z: num_complex::Complex<f64>) -> num_complex::Complex<f64> {
+        let z2 = z * z / 12.0;
+        (1.0 + z / 2.0 + z2) / (1.0 - z / 2.0 + z2)
+    }
+}
+
+/// Stage state `x + dt * Σ_j a_ij k_j`.
+fn stage_state(x: &DVector<f64>, k: &DVector<f64>, row: &[f64; 2], dt: f64) -> DVector<f64> {
+    let m = x.len();
+    let mut y = x.clone();
+    y.axpy(dt * row[0], &k.rows(0, m), 1.0);
+    y.axpy(dt * row[1], &k.rows(m, m), 1.0);
+    y
+}
+
+impl<M> Stepper<M> for GaussLegendre2
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+{
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        assert!(dt.is_finite() && dt > 0.0, "dt must be finite and > 0");
+
+        let x = state.to_dvector();
+        let m = x.len();
+        assert!(m > 0, "state dimension must be > 0");
+
+        // F(K)_i = k_i - f(t + c_i dt, x + dt Σ_j a_ij k_j)
+        let f_newton = |k: &DVector<f64>, out: &mut DVector<f64>| {
+            out.copy_from(k);
+            for i in 0..2 {
+                let y = M::State::from_dvector(stage_state(&x, k, &A[i], dt));
+                let fy = model.derivative(t + C[i] * dt, &y, control).to_dvector();
+                out.rows_mut(i * m, m).axpy(-1.0, &fy, 1.0);
+            }
+        };
+
+        // J(K)_ij = δ_ij I - dt a_ij J_f(stage i)
+        let j_newton = |k: &DVector<f64>, out: &mut DMatrix<f64>| {
+            out.fill(0.0);
+            for i in 0..2 {
+                let y = M::State::from_dvector(stage_state(&x, k, &A[i], dt));
+                let jf = model.jacobian(t + C[i] * dt, &y, control);
+                if let Err(err) = check_jacobian_shape::<M>(&jf, m) {
+                    panic!("{err}");
+                }
+                for (j, &a_ij) in A[i].iter().enumerate() {
+                    let mut block = out.view_mut((i * m, j * m), (m, m));
+                    block.copy_from(&jf);
+                    block *= -dt * a_ij;
+                }
+            }
+            for d in 0..2 * m {
+                out[(d, d)] += 1.0;
+            }
+        };
+
+        // Start both stages from the explicit slope
+        let f0 = model.derivative(t, state, control).to_dvector();
+        let mut k = DVector::zeros(2 * m);
+        k.rows_mut(0, m).copy_from(&f0);
+        k.rows_mut(m, m).copy_from(&f0);
+
+        self.last_stats = newton_into(
+            f_newton,
+            j_newton,
+            &mut k,
+            self.newton_opts,
+            &mut self.workspace,
+            None,
+        );
+
+        let mut x_next = x;
+        x_next.axpy(dt * B[0], &k.rows(0, m), 1.0);
+        x_next.axpy(dt * B[1], &k.rows(m, m), 1.0);
+        M::State::from_dvector(x_next)
+    }
+
+    fn last_step_stats(&self) -> StepStats {
+        self.last_stats
+    }
+
+    fn order(&self) -> usize {
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        traits::Dynamics,
+        types::DroneInput,
+    };
+
+    const COAST: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    /// Max abs error against the drag-only closed form after `t_end` seconds.
+    fn drag_error(drag: f64, steps: usize, t_end: f64) -> f64 {
+        let model = SimpleQuadcopter::new(drag);
+        let control = model.input_to_control(&COAST);
+        let (v0n, v0e) = (4.0, -2.0);
+        let dt = t_end / steps as f64;
+
+        let mut solver = GaussLegendre2::default();
+        let mut state = SimpleQuadState::new(0.0, 0.0, v0n, v0e, 0.3);
+        for i in 0..steps {
+            state = solver.step(&model, i as f64 * dt, &state, &control, dt);
+        }
+
+        let decay = (-drag * t_end).exp();
+        let exact = SimpleQuadState::new(
+            v0n * (1.0 - decay) / drag,
+            v0e * (1.0 - decay) / drag,
+            v0n * decay,
+            v0e * decay,
+            0.3,
+        );
+        (state.to_dvector() - exact.to_dvector()).amax()
+    }
+
+    #[test]
+    fn fourth_order_on_drag_only_case() {
+        let coarse = drag_error(1.0, 10, 2.0);
+        let fine = drag_error(1.0, 20, 2.0);
+        let order = (coarse / fine).log2();
+        assert!((order - 4.0).abs() < 0.2, "observed order {order}");
+    }
+
+    #[test]
+    fn stays_bounded_for_stiff_drag_at_large_dt() {
+        // drag * dt = 500: far outside any explicit method's stability region. Gauss–
+        // Legendre is A- but not L-stable (|R(-∞)| = 1), so stiff modes decay slowly
+        // rather than vanish; the requirement is only that they never grow.
+        let error = drag_error(500.0, 10, 10.0);
+        assert!(error.is_finite() && error < 4.0, "error {error}");
+    }
+
+    #[test]
+    fn stability_function_is_a_stable() {
+        for &z in &[
+            Complex::new(-1.0, 0.0),
+            Complex::new(-100.0, 50.0),
+            Complex::new(-1e-3, 10.0),
+        ] {
+            assert!(GaussLegendre2::stability(z).norm() <= 1.0);
+        }
+        assert!((GaussLegendre2::stability(Complex::new(0.0, 3.0)).norm() - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,9 +1,79 @@
+mod adams_bashforth;
 mod backward_euler;
+mod bdf2;
+#[cfg(feature = "rng")]
+mod euler_maruyama;
 mod forward_euler;
+mod gauss_legendre;
 mod newton;
 mod rk4;
+mod semi_implicit_euler;
+mod stability;
+mod yaw_split_euler;
 
+pub use adams_bashforth::AdamsBashforth2;
 pub use backward_euler::BackwardEuler;
+pub use bdf2::Bdf2;
+#[cfg(feature = "rng")]
+pub use euler_maruyama::EulerMaruyama;
 pub use forward_euler::ForwardEuler;
-pub use newton::{NewtonOpts, newton};
+pub use gauss_legendre::GaussLegendre2;
+pub use newton::{
+    JacobianReuse, NewtonOpts, NewtonWorkspace, newton, newton_into, newton_modified_into,
+};
 pub use rk4::Rk4;
+pub use semi_implicit_euler::SemiImplicitEuler;
+pub use stability::{max_stable_dt, max_stable_dt_forward_euler, stability_boundary};
+pub use yaw_split_euler::{YawCoupling, YawSplitEuler};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        traits::Stepper,
+        types::{DroneInput, StateVector},
+    };
+
+    const TURN: DroneInput = DroneInput {
+        roll_rad: 0.05,
+        pitch_rad: 0.2,
+        yaw_rate_rps: 0.3,
+        thrust_norm: 0.5,
+    };
+
+    /// Asserts the reported order and that it matches the order observed on a smooth
+    /// turn against a fine RK4 reference.
+    fn check_order<S: Stepper<SimpleQuadcopter>>(
+        name: &str,
+        make: impl Fn() -> S,
+        expected: usize,
+    ) {
+        assert_eq!(make().order(), expected, "{name}");
+
+        let model = SimpleQuadcopter::new(0.4);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+        let reference = predict(&TURN, start, &model, &mut Rk4, 0.0, 4.0, 40_000);
+        let error = |steps| {
+            let p = predict(&TURN, start, &model, &mut make(), 0.0, 4.0, steps);
+            (p.final_state().to_dvector() - reference.final_state().to_dvector()).amax()
+        };
+
+        let observed = (error(40) / error(80)).log2();
+        assert!(
+            (observed - expected as f64).abs() < 0.3,
+            "{name}: observed order {observed}"
+        );
+    }
+
+    #[test]
+    fn shipped_solvers_report_their_order() {
+        check_order("ForwardEuler", || ForwardEuler, 1);
+        check_order("BackwardEuler", BackwardEuler::default, 1);
+        check_order("Bdf2", Bdf2::default, 2);
+        check_order("AdamsBashforth2", AdamsBashforth2::new, 2);
+        check_order("Rk4", || Rk4, 4);
+        check_order("GaussLegendre2", GaussLegendre2::default, 4);
+    }
+}
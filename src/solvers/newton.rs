@@ -1,9 +1,17 @@
 use nalgebra::{DMatrix, DVector};
 
+use crate::traits::StepStats;
+
 #[derive(Clone, Copy, Debug)]
 pub struct NewtonOpts {
     pub iter_max: usize,
+    /// Absolute residual tolerance (`atol`).
     pub min_error: f64,
+    /// Relative tolerance (`rtol`). When non-zero, convergence is judged per component
+    /// on `|F_i| / (min_error + rel_error * |x_i|) < 1`, so large-magnitude components
+    /// (meters far from the origin) are not held to the roundoff-limited absolute bound
+    /// that suits small ones (radians). Zero keeps the purely absolute check.
+    pub rel_error: f64,
 }
 
 impl Default for NewtonOpts {
@@ -11,10 +19,60 @@ impl Default for NewtonOpts {
         Self {
             iter_max: 15,
             min_error: 1e-10,
+            rel_error: 0.0,
         }
     }
 }
 
+impl NewtonOpts {
+    /// Whether residual `fx` at iterate `x` meets the (mixed) tolerance.
+    fn is_converged(&self, fx: &DVector<f64>, x: &DVector<f64>) -> bool {
+        fx.iter()
+            .zip(x.iter())
+            .all(|(f, xi)| f.abs() < self.min_error + self.rel_error * xi.abs())
+    }
+}
+
+/// Preallocated buffers for [`newton_into`], reusable across solves of the same size.
+#[derive(Clone, Debug)]
+pub struct NewtonWorkspace {
+    /// Residual `F(x)`; holds the Newton update after each solve.
+    pub fx: DVector<f64>,
+    /// Jacobian `dF/dx`; overwritten by its LU factors during each solve.
+    pub jx: DMatrix<f64>,
+    pivots: Vec<usize>,
+    /// Whether `jx` and `pivots` hold the LU factors of a non-singular Jacobian.
+    factored: bool,
+}
+
+impl NewtonWorkspace {
+    pub fn new(m: usize) -> Self {
+        Self {
+            fx: DVector::zeros(m),
+            jx: DMatrix::zeros(m, m),
+            pivots: Vec::with_capacity(m),
+            factored: false,
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.fx.len()
+    }
+
+    /// Resize the buffers for an `m`-dimensional problem (no-op if already sized).
+    pub fn ensure_dim(&mut self, m: usize) {
+        if self.dim() != m {
+            *self = Self::new(m);
+        }
+    }
+}
+
+impl Default for NewtonWorkspace {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
 /// Newton method for vector root finding: solve `F(x) = 0`.
 /// Returns (solution, iterate history).
 pub fn newton<F, J>(
@@ -29,24 +87,313 @@ where
 {
     let mut x_hist = Vec::with_capacity(opts.iter_max);
     let mut x = x0;
-    x_hist.push(x.clone());
+    let mut ws = NewtonWorkspace::new(x.len());
+
+    newton_into(
+        |x, out| out.copy_from(&f(x)),
+        |x, out| out.copy_from(&dfdx(x)),
+        &mut x,
+        opts,
+        &mut ws,
+        Some(&mut x_hist),
+    );
+
+    (x, x_hist)
+}
+
+/// Allocation-free core of [`newton`]: iterates on `x` in place, with `f` and `dfdx`
+/// writing into the workspace buffers. The iterate history (including the initial
+/// guess) is pushed to `history` only when one is given.
+/// Returns the number of Newton updates applied and whether the residual converged.
+pub fn newton_into<F, J>(
+    mut f: F,
+    mut dfdx: J,
+    x: &mut DVector<f64>,
+    opts: NewtonOpts,
+    ws: &mut NewtonWorkspace,
+    mut history: Option<&mut Vec<DVector<f64>>>,
+) -> StepStats
+where
+    F: FnMut(&DVector<f64>, &mut DVector<f64>),
+    J: FnMut(&DVector<f64>, &mut DMatrix<f64>),
+{
+    ws.ensure_dim(x.len());
+    if let Some(hist) = history.as_deref_mut() {
+        hist.push(x.clone());
+    }
 
+    let mut updates = 0;
+    let mut converged = false;
     for _ in 1..opts.iter_max {
-        let fx = f(&x);
-        let max_abs = fx.iter().fold(0.0_f64, |acc, &v| acc.max(v.abs()));
-        if max_abs < opts.min_error {
+        f(x, &mut ws.fx);
+        if opts.is_converged(&ws.fx, x) {
+            converged = true;
             break;
         }
 
-        let jx = dfdx(&x);
-        let delta = jx
-            .lu()
-            .solve(&fx)
-            .expect("Newton: Jacobian is singular / solve failed");
+        dfdx(x, &mut ws.jx);
+        ws.factored = lu_solve_in_place(&mut ws.jx, &mut ws.fx, &mut ws.pivots);
+        assert!(ws.factored, "Newton: Jacobian is singular / solve failed");
 
-        x = x - delta;
-        x_hist.push(x.clone());
+        *x -= &ws.fx;
+        updates += 1;
+        if let Some(hist) = history.as_deref_mut() {
+            hist.push(x.clone());
+        }
     }
 
-    (x, x_hist)
+    #[cfg(feature = "trace")]
+    if !converged {
+        f(x, &mut ws.fx);
+        crate::trace::emit(crate::trace::TraceEvent::NewtonIterMax {
+            iter_max: opts.iter_max,
+            residual: ws.fx.amax(),
+        });
+    }
+
+    StepStats {
+        newton_iters: updates,
+        converged,
+        jacobian_evals: updates,
+    }
+}
+
+/// How often an implicit solver re-evaluates and refactorizes its Newton Jacobian.
+/// Besides the schedule, [`newton_modified_into`] refreshes the factors whenever
+/// convergence stalls, so reuse trades only speed, not the converged answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JacobianReuse {
+    /// Full Newton: a fresh Jacobian every iteration.
+    #[default]
+    EveryIteration,
+    /// Modified Newton: one Jacobian per step, at the initial guess.
+    PerStep,
+    /// Keep the factors from earlier steps, e.g. for small `dt` on smooth
+    /// trajectories where the Jacobian barely changes. They carry over changes of
+    /// control, time or state until convergence stalls.
+    AcrossSteps,
+}
+
+/// Residual contraction per iteration above which [`newton_modified_into`] treats
+/// the reused Jacobian as stale and refreshes it.
+const MAX_MODIFIED_RATE: f64 = 0.25;
+
+/// Modified Newton: like [`newton_into`], but solves with the LU factors already in
+/// `ws` when `reuse_factors` is set (from an earlier call of either function on the
+/// same-sized problem), and otherwise factorizes the Jacobian at the initial guess
+/// once. The Jacobian is only re-evaluated when an iteration shrinks the residual
+/// (max norm) by less than a factor of 4.
+///
+/// Reused factors may belong to a different `F`, e.g. an implicit step at another
+/// time or control. They only precondition the iteration: convergence is judged on
+/// `F` itself, and factors too far off stall it and get refreshed, at the cost of an
+/// iteration or two. A change that rescales the whole Jacobian, like `dt` in an
+/// implicit step, is still better met with fresh factors.
+pub fn newton_modified_into<F, J>(
+    mut f: F,
+    mut dfdx: J,
+    x: &mut DVector<f64>,
+    opts: NewtonOpts,
+    ws: &mut NewtonWorkspace,
+    reuse_factors: bool,
+    mut history: Option<&mut Vec<DVector<f64>>>,
+) -> StepStats
+where
+    F: FnMut(&DVector<f64>, &mut DVector<f64>),
+    J: FnMut(&DVector<f64>, &mut DMatrix<f64>),
+{
+    ws.ensure_dim(x.len());
+    if let Some(hist) = history.as_deref_mut() {
+        hist.push(x.clone());
+    }
+
+    let mut stale = !(reuse_factors && ws.factored);
+    let mut previous_residual = f64::INFINITY;
+    let (mut updates, mut jacobian_evals) = (0, 0);
+    let mut converged = false;
+    for _ in 1..opts.iter_max {
+        f(x, &mut ws.fx);
+        if opts.is_converged(&ws.fx, x) {
+            converged = true;
+            break;
+        }
+
+        let residual = ws.fx.amax();
+        if residual > MAX_MODIFIED_RATE * previous_residual {
+            stale = true;
+        }
+        if stale {
+            dfdx(x, &mut ws.jx);
+            ws.factored = lu_factor_in_place(&mut ws.jx, &mut ws.pivots);
+            assert!(ws.factored, "Newton: Jacobian is singular / solve failed");
+            jacobian_evals += 1;
+            stale = false;
+        }
+        lu_solve_factored(&ws.jx, &ws.pivots, &mut ws.fx);
+
+        *x -= &ws.fx;
+        updates += 1;
+        previous_residual = residual;
+        if let Some(hist) = history.as_deref_mut() {
+            hist.push(x.clone());
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    if !converged {
+        f(x, &mut ws.fx);
+        crate::trace::emit(crate::trace::TraceEvent::NewtonIterMax {
+            iter_max: opts.iter_max,
+            residual: ws.fx.amax(),
+        });
+    }
+
+    StepStats {
+        newton_iters: updates,
+        converged,
+        jacobian_evals,
+    }
+}
+
+/// Solve `a * x = b` by LU with partial pivoting, overwriting `a` with its factors and
+/// `b` with the solution. Returns `false` if `a` is singular.
+///
+/// This mirrors nalgebra's `LU::new(a).solve_mut(b)` operation for operation. It is
+/// written out because `LU::new` consumes the matrix and allocates a fresh permutation
+/// on every factorization. It also gives no way back to the buffer short of `unpack`,
+/// which allocates again. Here the factors stay in the workspace's `jx` and `pivots`,
+/// so [`newton_modified_into`] can solve against them on later steps without
+/// allocating.
+fn lu_solve_in_place(a: &mut DMatrix<f64>, b: &mut DVector<f64>, pivots: &mut Vec<usize>) -> bool {
+    if !lu_factor_in_place(a, pivots) {
+        return false;
+    }
+    lu_solve_factored(a, pivots, b);
+    true
+}
+
+/// Overwrite `a` with its LU factors (unit lower triangle below the diagonal) and
+/// `pivots` with the row swaps. Returns `false` if `a` is singular.
+fn lu_factor_in_place(a: &mut DMatrix<f64>, pivots: &mut Vec<usize>) -> bool {
+    let m = a.nrows();
+    pivots.clear();
+
+    for i in 0..m {
+        let piv = a.view_range(i.., i).icamax() + i;
+        pivots.push(piv);
+        let diag = a[(piv, i)];
+        if diag == 0.0 {
+            continue;
+        }
+
+        if piv != i {
+            a.swap_rows(i, piv);
+        }
+
+        let inv_diag = 1.0 / diag;
+        for r in (i + 1)..m {
+            a[(r, i)] *= inv_diag;
+        }
+        for k in (i + 1)..m {
+            let pivot = a[(i, k)];
+            for r in (i + 1)..m {
+                a[(r, k)] += -pivot * a[(r, i)];
+            }
+        }
+    }
+
+    (0..m).all(|i| a[(i, i)] != 0.0)
+}
+
+/// Solve with the factors from [`lu_factor_in_place`], overwriting `b`.
+fn lu_solve_factored(lu: &DMatrix<f64>, pivots: &[usize], b: &mut DVector<f64>) {
+    let m = lu.nrows();
+    // Permute, then forward (unit lower) and backward (upper) substitution
+    for (i, &piv) in pivots.iter().enumerate() {
+        if piv != i {
+            b.swap_rows(i, piv);
+        }
+    }
+    for i in 0..m {
+        let bi = b[i];
+        for r in (i + 1)..m {
+            b[r] += -bi * lu[(r, i)];
+        }
+    }
+    for i in (0..m).rev() {
+        b[i] /= lu[(i, i)];
+        let bi = b[i];
+        for r in 0..i {
+            b[r] += -bi * lu[(r, i)];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_place_lu_matches_nalgebra() {
+        let a = DMatrix::from_row_slice(
+            4,
+            4,
+            &[
+                0.0, 2.0, -1.0, 3.0, //
+                4.0, 1.0, 0.5, -2.0, //
+                -1.0, 3.0, 2.0, 0.0, //
+                2.0, -4.0, 1.0, 1.0,
+            ],
+        );
+        let b = DVector::from_vec(vec![1.0, -2.0, 0.5, 3.0]);
+
+        let expected = a.clone().lu().solve(&b).unwrap();
+        let (mut a_ws, mut b_ws, mut pivots) = (a, b, Vec::new());
+        assert!(lu_solve_in_place(&mut a_ws, &mut b_ws, &mut pivots));
+
+        assert_eq!(b_ws, expected);
+    }
+
+    #[test]
+    fn mixed_tolerance_converges_on_badly_scaled_state() {
+        // A far-away coordinate (~1.4e6 m) next to an angle-sized one: at the root the
+        // first residual is only known to ~1e-4, far above the absolute tolerance
+        let f = |x: &DVector<f64>, out: &mut DVector<f64>| {
+            out[0] = x[0] * x[0] - 2e12;
+            out[1] = x[1] * x[1] - 2.0;
+        };
+        let j = |x: &DVector<f64>, out: &mut DMatrix<f64>| {
+            out.fill(0.0);
+            out[(0, 0)] = 2.0 * x[0];
+            out[(1, 1)] = 2.0 * x[1];
+        };
+        let solve = |opts: NewtonOpts| {
+            let mut x = DVector::from_vec(vec![1.3e6, 1.0]);
+            let stats = newton_into(f, j, &mut x, opts, &mut NewtonWorkspace::default(), None);
+            (x, stats)
+        };
+
+        let (_, absolute) = solve(NewtonOpts {
+            iter_max: 50,
+            ..NewtonOpts::default()
+        });
+        assert!(!absolute.converged);
+        assert_eq!(absolute.newton_iters, 49);
+
+        let (x, mixed) = solve(NewtonOpts {
+            iter_max: 50,
+            rel_error: 1e-8,
+            ..NewtonOpts::default()
+        });
+        assert!(mixed.converged && mixed.newton_iters < 15, "{mixed:?}");
+        assert!((x[0] - 2e12f64.sqrt()).abs() < 1e-6);
+        assert!((x[1] - 2f64.sqrt()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn in_place_lu_reports_singular() {
+        let mut a = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        let mut b = DVector::from_vec(vec![1.0, 1.0]);
+        assert!(!lu_solve_in_place(&mut a, &mut b, &mut Vec::new()));
+    }
 }
@@ -7,37 +7,116 @@ use crate::{
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Rk4;
 
-impl<M: Dynamics> Stepper<M> for Rk4 {
-    fn step(
+impl Rk4 {
+    /// Advance `state` by one step in place (backward in time when `dt < 0`). A single
+    /// stage buffer, cloned from `state` once per step, is reused for all intermediate
+    /// states and the stage derivatives are accumulated in place, so a step makes five
+    /// fresh states: that buffer and the four returned by `derivative`.
+    pub fn step_in_place<M: Dynamics>(
         &mut self,
         model: &M,
         t: f64,
-        state: &M::State,
+        state: &mut M::State,
         control: &M::Control,
         dt: f64,
-    ) -> M::State {
-        assert!(dt.is_finite() && dt > 0.0, "dt must be finite and > 0");
+    ) {
+        assert!(
+            dt.is_finite() && dt != 0.0,
+            "dt must be finite and non-zero"
+        );
 
         let half_dt = 0.5 * dt;
 
-        let k1 = model.derivative(t, state, control);
-        let state_k2 = state.add_scaled(&k1, half_dt);
+        let mut incr = model.derivative(t, state, control);
+        let mut stage = state.clone();
+        stage.add_scaled_assign(&incr, half_dt);
 
-        let k2 = model.derivative(t + half_dt, &state_k2, control);
-        let state_k3 = state.add_scaled(&k2, half_dt);
+        let k2 = model.derivative(t + half_dt, &stage, control);
+        stage.clone_from(state);
+        stage.add_scaled_assign(&k2, half_dt);
 
-        let k3 = model.derivative(t + half_dt, &state_k3, control);
-        let state_k4 = state.add_scaled(&k3, dt);
+        let k3 = model.derivative(t + half_dt, &stage, control);
+        stage.clone_from(state);
+        stage.add_scaled_assign(&k3, dt);
 
-        let k4 = model.derivative(t + dt, &state_k4, control);
+        let k4 = model.derivative(t + dt, &stage, control);
 
         // x_{n+1} = x_n + dt/6 * (k1 + 2k2 + 2k3 + k4)
         let dt_over_6 = dt / 6.0;
+        incr.add_scaled_assign(&k2, 2.0);
+        incr.add_scaled_assign(&k3, 2.0);
+        incr.add_scaled_assign(&k4, 1.0);
+
+        state.add_scaled_assign(&incr, dt_over_6);
+    }
+}
+
+impl<M: Dynamics> Stepper<M> for Rk4 {
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        let mut next = state.clone();
+        self.step_in_place(model, t, &mut next, control, dt);
+        next
+    }
+
+    fn order(&self) -> usize {
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        types::DroneInput,
+    };
+
+    /// Allocating formulation the in-place step must reproduce bit for bit.
+    fn reference_step<M: Dynamics>(
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        let half_dt = 0.5 * dt;
+        let k1 = model.derivative(t, state, control);
+        let k2 = model.derivative(t + half_dt, &state.add_scaled(&k1, half_dt), control);
+        let k3 = model.derivative(t + half_dt, &state.add_scaled(&k2, half_dt), control);
+        let k4 = model.derivative(t + dt, &state.add_scaled(&k3, dt), control);
         let incr = k1
             .add_scaled(&k2, 2.0)
             .add_scaled(&k3, 2.0)
             .add_scaled(&k4, 1.0);
+        state.add_scaled(&incr, dt / 6.0)
+    }
+
+    #[test]
+    fn in_place_step_matches_allocating_formulation() {
+        let model = SimpleQuadcopter::new(0.2);
+        let control = model.input_to_control(&DroneInput {
+            roll_rad: 7f64.to_radians(),
+            pitch_rad: 12f64.to_radians(),
+            yaw_rate_rps: 0.4,
+            thrust_norm: 0.5,
+        });
+        let dt = 10.0 / 30_000.0;
+
+        let mut in_place = SimpleQuadState::new(0.0, 0.0, 5.0, 1.0, 0.3);
+        let mut reference = in_place;
+        for i in 0..30_000 {
+            let t = i as f64 * dt;
+            Rk4.step_in_place(&model, t, &mut in_place, &control, dt);
+            reference = reference_step(&model, t, &reference, &control, dt);
+        }
 
-        state.add_scaled(&incr, dt_over_6)
+        assert_eq!(in_place, reference);
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    traits::{Dynamics, Stepper},
+    types::SecondOrderState,
+};
+
+/// Semi-implicit (symplectic) Euler: the rates (velocities, and anything else that is
+/// not a position) take a Forward Euler step, then the positions advance with the
+/// *updated* velocities. Same cost as [`ForwardEuler`](super::ForwardEuler), but for
+/// position-dependent forces such as springs it keeps the energy bounded instead of
+/// letting it grow every step.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SemiImplicitEuler;
+
+impl<M> Stepper<M> for SemiImplicitEuler
+where
+    M: Dynamics,
+    M::State: SecondOrderState,
+{
+    fn step(
+        &mut self,
+        model: &M,
+        t: f64,
+        state: &M::State,
+        control: &M::Control,
+        dt: f64,
+    ) -> M::State {
+        assert!(dt.is_finite(), "dt must be finite");
+        assert!(dt != 0.0, "dt must be non-zero");
+
+        let dx = model.derivative(t, state, control);
+        state.add_scaled_rates(&dx, dt).advance_positions(dt)
+    }
+
+    fn order(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::ForwardEuler,
+        types::{DroneInput, IntegrableState},
+    };
+
+    /// Unit-mass spring `x'' = -k x`: the position-dependent force the scheme targets.
+    struct Spring {
+        k: f64,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Mass {
+        x: f64,
+        v: f64,
+    }
+
+    impl Mass {
+        fn energy(&self, k: f64) -> f64 {
+            0.5 * (self.v * self.v + k * self.x * self.x)
+        }
+    }
+
+    impl IntegrableState for Mass {
+        fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+            Mass {
+                x: self.x + scale * derivative.x,
+                v: self.v + scale * derivative.v,
+            }
+        }
+    }
+
+    impl SecondOrderState for Mass {
+        fn position_indices() -> &'static [usize] {
+            &[0]
+        }
+
+        fn velocity_indices() -> &'static [usize] {
+            &[1]
+        }
+
+        fn add_scaled_rates(&self, derivative: &Self, scale: f64) -> Self {
+            Mass {
+                v: self.v + scale * derivative.v,
+                ..*self
+            }
+        }
+
+        fn advance_positions(&self, scale: f64) -> Self {
+            Mass {
+                x: self.x + scale * self.v,
+                ..*self
+            }
+        }
+    }
+
+    impl Dynamics for Spring {
+        type State = Mass;
+        type Control = ();
+
+        fn input_to_control(&self, _input: &DroneInput) {}
+
+        fn derivative(&self, _t: f64, state: &Mass, _control: &()) -> Mass {
+            Mass {
+                x: state.v,
+                v: -self.k * state.x,
+            }
+        }
+    }
+
+    const HOVER: DroneInput = DroneInput {
+        roll_rad: 0.0,
+        pitch_rad: 0.0,
+        yaw_rate_rps: 0.0,
+        thrust_norm: 0.5,
+    };
+
+    #[test]
+    fn spring_energy_stays_bounded_where_forward_euler_grows() {
+        let spring = Spring { k: 4.0 };
+        let start = Mass { x: 1.0, v: 0.0 };
+        let e0 = start.energy(spring.k);
+        // Ten periods of 2 pi / sqrt(k) at 50 steps each
+        let (t_final, steps) = (10.0 * std::f64::consts::PI, 500);
+
+        let drift = |states: &[Mass]| {
+            states
+                .iter()
+                .map(|s| (s.energy(spring.k) / e0 - 1.0).abs())
+                .fold(0.0, f64::max)
+        };
+        let fe = predict(
+            &HOVER,
+            start,
+            &spring,
+            &mut ForwardEuler,
+            0.0,
+            t_final,
+            steps,
+        );
+        let se = predict(
+            &HOVER,
+            start,
+            &spring,
+            &mut SemiImplicitEuler,
+            0.0,
+            t_final,
+            steps,
+        );
+
+        // FE multiplies the energy by 1 + (k dt²) every step; SIE only oscillates by O(dt)
+        assert!(drift(&fe.states) > 10.0, "FE drift {}", drift(&fe.states));
+        assert!(drift(&se.states) < 0.15, "SIE drift {}", drift(&se.states));
+    }
+
+    #[test]
+    fn velocities_match_forward_euler_when_forces_ignore_position() {
+        // The quadcopter forces depend on heading and velocity only, so the rate update
+        // is Forward Euler's and only the positions differ, by dt times the velocity change
+        let model = SimpleQuadcopter::new(0.2);
+        let input = DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.1,
+            yaw_rate_rps: 0.5,
+            thrust_norm: 0.6,
+        };
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 0.0, 0.0);
+        let fe = predict(&input, start, &model, &mut ForwardEuler, 0.0, 5.0, 100);
+        let se = predict(&input, start, &model, &mut SemiImplicitEuler, 0.0, 5.0, 100);
+
+        for (a, b) in fe.states.iter().zip(&se.states) {
+            assert_eq!(
+                (a.v_north_mps, a.v_east_mps, a.yaw_rad, a.v_down_mps),
+                (b.v_north_mps, b.v_east_mps, b.yaw_rad, b.v_down_mps)
+            );
+        }
+        let dt = fe.dt();
+        let (last_fe, last_se) = (fe.final_state(), se.final_state());
+        let expected = dt * (last_fe.v_north_mps - start.v_north_mps);
+        assert!((last_se.north_m - last_fe.north_m - expected).abs() < 1e-9);
+    }
+}
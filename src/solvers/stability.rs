@@ -0,0 +1,190 @@
+use num_complex::Complex;
+
+use crate::{
+    solvers::ForwardEuler,
+    traits::{LinearizableDynamics, check_jacobian_shape},
+    types::StateVector,
+};
+
+/// Continuation sub-steps per full turn, so coarse `n_points` do not jump branches.
+const MIN_SUBSTEPS: usize = 512;
+const NEWTON_ITERS: usize = 50;
+const NEWTON_TOL: f64 = 1e-13;
+const FD_STEP: f64 = 1e-7;
+
+/// Slack on `|R(z)| <= 1`, so that zero eigenvalues (`R(0) = 1`) and their round-off
+/// do not count as unstable.
+const STABLE_TOL: f64 = 1e-9;
+/// Step sizes beyond this are reported as unlimited.
+const MAX_DT: f64 = 1e9;
+const BISECTION_ITERS: usize = 100;
+
+/// Boundary locus of a one-step method's stability region: the curve where
+/// `|R(z)| = 1`, traced by solving `R(z) = e^{iθ}` for `θ` around the unit circle.
+///
+/// Starts from `z = 0` (where `R(0) = 1` for any consistent method) and follows the
+/// branch through the origin by continuation in `θ`, using Newton's method with a
+/// finite-difference derivative. Returns `n_points + 1` points `(Re z, Im z)` with the
+/// last equal to the first, ready to draw as a closed curve. Methods whose boundary
+/// is unbounded (e.g. A-stable methods with `|R(∞)| = 1`) are not supported.
+pub fn stability_boundary<R>(stabfn: R, n_points: usize) -> Vec<(f64, f64)>
+where
+    R: Fn(Complex<f64>) -> Complex<f64>,
+{
+    assert!(n_points >= 3, "n_points must be >= 3");
+    let substeps = MIN_SUBSTEPS.div_ceil(n_points);
+    let dtheta = std::f64::consts::TAU / (n_points * substeps) as f64;
+
+    let mut z = Complex::new(0.0, 0.0);
+    let mut points = Vec::with_capacity(n_points + 1);
+    points.push((z.re, z.im));
+
+    for k in 1..=n_points * substeps {
+        let target = Complex::from_polar(1.0, k as f64 * dtheta);
+        for _ in 0..NEWTON_ITERS {
+            let g = stabfn(z) - target;
+            if g.norm() < NEWTON_TOL {
+                break;
+            }
+            let dg = (stabfn(z + FD_STEP) - stabfn(z)) / FD_STEP;
+            assert!(dg.norm() > 0.0, "stability function has zero derivative");
+            z -= g / dg;
+        }
+        if k.is_multiple_of(substeps) {
+            points.push((z.re, z.im));
+        }
+    }
+
+    // Close the curve exactly
+    points[n_points] = points[0];
+    points
+}
+
+/// Largest `dt` for which every `dt * λ`, `λ` an eigenvalue of the model Jacobian at
+/// `(t, state, control)`, lies in the stability region `|R(z)| <= 1` of `stabfn`. Found
+/// by bisection along the ray from the origin, so it assumes the region is star-shaped
+/// around 0 along each eigenvalue's direction (true for the explicit Runge-Kutta
+/// methods). Returns `f64::INFINITY` when no eigenvalue limits the step and 0 when no
+/// positive step is stable (e.g. an eigenvalue with positive real part).
+pub fn max_stable_dt<M, R>(
+    model: &M,
+    t: f64,
+    state: &M::State,
+    control: &M::Control,
+    stabfn: R,
+) -> f64
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+    R: Fn(Complex<f64>) -> Complex<f64>,
+{
+    let jac = model.jacobian(t, state, control);
+    if let Err(err) = check_jacobian_shape::<M>(&jac, state.to_dvector().len()) {
+        panic!("{err}");
+    }
+    let eigenvalues = jac.complex_eigenvalues();
+    let stable = |dt: f64| {
+        eigenvalues
+            .iter()
+            .all(|lambda| stabfn(lambda * dt).norm() <= 1.0 + STABLE_TOL)
+    };
+
+    let mut hi = 1.0;
+    while stable(hi) {
+        hi *= 2.0;
+        if hi > MAX_DT {
+            return f64::INFINITY;
+        }
+    }
+    let mut lo = 0.0;
+    for _ in 0..BISECTION_ITERS {
+        let mid = 0.5 * (lo + hi);
+        if stable(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// [`max_stable_dt`] for [`ForwardEuler`], whose region is the unit disc around -1:
+/// each stable eigenvalue allows `dt <= -2 Re(λ) / |λ|²`.
+pub fn max_stable_dt_forward_euler<M>(model: &M, state: &M::State, control: &M::Control) -> f64
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+{
+    max_stable_dt(model, 0.0, state, control, ForwardEuler::stability)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        solvers::BackwardEuler,
+        traits::{Dynamics, Stepper},
+        types::DroneInput,
+    };
+
+    fn max_radius_error(points: &[(f64, f64)], center: (f64, f64)) -> f64 {
+        points
+            .iter()
+            .map(|&(x, y)| ((x - center.0).hypot(y - center.1) - 1.0).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn forward_euler_boundary_is_unit_circle_at_minus_one() {
+        let points = stability_boundary(ForwardEuler::stability, 64);
+        assert_eq!(points.len(), 65);
+        assert_eq!(points[0], points[64]);
+        assert!(max_radius_error(&points, (-1.0, 0.0)) < 1e-8);
+        // Far side of the circle is reached half way round
+        assert!((points[32].0 + 2.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn backward_euler_boundary_is_unit_circle_at_plus_one() {
+        let points = stability_boundary(BackwardEuler::stability, 64);
+        assert!(max_radius_error(&points, (1.0, 0.0)) < 1e-8);
+        assert!((points[32].0 - 2.0).abs() < 1e-8);
+        // Outside that circle is stable
+        assert!(BackwardEuler::stability(Complex::new(-1.0, 0.0)).norm() < 1.0);
+    }
+
+    #[test]
+    fn forward_euler_limit_separates_bounded_from_divergent_steps() {
+        let drag = 0.5;
+        let model = SimpleQuadcopter::new(drag);
+        let control = model.input_to_control(&DroneInput::try_new(0.0, 0.1, 0.0).unwrap());
+        let state = SimpleQuadState::new(0.0, 0.0, 3.0, -1.0, 0.4);
+
+        // Only the drag poles -drag limit the step
+        let limit = max_stable_dt_forward_euler(&model, &state, &control);
+        assert!((limit - 2.0 / drag).abs() < 1e-6, "{limit}");
+
+        let peak_speed = |dt: f64| {
+            let mut s = state;
+            let mut peak: f64 = 0.0;
+            for _ in 0..500 {
+                s = ForwardEuler.step(&model, 0.0, &s, &control, dt);
+                peak = peak.max(s.v_north_mps.hypot(s.v_east_mps));
+            }
+            peak
+        };
+        assert!(peak_speed(0.95 * limit) < 10.0);
+        assert!(peak_speed(1.05 * limit) > 1e6);
+
+        // RK4's region reaches further along the negative real axis
+        let rk4_stability =
+            |z: Complex<f64>| 1.0 + z + z * z / 2.0 + z.powi(3) / 6.0 + z.powi(4) / 24.0;
+        let rk4 = max_stable_dt(&model, 0.0, &state, &control, rk4_stability);
+        assert!(rk4 > 1.3 * limit, "{rk4}");
+        assert_eq!(
+            max_stable_dt_forward_euler(&SimpleQuadcopter::new(0.0), &state, &control),
+            f64::INFINITY
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Opt-in structured tracing of integrations (feature `trace`), for debugging
+//! divergence without `println!`. A per-thread subscriber receives [`TraceEvent`]s:
+//! the start and end of each run of a fixed-step driver in [`predict`](crate::predict)
+//! (`predict`, `predict_on_grid`, `predict_backward`, `predict_sequence`, ...), every
+//! `step_stride`-th step, and a warning when Newton stops at `iter_max` without
+//! converging. With the feature off none of the hooks are compiled in.
+
+use std::{cell::RefCell, fmt::Debug, time::Duration};
+
+/// One structured trace record.
+#[derive(Debug)]
+pub enum TraceEvent<'a> {
+    /// A prediction is about to integrate `steps` steps of `dt` from `t0`; on an
+    /// explicit time grid `dt` is the first interval, and it is negative backward.
+    PredictStart { t0: f64, dt: f64, steps: usize },
+    /// State reached after step `step` (at time `t`), every `step_stride` steps.
+    Step {
+        step: usize,
+        t: f64,
+        state: &'a dyn Debug,
+    },
+    /// The prediction finished.
+    PredictEnd { steps: usize, cpu_time: Duration },
+    /// Newton used all `iter_max` iterations with the residual still at `residual`.
+    NewtonIterMax { iter_max: usize, residual: f64 },
+}
+
+/// Subscriber settings.
+#[derive(Debug, Clone, Copy)]
+pub struct TraceOpts {
+    /// Emit a [`TraceEvent::Step`] every this many steps (>= 1).
+    pub step_stride: usize,
+}
+
+impl Default for TraceOpts {
+    fn default() -> Self {
+        Self { step_stride: 100 }
+    }
+}
+
+type Subscriber = Box<dyn FnMut(&TraceEvent<'_>)>;
+
+thread_local! {
+    static SUBSCRIBER: RefCell<Option<(TraceOpts, Subscriber)>> = const { RefCell::new(None) };
+}
+
+/// Route this thread's trace events to `subscriber`, replacing any previous one. The
+/// subscriber must not itself run traced code (e.g. call `predict`).
+pub fn set_subscriber<F>(opts: TraceOpts, subscriber: F)
+where
+    F: FnMut(&TraceEvent<'_>) + 'static,
+{
+    assert!(opts.step_stride >= 1, "step_stride must be >= 1");
+    SUBSCRIBER.with(|s| *s.borrow_mut() = Some((opts, Box::new(subscriber))));
+}
+
+/// Stop tracing on this thread.
+pub fn clear_subscriber() {
+    SUBSCRIBER.with(|s| *s.borrow_mut() = None);
+}
+
+pub(crate) fn emit(event: TraceEvent<'_>) {
+    SUBSCRIBER.with(|s| {
+        if let Some((_, subscriber)) = s.borrow_mut().as_mut() {
+            subscriber(&event);
+        }
+    });
+}
+
+/// Whether step `step` falls on the subscriber's stride (false without a subscriber).
+pub(crate) fn wants_step(step: usize) -> bool {
+    SUBSCRIBER.with(|s| {
+        s.borrow()
+            .as_ref()
+            .is_some_and(|(opts, _)| step.is_multiple_of(opts.step_stride))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::{
+            predict, predict_backward, predict_budgeted, predict_endpoints, predict_on_grid,
+            predict_sequence,
+        },
+        solvers::{BackwardEuler, NewtonOpts, Rk4},
+        types::DroneInput,
+    };
+
+    #[test]
+    fn implicit_run_emits_span_stride_and_newton_warnings() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&log);
+        set_subscriber(TraceOpts { step_stride: 10 }, move |event| {
+            let record = match event {
+                TraceEvent::PredictStart { steps, .. } => format!("start {steps}"),
+                TraceEvent::Step { step, .. } => format!("step {step}"),
+                TraceEvent::PredictEnd { steps, .. } => format!("end {steps}"),
+                TraceEvent::NewtonIterMax { iter_max, .. } => format!("newton {iter_max}"),
+            };
+            sink.borrow_mut().push(record);
+        });
+
+        let input = DroneInput {
+            roll_rad: 0.1,
+            pitch_rad: 0.2,
+            yaw_rate_rps: 0.5,
+            thrust_norm: 0.5,
+        };
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 2.0, 1.0, 0.0);
+
+        predict(
+            &input,
+            start,
+            &model,
+            &mut BackwardEuler::default(),
+            0.0,
+            3.0,
+            30,
+        );
+        {
+            let log = log.borrow();
+            assert_eq!(log.first().map(String::as_str), Some("start 30"));
+            assert_eq!(log.last().map(String::as_str), Some("end 30"));
+            let steps: Vec<&str> = log
+                .iter()
+                .filter(|r| r.starts_with("step"))
+                .map(String::as_str)
+                .collect();
+            assert_eq!(steps, ["step 10", "step 20", "step 30"]);
+            assert!(!log.iter().any(|r| r.starts_with("newton")));
+        }
+
+        // A single Newton update cannot converge on the turning model
+        log.borrow_mut().clear();
+        let mut starved = BackwardEuler::new(NewtonOpts {
+            iter_max: 2,
+            min_error: 1e-14,
+            ..NewtonOpts::default()
+        });
+        predict(&input, start, &model, &mut starved, 0.0, 3.0, 30);
+        assert!(log.borrow().iter().any(|r| r == "newton 2"));
+
+        clear_subscriber();
+        log.borrow_mut().clear();
+        predict(
+            &input,
+            start,
+            &model,
+            &mut BackwardEuler::default(),
+            0.0,
+            3.0,
+            30,
+        );
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn every_driver_emits_its_span() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&log);
+        set_subscriber(TraceOpts { step_stride: 4 }, move |event| match event {
+            TraceEvent::PredictStart { t0, steps, .. } => {
+                sink.borrow_mut().push(format!("start {t0} {steps}"))
+            }
+            TraceEvent::Step { step, .. } => sink.borrow_mut().push(format!("step {step}")),
+            TraceEvent::PredictEnd { steps, .. } => sink.borrow_mut().push(format!("end {steps}")),
+            TraceEvent::NewtonIterMax { .. } => {}
+        });
+        let take = || std::mem::take(&mut *log.borrow_mut());
+
+        let input = DroneInput::try_new(0.1, 0.2, 0.3).unwrap();
+        let model = SimpleQuadcopter::new(0.1);
+        let start = SimpleQuadState::new(0.0, 0.0, 1.0, 0.0, 0.0);
+
+        predict_on_grid(&input, start, &model, &mut Rk4, &[1.0, 1.5, 2.5, 2.75]);
+        assert_eq!(take(), ["start 1 3", "end 3"]);
+
+        predict_backward(&input, start, &model, &mut Rk4, 2.0, 1.0, 8);
+        assert_eq!(take(), ["start 2 8", "step 4", "step 8", "end 8"]);
+
+        let schedule = [
+            (0.0, input),
+            (0.5, DroneInput::try_new(0.0, 0.0, 0.0).unwrap()),
+        ];
+        predict_sequence(&schedule, start, &model, &mut Rk4, 0.0, 1.0, 4);
+        assert_eq!(take(), ["start 0 4", "step 4", "end 4"]);
+
+        let budget = std::time::Duration::from_secs(60);
+        predict_budgeted(&input, start, &model, &mut Rk4, 0.0, 1.0, 4, budget);
+        assert_eq!(take(), ["start 0 4", "step 4", "end 4"]);
+
+        predict_endpoints(&[input, input], &start, &model, &mut Rk4, 1.0, 2);
+        assert_eq!(take(), ["start 0 2", "end 2", "start 0 2", "end 2"]);
+
+        clear_subscriber();
+    }
+}
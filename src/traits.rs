@@ -1,24 +1,65 @@
-use crate::types::{DroneInput, IntegrableState};
+use std::fmt::{self, Debug};
+
+use crate::types::{ControlChannels, DroneInput, IntegrableState, Saturation, StateVector};
 
 pub const GRAVITY: f64 = 9.81;
 
 /// Core interface a dynamic model must implement.
 /// All states are assumed to be expressed in the NED frame (North-East-Down).
 pub trait Dynamics {
-    type State: IntegrableState;
-    type Control: Clone;
+    type State: IntegrableState + Debug;
+    type Control: Clone + Debug;
 
     /// Map user input into model-specific control.
     fn input_to_control(&self, input: &DroneInput) -> Self::Control;
 
+    /// [`input_to_control`](Dynamics::input_to_control), also reporting which channels
+    /// were clamped to the model's limits. The default reports none; models that clamp
+    /// override it.
+    fn input_to_control_checked(&self, input: &DroneInput) -> (Self::Control, Saturation) {
+        (self.input_to_control(input), Saturation::default())
+    }
+
     /// Time derivative: dx/dt = f(t, x, u)
     fn derivative(&self, t: f64, state: &Self::State, control: &Self::Control) -> Self::State;
 
-    /// Optional validation hook for states.
-    fn validate_state(&self, _state: &Self::State) {}
+    /// Validation hook the `predict` drivers call before every step. The default
+    /// panics on a non-finite state, naming the field via
+    /// [`IntegrableState::non_finite_field`].
+    fn validate_state(&self, state: &Self::State) {
+        if let Some(field) = state.non_finite_field() {
+            panic!("{field} must be finite");
+        }
+    }
+
+    /// Optional post-step projection applied by the `predict` drivers after every
+    /// accepted step, for kinematic constraints that are not part of the dynamics.
+    fn project_state(&self, _state: &mut Self::State) {}
 
-    fn dfdx(&self, _state: &Self::State, _control: &Self::Control) -> Vec<Vec<f64>> {
-        unimplemented!("Analytical Jacobian not implemented for this model");
+    /// State Jacobian `∂f/∂x` at `t = 0` as rows. Defaults to central differences of
+    /// [`derivative`](Dynamics::derivative); models implementing
+    /// [`AutoDiffDynamics`](crate::autodiff::AutoDiffDynamics) can return the exact
+    /// `autodiff::jacobian` instead when the `autodiff` feature is on.
+    fn dfdx(&self, state: &Self::State, control: &Self::Control) -> Vec<Vec<f64>>
+    where
+        Self::State: StateVector,
+    {
+        const FD_STEP: f64 = 1e-6;
+        let x = state.to_dvector();
+
+        let mut columns = Vec::with_capacity(x.len());
+        for k in 0..x.len() {
+            let (mut hi, mut lo) = (x.clone(), x.clone());
+            let h = FD_STEP * x[k].abs().max(1.0);
+            hi[k] += h;
+            lo[k] -= h;
+            let f_hi = self.derivative(0.0, &Self::State::from_dvector(hi), control);
+            let f_lo = self.derivative(0.0, &Self::State::from_dvector(lo), control);
+            columns.push((f_hi.to_dvector() - f_lo.to_dvector()) / (2.0 * h));
+        }
+        (0..x.len())
+            .map(|i| columns.iter().map(|c| c[i]).collect())
+            .collect()
     }
 }
 
@@ -31,6 +72,96 @@ pub trait LinearizableDynamics: Dynamics {
         state: &Self::State,
         control: &Self::Control,
     ) -> nalgebra::DMatrix<f64>;
+
+    /// Jacobians at many points at once, element `k` being
+    /// `jacobian(t[k], &states[k], &controls[k])`. The default loops; override it when a
+    /// model can amortize per-call overhead across the batch.
+    fn jacobian_batch(
+        &self,
+        states: &[Self::State],
+        controls: &[Self::Control],
+        t: &[f64],
+    ) -> Vec<nalgebra::DMatrix<f64>> {
+        assert!(
+            states.len() == controls.len() && states.len() == t.len(),
+            "states, controls and t must have the same length"
+        );
+        states
+            .iter()
+            .zip(controls)
+            .zip(t)
+            .map(|((state, control), t)| self.jacobian(*t, state, control))
+            .collect()
+    }
+
+    /// `n × m` Jacobian `∂f/∂u` of the derivative with respect to the control channels
+    /// (in [`ControlChannels`] order). Defaults to central differences; override it
+    /// with the analytic form where available.
+    fn control_jacobian(
+        &self,
+        t: f64,
+        state: &Self::State,
+        control: &Self::Control,
+    ) -> nalgebra::DMatrix<f64>
+    where
+        Self::State: StateVector,
+        Self::Control: ControlChannels,
+    {
+        const FD_STEP: f64 = 1e-6;
+        let u = control.channels();
+        let n = state.to_dvector().len();
+
+        let mut j = nalgebra::DMatrix::zeros(n, u.len());
+        for k in 0..u.len() {
+            let (mut hi, mut lo) = (u.clone(), u.clone());
+            let h = FD_STEP * u[k].abs().max(1.0);
+            hi[k] += h;
+            lo[k] -= h;
+            let f_hi = self.derivative(t, state, &Self::Control::from_channels(&hi));
+            let f_lo = self.derivative(t, state, &Self::Control::from_channels(&lo));
+            j.set_column(k, &((f_hi.to_dvector() - f_lo.to_dvector()) / (2.0 * h)));
+        }
+        j
+    }
+}
+
+/// A [`LinearizableDynamics::jacobian`] whose shape does not match the state, reported
+/// by the implicit solvers with the offending model's type name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JacobianShapeError {
+    pub model: &'static str,
+    pub rows: usize,
+    pub cols: usize,
+    pub state_dim: usize,
+}
+
+impl fmt::Display for JacobianShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "model {} returned a {}×{} Jacobian but state dimension is {}",
+            self.model, self.rows, self.cols, self.state_dim
+        )
+    }
+}
+
+impl std::error::Error for JacobianShapeError {}
+
+/// Check that `jacobian`, as returned by model `M`, is `state_dim × state_dim`.
+pub fn check_jacobian_shape<M: ?Sized>(
+    jacobian: &nalgebra::DMatrix<f64>,
+    state_dim: usize,
+) -> Result<(), JacobianShapeError> {
+    let (rows, cols) = jacobian.shape();
+    if rows == state_dim && cols == state_dim {
+        return Ok(());
+    }
+    Err(JacobianShapeError {
+        model: std::any::type_name::<M>(),
+        rows,
+        cols,
+        state_dim,
+    })
 }
 
 pub trait Stepper<M: Dynamics> {
@@ -42,4 +173,47 @@ pub trait Stepper<M: Dynamics> {
         control: &M::Control,
         dt: f64,
     ) -> M::State;
+
+    /// Global convergence order `p`: the endpoint error scales as `dt^p`.
+    fn order(&self) -> usize;
+
+    /// Solver statistics of the most recent [`step`](Stepper::step). Explicit solvers
+    /// report the trivial default.
+    fn last_step_stats(&self) -> StepStats {
+        StepStats::default()
+    }
+}
+
+/// Per-step solver statistics, see [`Stepper::last_step_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepStats {
+    /// Newton updates applied (0 for explicit solvers).
+    pub newton_iters: usize,
+    /// Whether Newton reached `min_error` before `iter_max` (always true when explicit).
+    pub converged: bool,
+    /// Jacobians evaluated and factorized. Equals `newton_iters` for full Newton; fewer
+    /// when factors are reused, see [`JacobianReuse`](crate::solvers::JacobianReuse).
+    pub jacobian_evals: usize,
+}
+
+impl Default for StepStats {
+    fn default() -> Self {
+        Self {
+            newton_iters: 0,
+            converged: true,
+            jacobian_evals: 0,
+        }
+    }
+}
+
+/// Additive effect layered on top of a base model by
+/// [`Composed`](crate::dynamic_models::Composed), e.g. wind or ground effect.
+pub trait DisturbanceModel<M: Dynamics> {
+    /// Contribution added to `M::derivative` at `(t, state, control)`.
+    fn extra_derivative(&self, t: f64, state: &M::State, control: &M::Control) -> M::State;
+}
+
+/// Disturbances that can also provide the Jacobian of their contribution.
+pub trait LinearizableDisturbance<M: Dynamics>: DisturbanceModel<M> {
+    fn jacobian(&self, t: f64, state: &M::State, control: &M::Control) -> nalgebra::DMatrix<f64>;
 }
@@ -0,0 +1,268 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::{
+    dynamic_models::SimpleQuadcopter,
+    solvers::{NewtonOpts, NewtonWorkspace, newton_into},
+    traits::{Dynamics, LinearizableDynamics},
+    types::{DroneInput, StateVector},
+};
+
+/// Why [`trim`] found no equilibrium.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimError {
+    /// The Jacobian of the trimmed derivatives is singular at the guess, e.g. a
+    /// drag-free model whose velocity grows without bound under any tilt.
+    Singular,
+    /// Newton stopped with the trimmed derivatives still this large (max norm).
+    NotConverged { residual: f64 },
+    /// The model's workspace stops any steady motion at its walls.
+    Confined,
+}
+
+impl fmt::Display for TrimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Singular => write!(f, "no finite trim: the trim Jacobian is singular"),
+            Self::NotConverged { residual } => {
+                write!(f, "trim did not converge (residual {residual:e})")
+            }
+            Self::Confined => write!(f, "no steady motion: the workspace stops it at a wall"),
+        }
+    }
+}
+
+impl std::error::Error for TrimError {}
+
+/// Steady state under a constant `input`: solves `derivative(state, control)_k = 0` for
+/// the state components `k` in `components` (by [`StateVector`] index, typically the
+/// velocities), varying only those components, with Newton on the model Jacobian.
+/// The other components, such as position and yaw, are taken from `guess`.
+///
+/// The equilibrium is passed through [`Dynamics::project_state`], as the `predict`
+/// drivers do after every step, so a model with a kinematic clamp (e.g. a ground-speed
+/// limit) reports the clamped state it settles on rather than one it cannot reach.
+pub fn trim<M>(
+    model: &M,
+    input: &DroneInput,
+    guess: &M::State,
+    components: &[usize],
+) -> Result<M::State, TrimError>
+where
+    M: LinearizableDynamics,
+    M::State: StateVector,
+{
+    assert!(!components.is_empty(), "need at least one trim component");
+    let control = model.input_to_control(input);
+    let base = guess.to_dvector();
+    assert!(
+        components.iter().all(|&k| k < base.len()),
+        "trim component out of bounds for state of dim {}",
+        base.len()
+    );
+
+    let with = |z: &DVector<f64>| {
+        let mut x = base.clone();
+        for (&k, v) in components.iter().zip(z.iter()) {
+            x[k] = *v;
+        }
+        M::State::from_dvector(x)
+    };
+    let f = |z: &DVector<f64>, out: &mut DVector<f64>| {
+        let dx = model.derivative(0.0, &with(z), &control).to_dvector();
+        for (i, &k) in components.iter().enumerate() {
+            out[i] = dx[k];
+        }
+    };
+    let j = |z: &DVector<f64>, out: &mut DMatrix<f64>| {
+        let jac = model.jacobian(0.0, &with(z), &control);
+        for (r, &kr) in components.iter().enumerate() {
+            for (c, &kc) in components.iter().enumerate() {
+                out[(r, c)] = jac[(kr, kc)];
+            }
+        }
+    };
+
+    let mut z = DVector::from_iterator(components.len(), components.iter().map(|&k| base[k]));
+    let mut ws = NewtonWorkspace::new(z.len());
+    j(&z, &mut ws.jx);
+    if !ws.jx.clone().lu().is_invertible() {
+        return Err(TrimError::Singular);
+    }
+
+    let opts = NewtonOpts {
+        iter_max: 50,
+        ..NewtonOpts::default()
+    };
+    let stats = newton_into(f, j, &mut z, opts, &mut ws, None);
+    if !stats.converged {
+        f(&z, &mut ws.fx);
+        return Err(TrimError::NotConverged {
+            residual: ws.fx.amax(),
+        });
+    }
+    let mut state = with(&z);
+    model.project_state(&mut state);
+    Ok(state)
+}
+
+/// Equilibrium body-frame velocity reached under a constant input, see
+/// [`steady_state_gain`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SteadyVelocity {
+    pub forward_mps: f64,
+    pub right_mps: f64,
+    /// Positive down, so a climb is negative.
+    pub down_mps: f64,
+}
+
+/// DC gain of the [`SimpleQuadcopter`] from stick to velocity: the body-frame velocity
+/// its linear drag settles on under `input`, `accel / drag` per channel (e.g.
+/// `g tan(pitch) / drag` forward). At yaw 0 forward is North and right is East. The
+/// yaw rate is ignored, since a turning quad has no steady NED velocity; zero drag
+/// has no finite equilibrium.
+///
+/// A [`max_ground_speed_mps`](SimpleQuadcopter::max_ground_speed_mps) limit scales the
+/// horizontal velocity back onto it, which is where the clamped drivers settle since
+/// the drag equilibrium lies along the acceleration. A
+/// [`workspace`](SimpleQuadcopter::workspace) eventually stops any horizontal motion at
+/// a wall, so it is reported as [`TrimError::Confined`].
+pub fn steady_state_gain(
+    model: &SimpleQuadcopter,
+    input: &DroneInput,
+) -> Result<SteadyVelocity, TrimError> {
+    if model.drag == 0.0 {
+        return Err(TrimError::Singular);
+    }
+    let control = model.input_to_control(input);
+    let (mut forward_mps, mut right_mps) = (
+        control.ax_body_mps2 / model.drag,
+        control.ay_body_mps2 / model.drag,
+    );
+    let speed = forward_mps.hypot(right_mps);
+    if model.workspace.is_some() && speed > 0.0 {
+        return Err(TrimError::Confined);
+    }
+    if let Some(max) = model.max_ground_speed_mps
+        && speed > max
+    {
+        forward_mps *= max / speed;
+        right_mps *= max / speed;
+    }
+    Ok(SteadyVelocity {
+        forward_mps,
+        right_mps,
+        down_mps: control.az_down_mps2 / model.drag,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::SimpleQuadState,
+        predict::predict,
+        solvers::Rk4,
+        traits::GRAVITY,
+        types::{GroundVelocity, Workspace},
+    };
+
+    const VELOCITIES: [usize; 3] = [2, 3, 6];
+
+    #[test]
+    fn linear_drag_trim_matches_closed_form() {
+        let (drag, pitch, yaw) = (0.4, 0.2_f64, 0.6_f64);
+        let model = SimpleQuadcopter::new(drag);
+        let input = DroneInput::try_new(0.0, pitch, 0.0).unwrap();
+        let guess = SimpleQuadState::new(5.0, -3.0, 0.0, 0.0, yaw);
+
+        let state = trim(&model, &input, &guess, &VELOCITIES).unwrap();
+        let speed = GRAVITY * pitch.tan() / drag;
+        assert!((state.v_north_mps - speed * yaw.cos()).abs() < 1e-9);
+        assert!((state.v_east_mps - speed * yaw.sin()).abs() < 1e-9);
+        assert_eq!(
+            (state.north_m, state.east_m, state.yaw_rad),
+            (5.0, -3.0, yaw)
+        );
+    }
+
+    #[test]
+    fn drag_free_model_has_no_finite_trim() {
+        let input = DroneInput::try_new(0.0, 0.2, 0.0).unwrap();
+        let err = trim(
+            &SimpleQuadcopter::new(0.0),
+            &input,
+            &SimpleQuadState::zero(),
+            &VELOCITIES,
+        )
+        .unwrap_err();
+        assert_eq!(err, TrimError::Singular);
+        assert!(err.to_string().contains("singular"));
+    }
+
+    #[test]
+    fn doubling_drag_halves_the_steady_velocity() {
+        let input = DroneInput::try_new(0.05, 0.2, 0.0).unwrap();
+        let low = steady_state_gain(&SimpleQuadcopter::new(0.2), &input).unwrap();
+        let high = steady_state_gain(&SimpleQuadcopter::new(0.4), &input).unwrap();
+
+        assert!((low.forward_mps - GRAVITY * 0.2_f64.tan() / 0.2).abs() < 1e-12);
+        assert!((high.forward_mps - 0.5 * low.forward_mps).abs() < 1e-12);
+        assert!((high.right_mps - 0.5 * low.right_mps).abs() < 1e-12);
+        assert_eq!(low.down_mps, 0.0);
+
+        // Agrees with the Newton trim at yaw 0
+        let trimmed = trim(
+            &SimpleQuadcopter::new(0.2),
+            &input,
+            &SimpleQuadState::zero(),
+            &VELOCITIES,
+        )
+        .unwrap();
+        assert!((trimmed.v_north_mps - low.forward_mps).abs() < 1e-9);
+        assert!((trimmed.v_east_mps - low.right_mps).abs() < 1e-9);
+
+        assert_eq!(
+            steady_state_gain(&SimpleQuadcopter::new(0.0), &input),
+            Err(TrimError::Singular)
+        );
+    }
+
+    #[test]
+    fn speed_limit_caps_the_reported_equilibrium() {
+        let model = SimpleQuadcopter::new(0.2).with_max_ground_speed(5.0);
+        let input = DroneInput::try_new(0.1, 0.3, 0.0).unwrap();
+
+        let gain = steady_state_gain(&model, &input).unwrap();
+        let free = steady_state_gain(&SimpleQuadcopter::new(0.2), &input).unwrap();
+        assert!((gain.forward_mps.hypot(gain.right_mps) - 5.0).abs() < 1e-12);
+        assert!(
+            (gain.right_mps / gain.forward_mps - free.right_mps / free.forward_mps).abs() < 1e-12
+        );
+
+        // The clamped drivers settle on the same velocity
+        let settled = predict(
+            &input,
+            SimpleQuadState::zero(),
+            &model,
+            &mut Rk4,
+            0.0,
+            60.0,
+            600,
+        );
+        let end = settled.final_state();
+        assert!((end.v_north_mps - gain.forward_mps).abs() < 1e-6);
+        assert!((end.v_east_mps - gain.right_mps).abs() < 1e-6);
+
+        let trimmed = trim(&model, &input, &SimpleQuadState::zero(), &VELOCITIES).unwrap();
+        assert!((trimmed.ground_speed() - 5.0).abs() < 1e-9);
+        assert!((trimmed.v_north_mps - gain.forward_mps).abs() < 1e-9);
+
+        let caged =
+            SimpleQuadcopter::new(0.2).with_workspace(Workspace::new(-10.0, 10.0, -10.0, 10.0));
+        assert_eq!(steady_state_gain(&caged, &input), Err(TrimError::Confined));
+        let hover = DroneInput::try_new(0.0, 0.0, 0.0).unwrap();
+        assert!(steady_state_gain(&caged, &hover).is_ok());
+    }
+}
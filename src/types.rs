@@ -1,16 +1,365 @@
-/// Pilot stick inputs expressed in radians and rad/s.
+use std::fmt;
+
+/// Pilot stick inputs expressed in radians and rad/s, plus normalized collective thrust.
 /// Body frame is x-forward, y-right, z-down. Positive yaw is clockwise when viewed from above.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DroneInput {
     pub roll_rad: f64,     // radians, right wing down is positive
-    pub pitch_rad: f64,    // radians, nose up is positive
+    pub pitch_rad: f64,    // radians, pitch forward (nose down) is positive
     pub yaw_rate_rps: f64, // radians per second, clockwise is positive
+    pub thrust_norm: f64,  // throttle stick in [0, 1], 0.5 holds altitude, higher climbs
+}
+
+/// Magnitude bounds checked by [`DroneInput::try_new_bounded`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputLimits {
+    /// Maximum absolute roll and pitch, in radians.
+    pub max_tilt_rad: f64,
+    /// Maximum absolute yaw rate, in rad/s.
+    pub max_yaw_rate_rps: f64,
+}
+
+/// Input channels a model clamped when converting a command, see
+/// [`Dynamics::input_to_control_checked`](crate::traits::Dynamics::input_to_control_checked).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Saturation {
+    pub roll: bool,
+    pub pitch: bool,
+    pub yaw_rate: bool,
+    pub thrust: bool,
+}
+
+impl Saturation {
+    pub fn any(&self) -> bool {
+        self.roll || self.pitch || self.yaw_rate || self.thrust
+    }
+
+    /// Channels saturated in either `self` or `other`, e.g. over a command schedule.
+    pub fn union(self, other: Saturation) -> Saturation {
+        Saturation {
+            roll: self.roll || other.roll,
+            pitch: self.pitch || other.pitch,
+            yaw_rate: self.yaw_rate || other.yaw_rate,
+            thrust: self.thrust || other.thrust,
+        }
+    }
+}
+
+/// Stick center deadband, per channel: displacements within the half-width produce no
+/// command, and the rest of the stick travel is stretched back to full scale so the
+/// response is continuous at the edge. Throttle is centered on hover (0.5).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deadband {
+    pub roll_rad: f64,
+    pub pitch_rad: f64,
+    pub yaw_rate_rps: f64,
+    pub thrust_norm: f64,
+    /// Full-scale stick deflection each channel is rescaled to.
+    pub full_scale: InputLimits,
+}
+
+impl Deadband {
+    /// Input after the deadband: `sign(x) (|x| - d) F / (F - d)` outside the band,
+    /// with `d` the half-width and `F` the full scale of the channel.
+    pub fn apply(&self, input: &DroneInput) -> DroneInput {
+        let band = |x: f64, d: f64, full: f64| {
+            assert!(
+                (0.0..full).contains(&d),
+                "deadband must be >= 0 and below full scale"
+            );
+            if x.abs() <= d {
+                0.0
+            } else {
+                (x.abs() - d).copysign(x) * full / (full - d)
+            }
+        };
+        let tilt = self.full_scale.max_tilt_rad;
+        DroneInput {
+            roll_rad: band(input.roll_rad, self.roll_rad, tilt),
+            pitch_rad: band(input.pitch_rad, self.pitch_rad, tilt),
+            yaw_rate_rps: band(
+                input.yaw_rate_rps,
+                self.yaw_rate_rps,
+                self.full_scale.max_yaw_rate_rps,
+            ),
+            thrust_norm: 0.5 + band(input.thrust_norm - 0.5, self.thrust_norm, 0.5),
+        }
+    }
+}
+
+/// Rectangular North/East arena [m], e.g. a test cage, used by
+/// [`SimpleQuadcopter::with_workspace`](crate::dynamic_models::SimpleQuadcopter::with_workspace).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Workspace {
+    pub north_min: f64,
+    pub north_max: f64,
+    pub east_min: f64,
+    pub east_max: f64,
+}
+
+impl Workspace {
+    pub fn new(north_min: f64, north_max: f64, east_min: f64, east_max: f64) -> Self {
+        assert!(
+            [north_min, north_max, east_min, east_max]
+                .iter()
+                .all(|v| v.is_finite()),
+            "workspace bounds must be finite"
+        );
+        assert!(
+            north_min < north_max && east_min < east_max,
+            "workspace bounds must satisfy min < max"
+        );
+        Self {
+            north_min,
+            north_max,
+            east_min,
+            east_max,
+        }
+    }
+
+    pub fn contains(&self, (north, east): (f64, f64)) -> bool {
+        (self.north_min..=self.north_max).contains(&north)
+            && (self.east_min..=self.east_max).contains(&east)
+    }
+
+    /// Clamp a position into the box and zero the velocity components pushing out
+    /// through a wall it touches, leaving motion along the wall. Returns the new
+    /// `(north, east, v_north, v_east)`.
+    pub fn confine(
+        &self,
+        north: f64,
+        east: f64,
+        v_north: f64,
+        v_east: f64,
+    ) -> (f64, f64, f64, f64) {
+        let axis = |x: f64, v: f64, lo: f64, hi: f64| {
+            if x <= lo {
+                (lo, v.max(0.0))
+            } else if x >= hi {
+                (hi, v.min(0.0))
+            } else {
+                (x, v)
+            }
+        };
+        let (n, vn) = axis(north, v_north, self.north_min, self.north_max);
+        let (e, ve) = axis(east, v_east, self.east_min, self.east_max);
+        (n, e, vn, ve)
+    }
+}
+
+/// Why a [`DroneInput`] was rejected at construction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputError {
+    /// The channel value is NaN or infinite.
+    NonFinite { channel: &'static str, value: f64 },
+    /// The channel magnitude exceeds the supplied limit.
+    OutOfRange {
+        channel: &'static str,
+        value: f64,
+        limit: f64,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite { channel, value } => {
+                write!(f, "{channel} must be finite, got {value}")
+            }
+            Self::OutOfRange {
+                channel,
+                value,
+                limit,
+            } => write!(f, "{channel} = {value} exceeds limit ±{limit}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl DroneInput {
+    /// Validated constructor: rejects NaN and infinite channels. Throttle is set to hover.
+    pub fn try_new(roll_rad: f64, pitch_rad: f64, yaw_rate_rps: f64) -> Result<Self, InputError> {
+        for (channel, value) in [
+            ("roll_rad", roll_rad),
+            ("pitch_rad", pitch_rad),
+            ("yaw_rate_rps", yaw_rate_rps),
+        ] {
+            if !value.is_finite() {
+                return Err(InputError::NonFinite { channel, value });
+            }
+        }
+        Ok(Self {
+            roll_rad,
+            pitch_rad,
+            yaw_rate_rps,
+            thrust_norm: 0.5,
+        })
+    }
+
+    /// Like [`DroneInput::try_new`], additionally rejecting channels beyond `limits`.
+    pub fn try_new_bounded(
+        roll_rad: f64,
+        pitch_rad: f64,
+        yaw_rate_rps: f64,
+        limits: &InputLimits,
+    ) -> Result<Self, InputError> {
+        let input = Self::try_new(roll_rad, pitch_rad, yaw_rate_rps)?;
+        for (channel, value, limit) in [
+            ("roll_rad", roll_rad, limits.max_tilt_rad),
+            ("pitch_rad", pitch_rad, limits.max_tilt_rad),
+            ("yaw_rate_rps", yaw_rate_rps, limits.max_yaw_rate_rps),
+        ] {
+            if value.abs() > limit {
+                return Err(InputError::OutOfRange {
+                    channel,
+                    value,
+                    limit,
+                });
+            }
+        }
+        Ok(input)
+    }
+
+    /// Channel-wise linear interpolation: `s = 0` gives `self`, `s = 1` gives `other`.
+    pub fn lerp(&self, other: &DroneInput, s: f64) -> DroneInput {
+        DroneInput {
+            roll_rad: self.roll_rad + s * (other.roll_rad - self.roll_rad),
+            pitch_rad: self.pitch_rad + s * (other.pitch_rad - self.pitch_rad),
+            yaw_rate_rps: self.yaw_rate_rps + s * (other.yaw_rate_rps - self.yaw_rate_rps),
+            thrust_norm: self.thrust_norm + s * (other.thrust_norm - self.thrust_norm),
+        }
+    }
+}
+
+/// Maximum stick slew per channel, used by [`RateLimiter`]. All rates are magnitudes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlewRates {
+    /// Roll and pitch stick rate, in rad/s.
+    pub tilt_rps: f64,
+    /// Yaw-rate stick rate, in rad/s².
+    pub yaw_rate_rps2: f64,
+    /// Throttle stick rate, in normalized units per second.
+    pub thrust_per_s: f64,
+}
+
+/// Slew-rate limiter for pilot commands: each channel moves toward the target by at
+/// most its [`SlewRates`] bound per second. This limits the command itself, unlike
+/// actuator lag in a model, which shapes the response to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimiter {
+    pub rates: SlewRates,
+    previous: DroneInput,
+}
+
+impl RateLimiter {
+    /// Start from `previous`, the stick position before the first limited command.
+    pub fn new(previous: DroneInput, rates: SlewRates) -> Self {
+        assert!(
+            [rates.tilt_rps, rates.yaw_rate_rps2, rates.thrust_per_s]
+                .iter()
+                .all(|r| *r >= 0.0),
+            "slew rates must be >= 0"
+        );
+        Self { rates, previous }
+    }
+
+    /// Most recent limited command.
+    pub fn previous(&self) -> DroneInput {
+        self.previous
+    }
+
+    /// Move the command toward `target` over `dt` seconds and return the limited input.
+    pub fn apply(&mut self, target: &DroneInput, dt: f64) -> DroneInput {
+        assert!(dt.is_finite() && dt >= 0.0, "dt must be finite and >= 0");
+        let slew = |from: f64, to: f64, rate: f64| {
+            let max_step = rate * dt;
+            if (to - from).abs() <= max_step {
+                to
+            } else {
+                from + max_step.copysign(to - from)
+            }
+        };
+        let p = self.previous;
+        self.previous = DroneInput {
+            roll_rad: slew(p.roll_rad, target.roll_rad, self.rates.tilt_rps),
+            pitch_rad: slew(p.pitch_rad, target.pitch_rad, self.rates.tilt_rps),
+            yaw_rate_rps: slew(
+                p.yaw_rate_rps,
+                target.yaw_rate_rps,
+                self.rates.yaw_rate_rps2,
+            ),
+            thrust_norm: slew(p.thrust_norm, target.thrust_norm, self.rates.thrust_per_s),
+        };
+        self.previous
+    }
+}
+
+/// First-order low-pass filter of a time-stamped stick schedule `(time, input)`, sorted
+/// by time, applied per channel with corner frequency `cutoff_hz`. Removes telemetry
+/// jitter before prediction; unlike actuator lag it shapes the command, not the
+/// vehicle response. Handles uneven sample spacing; the first entry passes unchanged.
+pub fn smooth_inputs(schedule: &[(f64, DroneInput)], cutoff_hz: f64) -> Vec<(f64, DroneInput)> {
+    assert!(
+        cutoff_hz.is_finite() && cutoff_hz > 0.0,
+        "cutoff_hz must be finite and > 0"
+    );
+    assert!(
+        schedule.windows(2).all(|w| w[0].0 <= w[1].0),
+        "schedule must be sorted by time"
+    );
+
+    let omega = 2.0 * std::f64::consts::PI * cutoff_hz;
+    let mut smoothed = Vec::with_capacity(schedule.len());
+    let mut previous: Option<(f64, DroneInput)> = None;
+    for &(t, raw) in schedule {
+        let filtered = match previous {
+            Some((t_prev, y)) => y.lerp(&raw, 1.0 - (-omega * (t - t_prev)).exp()),
+            None => raw,
+        };
+        smoothed.push((t, filtered));
+        previous = Some((t, filtered));
+    }
+    smoothed
 }
 
 /// Minimal trait for states that can be integrated with time-marching methods.
 /// The derivative has the same shape as the state.
 pub trait IntegrableState: Clone {
     fn add_scaled(&self, derivative: &Self, scale: f64) -> Self;
+
+    /// In-place `self += scale * derivative`. Override for heap-backed states to avoid
+    /// allocating a fresh state per call.
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        *self = self.add_scaled(derivative, scale);
+    }
+
+    /// Name of the first NaN or infinite component, if any. Used for diagnostics;
+    /// the default reports nothing.
+    fn non_finite_field(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether every component is finite. Defaults to [`IntegrableState::non_finite_field`]
+    /// finding nothing, so states only list their fields once.
+    fn is_finite(&self) -> bool {
+        self.non_finite_field().is_none()
+    }
+}
+
+/// Type-erased states, e.g. of [`AnyModel`](crate::dynamic_models::AnyModel).
+impl IntegrableState for nalgebra::DVector<f64> {
+    fn add_scaled(&self, derivative: &Self, scale: f64) -> Self {
+        self + derivative * scale
+    }
+
+    fn add_scaled_assign(&mut self, derivative: &Self, scale: f64) {
+        self.axpy(scale, derivative, 1.0);
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        self.iter().any(|v| !v.is_finite()).then_some("component")
+    }
 }
 
 /// Optional helper for anything that can be projected into a 2D plot.
@@ -18,8 +367,188 @@ pub trait Position2D {
     fn position(&self) -> (f64, f64);
 }
 
+/// States that expose their NED ground velocity as `(v_north, v_east)` in m/s.
+pub trait GroundVelocity {
+    fn ground_velocity(&self) -> (f64, f64);
+
+    /// Magnitude of the ground velocity [m/s].
+    fn ground_speed(&self) -> f64 {
+        let (vn, ve) = self.ground_velocity();
+        vn.hypot(ve)
+    }
+
+    /// Horizontal kinetic energy per unit mass, `½(v_north² + v_east²)` [J/kg]. Never
+    /// increases under drag with no input, so growth flags integrator instability.
+    fn kinetic_energy(&self) -> f64 {
+        let (vn, ve) = self.ground_velocity();
+        0.5 * (vn * vn + ve * ve)
+    }
+
+    /// Course over ground [rad]: `atan2(v_east, v_north)`, 0 = North, positive clockwise.
+    /// Differs from yaw whenever the vehicle drifts sideways.
+    fn ground_track_rad(&self) -> f64 {
+        let (vn, ve) = self.ground_velocity();
+        ve.atan2(vn)
+    }
+}
+
+/// States that carry the nose direction, e.g. for rotating NED quantities into the
+/// body frame.
+pub trait Heading {
+    /// Yaw [rad]: 0 = North, positive clockwise.
+    fn yaw_rad(&self) -> f64;
+
+    /// Copy of the state with the yaw replaced.
+    fn with_yaw(&self, yaw_rad: f64) -> Self;
+}
+
+/// States split into positions and the rates that drive them, where the position
+/// derivative is the velocity held in the state itself (kinematic coupling). Used by
+/// [`SemiImplicitEuler`](crate::solvers::SemiImplicitEuler).
+pub trait SecondOrderState: IntegrableState {
+    /// Indices of the positions in the [`StateVector`] layout, paired element-wise
+    /// with [`SecondOrderState::velocity_indices`]: the derivative of position
+    /// `position_indices()[k]` is the state's `velocity_indices()[k]` component.
+    fn position_indices() -> &'static [usize];
+
+    /// Indices of the velocities driving each of the
+    /// [`SecondOrderState::position_indices`].
+    fn velocity_indices() -> &'static [usize];
+
+    /// `self + scale * derivative` on every component except the positions, which are
+    /// left unchanged.
+    fn add_scaled_rates(&self, derivative: &Self, scale: f64) -> Self;
+
+    /// Positions advanced by `scale` times the velocities of `self`; everything else is
+    /// left unchanged.
+    fn advance_positions(&self, scale: f64) -> Self;
+}
+
 /// States that can be viewed as dense vectors for matrix-based solvers/analysis.
 pub trait StateVector: IntegrableState {
     fn to_dvector(&self) -> nalgebra::DVector<f64>;
     fn from_dvector(v: nalgebra::DVector<f64>) -> Self;
 }
+
+impl StateVector for nalgebra::DVector<f64> {
+    fn to_dvector(&self) -> nalgebra::DVector<f64> {
+        self.clone()
+    }
+
+    fn from_dvector(v: nalgebra::DVector<f64>) -> Self {
+        v
+    }
+}
+
+/// Controls whose scalar channels can be listed, e.g. for plotting or logging.
+pub trait ControlChannels {
+    /// Human-readable channel names, in the same order as [`ControlChannels::channels`].
+    fn channel_names() -> &'static [&'static str];
+    fn channels(&self) -> Vec<f64>;
+    /// Inverse of [`ControlChannels::channels`].
+    fn from_channels(values: &[f64]) -> Self;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: InputLimits = InputLimits {
+        max_tilt_rad: 0.5,
+        max_yaw_rate_rps: 1.0,
+    };
+
+    #[test]
+    fn try_new_rejects_nan() {
+        let err = DroneInput::try_new(0.1, f64::NAN, 0.0).unwrap_err();
+        assert!(matches!(
+            err,
+            InputError::NonFinite {
+                channel: "pitch_rad",
+                ..
+            }
+        ));
+        assert!(DroneInput::try_new(0.0, 0.0, f64::INFINITY).is_err());
+        assert_eq!(
+            DroneInput::try_new(0.1, 0.2, 0.3),
+            Ok(DroneInput {
+                roll_rad: 0.1,
+                pitch_rad: 0.2,
+                yaw_rate_rps: 0.3,
+                thrust_norm: 0.5,
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_bounded_rejects_over_limit_pitch() {
+        let err = DroneInput::try_new_bounded(0.1, 0.8, 0.0, &LIMITS).unwrap_err();
+        assert_eq!(
+            err,
+            InputError::OutOfRange {
+                channel: "pitch_rad",
+                value: 0.8,
+                limit: 0.5,
+            }
+        );
+        assert!(err.to_string().contains("pitch_rad"));
+
+        assert!(DroneInput::try_new_bounded(-0.5, 0.5, -1.0, &LIMITS).is_ok());
+        assert!(matches!(
+            DroneInput::try_new_bounded(f64::NAN, 0.0, 0.0, &LIMITS),
+            Err(InputError::NonFinite { .. })
+        ));
+    }
+
+    #[test]
+    fn smoothing_reduces_noise_and_keeps_the_mean() {
+        let pitch = 0.2;
+        let noisy: Vec<(f64, DroneInput)> = (0..2000)
+            .map(|k| {
+                let noise = 0.05 * (k as f64 * 2.3).sin();
+                let input = DroneInput::try_new(0.0, pitch + noise, 0.0).unwrap();
+                (k as f64 * 0.01, input)
+            })
+            .collect();
+
+        let stats = |schedule: &[(f64, DroneInput)]| {
+            let tail: Vec<f64> = schedule[500..].iter().map(|(_, u)| u.pitch_rad).collect();
+            let mean = tail.iter().sum::<f64>() / tail.len() as f64;
+            let var = tail.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / tail.len() as f64;
+            (mean, var)
+        };
+
+        let smoothed = smooth_inputs(&noisy, 1.0);
+        assert_eq!(smoothed.len(), noisy.len());
+        assert_eq!(smoothed[0], noisy[0]);
+        let (raw_mean, raw_var) = stats(&noisy);
+        let (mean, var) = stats(&smoothed);
+        assert!(var < 0.05 * raw_var, "{var} vs {raw_var}");
+        assert!((mean - raw_mean).abs() < 2e-3, "{mean} vs {raw_mean}");
+        assert!(smoothed.iter().all(|(_, u)| u.thrust_norm == 0.5));
+    }
+
+    #[test]
+    fn rate_limiter_spreads_a_step_over_several_steps() {
+        let hover = DroneInput::try_new(0.0, 0.0, 0.0).unwrap();
+        let rates = SlewRates {
+            tilt_rps: 0.5,
+            yaw_rate_rps2: 1.0,
+            thrust_per_s: 1.0,
+        };
+        let mut limiter = RateLimiter::new(hover, rates);
+        let target = DroneInput::try_new(0.3, -0.1, 0.0).unwrap();
+
+        let rolls: Vec<f64> = (0..8)
+            .map(|_| limiter.apply(&target, 0.1).roll_rad)
+            .collect();
+        for (k, roll) in rolls.iter().take(6).enumerate() {
+            assert!(
+                (roll - 0.05 * (k + 1) as f64).abs() < 1e-12,
+                "step {k}: {roll}"
+            );
+        }
+        assert_eq!(rolls[6], 0.3);
+        assert_eq!(limiter.previous(), target);
+    }
+}
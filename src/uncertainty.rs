@@ -0,0 +1,713 @@
+use std::{fs, io, path::Path};
+
+use nalgebra::{DMatrix, DVector, Matrix2, Vector2};
+
+use crate::{
+    linear::discretize,
+    predict::{HorizonConfig, Prediction, predict, predict_cfg},
+    traits::{Dynamics, LinearizableDynamics, Stepper},
+    types::{DroneInput, Position2D, StateVector},
+};
+
+/// Scaling parameters of the unscented transform.
+#[derive(Clone, Copy, Debug)]
+pub struct UnscentedOpts {
+    /// Spread of the sigma points around the mean.
+    pub alpha: f64,
+    /// Prior knowledge of the distribution (2 is optimal for Gaussians).
+    pub beta: f64,
+    /// Secondary scaling parameter.
+    pub kappa: f64,
+}
+
+impl Default for UnscentedOpts {
+    fn default() -> Self {
+        Self {
+            alpha: 1.0,
+            beta: 2.0,
+            kappa: 0.0,
+        }
+    }
+}
+
+/// Propagate a Gaussian state covariance along a prediction through the linearized
+/// dynamics: `P_{k+1} = A_d P_k A_d^T + Q`, with `A_d` the discretized Jacobian at
+/// state `k` and `Q` the per-step process noise. Returns one covariance per state.
+pub fn propagate_covariance<M>(
+    model: &M,
+    prediction: &Prediction<M::State, M::Control>,
+    p0: DMatrix<f64>,
+    q: DMatrix<f64>,
+) -> Vec<DMatrix<f64>>
+where
+    M: LinearizableDynamics,
+{
+    assert!(p0.is_square(), "p0 must be square");
+    assert!(q.shape() == p0.shape(), "q must have the same shape as p0");
+
+    let n = prediction.n();
+    let times: Vec<f64> = (0..n).map(|i| prediction.t_at(i)).collect();
+    let controls: Vec<_> = times
+        .iter()
+        .map(|t| prediction.control_at(*t).clone())
+        .collect();
+    let jacobians = model.jacobian_batch(&prediction.states[..n], &controls, &times);
+
+    let mut covariances = Vec::with_capacity(prediction.states.len());
+    let mut p = p0;
+
+    for (i, j) in jacobians.iter().enumerate() {
+        assert!(
+            j.shape() == p.shape(),
+            "jacobian must be square with dimension matching the covariance"
+        );
+
+        let a_d = discretize(j, prediction.t_at(i + 1) - prediction.t_at(i));
+        let p_next = &a_d * &p * a_d.transpose() + &q;
+        covariances.push(std::mem::replace(&mut p, p_next));
+    }
+    covariances.push(p);
+
+    covariances
+}
+
+/// Mean trajectory and per-state covariance produced by [`predict_unscented`].
+#[derive(Debug, Clone)]
+pub struct UnscentedPrediction<S, U> {
+    /// Prediction of the central sigma point with `states` replaced by the weighted mean.
+    pub mean: Prediction<S, U>,
+    pub covariances: Vec<DMatrix<f64>>,
+}
+
+/// Unscented (sigma-point) prediction: sigma points drawn around `initial_state` with
+/// covariance `p0` are each propagated with [`predict`] and recombined per step. Unlike
+/// [`propagate_covariance`] this captures the `cos/sin(yaw)` nonlinearity.
+///
+/// Returns the prediction of the central sigma point with its `states` replaced by the
+/// weighted mean trajectory, and one covariance per state.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_unscented<M, St>(
+    nominal_input: &DroneInput,
+    initial_state: M::State,
+    p0: &DMatrix<f64>,
+    opts: UnscentedOpts,
+    model: &M,
+    solver: &mut St,
+    t0: f64,
+    t_final: f64,
+    steps: usize,
+) -> UnscentedPrediction<M::State, M::Control>
+where
+    M: Dynamics,
+    M::State: StateVector,
+    St: Stepper<M>,
+{
+    let x0 = initial_state.to_dvector();
+    let n = x0.len();
+    assert!(n > 0, "state dimension must be > 0");
+    assert!(
+        p0.nrows() == n && p0.ncols() == n,
+        "p0 must be square with dimension matching the state"
+    );
+
+    let n_f = n as f64;
+    let lambda = opts.alpha * opts.alpha * (n_f + opts.kappa) - n_f;
+    assert!(n_f + lambda > 0.0, "n + lambda must be > 0");
+
+    let w_mean_0 = lambda / (n_f + lambda);
+    let w_cov_0 = w_mean_0 + (1.0 - opts.alpha * opts.alpha + opts.beta);
+    let w_i = 1.0 / (2.0 * (n_f + lambda));
+
+    // Symmetric square root via eigendecomposition, tolerating singular p0
+    let eig = (p0 * (n_f + lambda)).symmetric_eigen();
+    let sqrt_diag = eig.eigenvalues.map(|l| l.max(0.0).sqrt());
+    let root =
+        &eig.eigenvectors * DMatrix::from_diagonal(&sqrt_diag) * eig.eigenvectors.transpose();
+
+    let mut sigma_points = Vec::with_capacity(2 * n + 1);
+    sigma_points.push(x0.clone());
+    for k in 0..n {
+        sigma_points.push(&x0 + root.column(k));
+        sigma_points.push(&x0 - root.column(k));
+    }
+
+    let runs: Vec<Vec<DVector<f64>>> = sigma_points
+        .into_iter()
+        .map(|x| {
+            predict(
+                nominal_input,
+                M::State::from_dvector(x),
+                model,
+                solver,
+                t0,
+                t_final,
+                steps,
+            )
+            .states
+            .iter()
+            .map(StateVector::to_dvector)
+            .collect()
+        })
+        .collect();
+
+    let mut nominal = predict(
+        nominal_input,
+        initial_state,
+        model,
+        solver,
+        t0,
+        t_final,
+        steps,
+    );
+
+    let weight = |j: usize, w0: f64| if j == 0 { w0 } else { w_i };
+    let mut covariances = Vec::with_capacity(steps + 1);
+    for (k, state) in nominal.states.iter_mut().enumerate() {
+        let mean = runs
+            .iter()
+            .enumerate()
+            .fold(DVector::zeros(n), |acc, (j, run)| {
+                acc + &run[k] * weight(j, w_mean_0)
+            });
+        let cov = runs
+            .iter()
+            .enumerate()
+            .fold(DMatrix::zeros(n, n), |acc, (j, run)| {
+                let d = &run[k] - &mean;
+                acc + &d * d.transpose() * weight(j, w_cov_0)
+            });
+
+        *state = M::State::from_dvector(mean);
+        covariances.push(cov);
+    }
+
+    UnscentedPrediction {
+        mean: nominal,
+        covariances,
+    }
+}
+
+/// Points on the `n_sigma` confidence ellipse of the north/east block (rows/cols 0 and 1)
+/// of `covariance`, centered on `center`. The contour is closed (first point repeated).
+pub fn confidence_ellipse(
+    center: (f64, f64),
+    covariance: &DMatrix<f64>,
+    n_sigma: f64,
+    n_points: usize,
+) -> Vec<(f64, f64)> {
+    assert!(
+        covariance.nrows() >= 2 && covariance.ncols() >= 2,
+        "covariance must be at least 2x2"
+    );
+    assert!(n_points >= 3, "n_points must be >= 3");
+
+    let block = covariance.fixed_view::<2, 2>(0, 0).into_owned();
+    let eig = block.symmetric_eigen();
+
+    // Semi-axes along the eigenvectors; clamp tiny negative eigenvalues from round-off
+    let r0 = n_sigma * eig.eigenvalues[0].max(0.0).sqrt();
+    let r1 = n_sigma * eig.eigenvalues[1].max(0.0).sqrt();
+    let v0 = eig.eigenvectors.column(0);
+    let v1 = eig.eigenvectors.column(1);
+
+    (0..=n_points)
+        .map(|k| {
+            let theta = std::f64::consts::TAU * (k % n_points) as f64 / n_points as f64;
+            let (s, c) = theta.sin_cos();
+            (
+                center.0 + r0 * c * v0[0] + r1 * s * v1[0],
+                center.1 + r0 * c * v0[1] + r1 * s * v1[1],
+            )
+        })
+        .collect()
+}
+
+/// Smallest extent [m] per axis of an [`endpoint_histogram`].
+const MIN_HISTOGRAM_EXTENT_M: f64 = 1.0;
+
+/// 2D histogram of where an ensemble of predictions ends up, see [`endpoint_histogram`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointHistogram {
+    /// `counts[i][j]` holds the endpoints in north bin `i` and east bin `j`.
+    pub counts: Vec<Vec<u32>>,
+    /// `bins_n + 1` ascending bin edges [m].
+    pub north_edges: Vec<f64>,
+    /// `bins_e + 1` ascending bin edges [m].
+    pub east_edges: Vec<f64>,
+}
+
+/// Bin the final XY positions of `predictions` into a `bins_n × bins_e` grid. The extent
+/// is computed automatically to cover every endpoint; an axis spanning less than 1 m is
+/// widened to 1 m around its centre, so a tight ensemble lands in the middle bin
+/// (for an odd bin count) instead of being spread across the whole grid.
+pub fn endpoint_histogram<S: Position2D, U>(
+    predictions: &[Prediction<S, U>],
+    bins_n: usize,
+    bins_e: usize,
+) -> EndpointHistogram {
+    assert!(bins_n > 0 && bins_e > 0, "need at least one bin per axis");
+    assert!(!predictions.is_empty(), "need at least one prediction");
+
+    let mut n_range = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut e_range = (f64::INFINITY, f64::NEG_INFINITY);
+    for p in predictions {
+        let (n, e) = p.final_state().position();
+        n_range = (n_range.0.min(n), n_range.1.max(n));
+        e_range = (e_range.0.min(e), e_range.1.max(e));
+    }
+
+    let edges = |(lo, hi): (f64, f64), bins: usize| -> Vec<f64> {
+        let (lo, hi) = if hi - lo >= MIN_HISTOGRAM_EXTENT_M {
+            (lo, hi)
+        } else {
+            let center = 0.5 * (lo + hi);
+            let half = 0.5 * MIN_HISTOGRAM_EXTENT_M;
+            (center - half, center + half)
+        };
+        (0..=bins)
+            .map(|k| lo + (hi - lo) * k as f64 / bins as f64)
+            .collect()
+    };
+    let north_edges = edges(n_range, bins_n);
+    let east_edges = edges(e_range, bins_e);
+
+    // Index of the bin containing `x`; the upper edge belongs to the last bin
+    let bin = |edges: &[f64], x: f64| -> usize {
+        let (lo, hi) = (edges[0], edges[edges.len() - 1]);
+        let bins = edges.len() - 1;
+        (((x - lo) / (hi - lo) * bins as f64) as usize).min(bins - 1)
+    };
+
+    let mut counts = vec![vec![0u32; bins_e]; bins_n];
+    for p in predictions {
+        let (n, e) = p.final_state().position();
+        counts[bin(&north_edges, n)][bin(&east_edges, e)] += 1;
+    }
+
+    EndpointHistogram {
+        counts,
+        north_edges,
+        east_edges,
+    }
+}
+
+/// Final XY position `(north, east)` [m] of every prediction in the ensemble, in order.
+pub fn ensemble_endpoints<S: Position2D, U>(predictions: &[Prediction<S, U>]) -> Vec<(f64, f64)> {
+    predictions
+        .iter()
+        .map(|p| p.final_state().position())
+        .collect()
+}
+
+/// Write `endpoints` as CSV with a `north_m,east_m` header and one row per point, at
+/// full precision.
+pub fn endpoints_to_csv<P: AsRef<Path>>(endpoints: &[(f64, f64)], path: P) -> io::Result<()> {
+    let mut csv = String::from("north_m,east_m\n");
+    for (n, e) in endpoints {
+        csv.push_str(&format!("{n:e},{e:e}\n"));
+    }
+    fs::write(path, csv)
+}
+
+/// Sampling schedule and stopping rule of [`predict_ensemble_adaptive`].
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveEnsembleOpts {
+    /// Samples drawn before the first spread estimate (at least 2).
+    pub initial_samples: usize,
+    /// Samples added per round while the spread is still moving.
+    pub batch: usize,
+    /// Hard cap on the ensemble size.
+    pub max_samples: usize,
+    /// Absolute tolerance [m²] on the change of the endpoint covariance (Frobenius
+    /// norm) over one batch.
+    pub tolerance_m2: f64,
+    /// Relative tolerance on the same change, as a fraction of the covariance norm.
+    pub relative_tolerance: f64,
+}
+
+impl Default for AdaptiveEnsembleOpts {
+    fn default() -> Self {
+        Self {
+            initial_samples: 8,
+            batch: 8,
+            max_samples: 256,
+            tolerance_m2: 0.01,
+            relative_tolerance: 0.05,
+        }
+    }
+}
+
+/// Result of [`predict_ensemble_adaptive`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveEnsemble<S, U> {
+    pub predictions: Vec<Prediction<S, U>>,
+    /// Mean endpoint `(north, east)` [m].
+    pub endpoint_mean: (f64, f64),
+    /// Sample covariance of the endpoints [m²], north first.
+    pub endpoint_covariance: Matrix2<f64>,
+    /// Whether the covariance settled before `max_samples` was reached.
+    pub converged: bool,
+}
+
+/// Monte Carlo ensemble whose size adapts to the spread: draws `initial_samples`
+/// inputs from `sample_input`, then adds `batch` more at a time until one batch moves
+/// the endpoint covariance by less than the tolerance, or `max_samples` is reached.
+/// A pilot flying predictably is covered by a handful of samples.
+pub fn predict_ensemble_adaptive<M, St, F>(
+    mut sample_input: F,
+    initial_state: M::State,
+    model: &M,
+    solver: &mut St,
+    cfg: &HorizonConfig,
+    opts: &AdaptiveEnsembleOpts,
+) -> AdaptiveEnsemble<M::State, M::Control>
+where
+    M: Dynamics,
+    M::State: Position2D,
+    St: Stepper<M>,
+    F: FnMut() -> DroneInput,
+{
+    assert!(
+        opts.initial_samples >= 2,
+        "need at least two initial samples"
+    );
+    assert!(opts.batch > 0, "batch must be > 0");
+    assert!(
+        opts.max_samples >= opts.initial_samples,
+        "max_samples must be >= initial_samples"
+    );
+
+    let mut predictions = Vec::with_capacity(opts.max_samples);
+    let mut add = |count: usize, predictions: &mut Vec<_>| {
+        for _ in 0..count {
+            let input = sample_input();
+            predictions.push(predict_cfg(
+                &input,
+                initial_state.clone(),
+                model,
+                solver,
+                cfg,
+            ));
+        }
+    };
+
+    add(opts.initial_samples, &mut predictions);
+    let (mut mean, mut covariance) = endpoint_moments(&ensemble_endpoints(&predictions));
+    let mut converged = false;
+    while predictions.len() < opts.max_samples {
+        let count = opts.batch.min(opts.max_samples - predictions.len());
+        add(count, &mut predictions);
+        let (next_mean, next_covariance) = endpoint_moments(&ensemble_endpoints(&predictions));
+        let change = (next_covariance - covariance).norm();
+        (mean, covariance) = (next_mean, next_covariance);
+        if change
+            <= opts
+                .tolerance_m2
+                .max(opts.relative_tolerance * covariance.norm())
+        {
+            converged = true;
+            break;
+        }
+    }
+
+    AdaptiveEnsemble {
+        predictions,
+        endpoint_mean: mean,
+        endpoint_covariance: covariance,
+        converged,
+    }
+}
+
+/// Mean and unbiased sample covariance of at least two points.
+fn endpoint_moments(points: &[(f64, f64)]) -> ((f64, f64), Matrix2<f64>) {
+    let n = points.len() as f64;
+    let (sn, se) = points
+        .iter()
+        .fold((0.0, 0.0), |(a, b), (pn, pe)| (a + pn, b + pe));
+    let mean = (sn / n, se / n);
+    let mut covariance = Matrix2::zeros();
+    for (pn, pe) in points {
+        let d = Vector2::new(pn - mean.0, pe - mean.1);
+        covariance += d * d.transpose();
+    }
+    (mean, covariance / (n - 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+        predict::predict,
+        solvers::Rk4,
+        types::DroneInput,
+    };
+
+    #[test]
+    fn position_variance_grows_quadratically_without_drag() {
+        let model = SimpleQuadcopter::new(0.0);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let prediction = predict(
+            &input,
+            SimpleQuadState::new(0.0, 0.0, 3.0, 0.0, 0.0),
+            &model,
+            &mut Rk4,
+            0.0,
+            4.0,
+            400,
+        );
+
+        // Uncertain velocity only, no process noise: var(north) = var(v_north) * t^2
+        let sigma_v2 = 0.25;
+        let p0 = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![
+            0.0, 0.0, sigma_v2, sigma_v2, 0.0, 0.0, 0.0,
+        ]));
+        let q = DMatrix::zeros(7, 7);
+
+        let covs = propagate_covariance(&model, &prediction, p0, q);
+        assert_eq!(covs.len(), prediction.states.len());
+
+        for i in [100, 200, 400] {
+            let t = prediction.t_at(i);
+            assert!((covs[i][(0, 0)] - sigma_v2 * t * t).abs() < 1e-9);
+            assert!((covs[i][(1, 1)] - sigma_v2 * t * t).abs() < 1e-9);
+        }
+        assert!((covs[400][(0, 0)] / covs[100][(0, 0)] - 16.0).abs() < 1e-6);
+    }
+
+    fn position_block_diff(a: &DMatrix<f64>, b: &DMatrix<f64>) -> f64 {
+        let da = a.fixed_view::<2, 2>(0, 0).into_owned();
+        let db = b.fixed_view::<2, 2>(0, 0).into_owned();
+        (da - db).norm() / db.norm()
+    }
+
+    #[test]
+    fn unscented_matches_linearized_for_linear_motion() {
+        // Zero control: only drag acts, so the dynamics are linear in the state
+        let model = SimpleQuadcopter::new(0.3);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 0.0,
+            yaw_rate_rps: 0.0,
+            thrust_norm: 0.5,
+        };
+        let x0 = SimpleQuadState::new(0.0, 0.0, 4.0, 1.0, 0.2);
+        let p0 = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![
+            0.5, 0.5, 0.2, 0.2, 0.1, 0.3, 0.1,
+        ]));
+
+        let ut = predict_unscented(
+            &input,
+            x0,
+            &p0,
+            UnscentedOpts::default(),
+            &model,
+            &mut Rk4,
+            0.0,
+            3.0,
+            300,
+        );
+        let nominal = predict(&input, x0, &model, &mut Rk4, 0.0, 3.0, 300);
+        let lin = propagate_covariance(&model, &nominal, p0, DMatrix::zeros(7, 7));
+
+        assert!(
+            (ut.mean.final_state().to_dvector() - nominal.final_state().to_dvector()).amax() < 1e-9
+        );
+        assert!(position_block_diff(&ut.covariances[300], &lin[300]) < 1e-6);
+    }
+
+    #[test]
+    fn unscented_differs_from_linearized_in_fast_turn() {
+        let model = SimpleQuadcopter::new(0.1);
+        let input = DroneInput {
+            roll_rad: 0.0,
+            pitch_rad: 20f64.to_radians(),
+            yaw_rate_rps: 1.0,
+            thrust_norm: 0.5,
+        };
+        let x0 = SimpleQuadState::zero();
+        let p0 = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.6_f64.powi(2),
+            0.0,
+            0.0,
+        ]));
+
+        let ut = predict_unscented(
+            &input,
+            x0,
+            &p0,
+            UnscentedOpts::default(),
+            &model,
+            &mut Rk4,
+            0.0,
+            4.0,
+            400,
+        );
+        let nominal = predict(&input, x0, &model, &mut Rk4, 0.0, 4.0, 400);
+        let lin = propagate_covariance(&model, &nominal, p0, DMatrix::zeros(7, 7));
+
+        assert!(position_block_diff(&ut.covariances[400], &lin[400]) > 0.1);
+    }
+
+    #[test]
+    fn confidence_ellipse_axes_follow_eigenvalues() {
+        let cov = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![4.0, 1.0, 9.0]));
+        let pts = confidence_ellipse((10.0, -5.0), &cov, 2.0, 360);
+
+        assert_eq!(pts.first(), pts.last());
+        let max_dn = pts.iter().map(|p| (p.0 - 10.0).abs()).fold(0.0, f64::max);
+        let max_de = pts.iter().map(|p| (p.1 + 5.0).abs()).fold(0.0, f64::max);
+        assert!((max_dn - 4.0).abs() < 1e-9);
+        assert!((max_de - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn endpoints_match_each_final_position() {
+        let model = SimpleQuadcopter::new(0.1);
+        let predictions: Vec<_> = (0..7)
+            .map(|k| {
+                let input = DroneInput {
+                    roll_rad: 0.0,
+                    pitch_rad: 0.1,
+                    yaw_rate_rps: 0.1 * k as f64,
+                    thrust_norm: 0.5,
+                };
+                predict(
+                    &input,
+                    SimpleQuadState::zero(),
+                    &model,
+                    &mut Rk4,
+                    0.0,
+                    5.0,
+                    50,
+                )
+            })
+            .collect();
+
+        let endpoints = ensemble_endpoints(&predictions);
+        assert_eq!(endpoints.len(), predictions.len());
+        for (p, e) in predictions.iter().zip(&endpoints) {
+            assert_eq!(p.final_state().position(), *e);
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "pilots_intent_{}_endpoints.csv",
+            std::process::id()
+        ));
+        endpoints_to_csv(&endpoints, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("north_m,east_m"));
+        let parsed: Vec<(f64, f64)> = lines
+            .map(|l| {
+                let (n, e) = l.split_once(',').unwrap();
+                (n.parse().unwrap(), e.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(parsed, endpoints);
+    }
+
+    #[test]
+    fn tight_ensemble_lands_in_one_bin() {
+        let model = SimpleQuadcopter::new(0.1);
+        let predictions: Vec<_> = (0..50)
+            .map(|k| {
+                let input = DroneInput {
+                    roll_rad: 10f64.to_radians() + 1e-5 * (k as f64 - 25.0),
+                    pitch_rad: 15f64.to_radians(),
+                    yaw_rate_rps: 0.0,
+                    thrust_norm: 0.5,
+                };
+                predict(
+                    &input,
+                    SimpleQuadState::zero(),
+                    &model,
+                    &mut Rk4,
+                    0.0,
+                    10.0,
+                    100,
+                )
+            })
+            .collect();
+
+        let hist = endpoint_histogram(&predictions, 7, 5);
+        assert_eq!(hist.counts.len(), 7);
+        assert!(hist.counts.iter().all(|row| row.len() == 5));
+        assert_eq!((hist.north_edges.len(), hist.east_edges.len()), (8, 6));
+
+        // The endpoints span far less than 1 m, so each axis is 1 m centred on them,
+        // away from the start at the origin
+        let endpoints = ensemble_endpoints(&predictions);
+        let span = |axis: fn(&(f64, f64)) -> f64| {
+            let values = endpoints.iter().map(axis);
+            let lo = values.clone().fold(f64::INFINITY, f64::min);
+            (lo, values.fold(f64::NEG_INFINITY, f64::max))
+        };
+        for (edges, (lo, hi)) in [
+            (&hist.north_edges, span(|p| p.0)),
+            (&hist.east_edges, span(|p| p.1)),
+        ] {
+            assert!(hi - lo < 0.2 && lo > 10.0);
+            let center = 0.5 * (lo + hi);
+            assert!((edges[0] - (center - 0.5)).abs() < 1e-12);
+            assert!((edges[edges.len() - 1] - (center + 0.5)).abs() < 1e-12);
+        }
+
+        let total: u32 = hist.counts.iter().flatten().sum();
+        assert_eq!(total, 50);
+        assert_eq!(hist.counts[3][2], 50);
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn predictable_pilot_needs_far_fewer_samples() {
+        use crate::rng::{Rng, SplitMix64};
+
+        let model = SimpleQuadcopter::new(0.3);
+        let cfg = HorizonConfig::new(0.0, 5.0, 50).unwrap();
+        let opts = AdaptiveEnsembleOpts::default();
+        let run = |sigma: f64| {
+            let mut rng = SplitMix64::new(7);
+            predict_ensemble_adaptive(
+                || DroneInput {
+                    roll_rad: sigma * rng.standard_normal(),
+                    pitch_rad: 0.15 + sigma * rng.standard_normal(),
+                    yaw_rate_rps: 2.0 * sigma * rng.standard_normal(),
+                    thrust_norm: 0.5,
+                },
+                SimpleQuadState::zero(),
+                &model,
+                &mut Rk4,
+                &cfg,
+                &opts,
+            )
+        };
+
+        let calm = run(1e-4);
+        let erratic = run(0.2);
+        assert!(calm.converged);
+        assert_eq!(calm.predictions.len(), opts.initial_samples + opts.batch);
+        assert!(calm.endpoint_covariance.norm() < 1e-4);
+        assert!(
+            erratic.predictions.len() >= 4 * calm.predictions.len(),
+            "erratic used {} samples",
+            erratic.predictions.len()
+        );
+        assert!(erratic.endpoint_covariance.norm() > 1.0);
+
+        let endpoints = ensemble_endpoints(&erratic.predictions);
+        let mean_north = endpoints.iter().map(|p| p.0).sum::<f64>() / endpoints.len() as f64;
+        assert!((erratic.endpoint_mean.0 - mean_north).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,87 @@
+// Unit conversions at API boundaries. Models work in SI: m, m/s, rad, rad/s.
+
+/// Metres per second in one international knot (1852 m per hour).
+pub const MPS_PER_KNOT: f64 = 1852.0 / 3600.0;
+
+pub fn knots_to_mps(knots: f64) -> f64 {
+    knots * MPS_PER_KNOT
+}
+
+pub fn mps_to_knots(mps: f64) -> f64 {
+    mps / MPS_PER_KNOT
+}
+
+pub fn deg_to_rad(deg: f64) -> f64 {
+    deg.to_radians()
+}
+
+pub fn rad_to_deg(rad: f64) -> f64 {
+    rad.to_degrees()
+}
+
+/// Degrees per second to radians per second.
+pub fn dps_to_rps(dps: f64) -> f64 {
+    dps.to_radians()
+}
+
+/// Radians per second to degrees per second.
+pub fn rps_to_dps(rps: f64) -> f64 {
+    rps.to_degrees()
+}
+
+/// Speed in knots; convert with `.mps()` or `f64::from`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Knots(pub f64);
+
+impl Knots {
+    pub fn mps(self) -> f64 {
+        knots_to_mps(self.0)
+    }
+}
+
+impl From<Knots> for f64 {
+    /// SI value in m/s.
+    fn from(k: Knots) -> f64 {
+        k.mps()
+    }
+}
+
+/// Angle in degrees; convert with `.rad()` or `f64::from`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Degrees(pub f64);
+
+impl Degrees {
+    pub fn rad(self) -> f64 {
+        deg_to_rad(self.0)
+    }
+}
+
+impl From<Degrees> for f64 {
+    /// SI value in rad.
+    fn from(d: Degrees) -> f64 {
+        d.rad()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_knot_in_mps() {
+        assert!((knots_to_mps(1.0) - 0.514444).abs() < 1e-6);
+        assert!((Knots(20.0).mps() - 10.288_888).abs() < 1e-6);
+        assert_eq!(f64::from(Knots(3.0)), knots_to_mps(3.0));
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for x in [-370.0, -1.5, 0.0, 0.25, 42.0, 1e4] {
+            assert!((mps_to_knots(knots_to_mps(x)) - x).abs() <= 1e-12 * x.abs().max(1.0));
+            assert!((rad_to_deg(deg_to_rad(x)) - x).abs() <= 1e-12 * x.abs().max(1.0));
+            assert!((rps_to_dps(dps_to_rps(x)) - x).abs() <= 1e-12 * x.abs().max(1.0));
+        }
+        assert_eq!(f64::from(Degrees(180.0)), std::f64::consts::PI);
+        assert_eq!(Degrees(90.0).rad(), std::f64::consts::FRAC_PI_2);
+    }
+}
@@ -0,0 +1,98 @@
+//! Counts heap allocations of the Backward Euler workspace path. This lives in its own
+//! test binary because the counting `#[global_allocator]` would otherwise replace the
+//! allocator of the whole library test suite.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use nalgebra::{DMatrix, DVector};
+use pilots_intent::{
+    dynamic_models::{SimpleQuadState, SimpleQuadcopter},
+    solvers::{BackwardEuler, NewtonOpts, newton},
+    traits::{Dynamics, LinearizableDynamics, Stepper},
+    types::{DroneInput, StateVector},
+};
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// The per-step allocating formulation the workspace path replaced.
+fn reference_step(
+    model: &SimpleQuadcopter,
+    t: f64,
+    state: &SimpleQuadState,
+    control: &<SimpleQuadcopter as Dynamics>::Control,
+    dt: f64,
+) -> SimpleQuadState {
+    let u_prev = state.to_dvector();
+    let f = |x: &DVector<f64>| {
+        let fx = model.derivative(t + dt, &SimpleQuadState::from_dvector(x.clone()), control);
+        x - &u_prev - fx.to_dvector() * dt
+    };
+    let j = |x: &DVector<f64>| {
+        let j = model.jacobian(t + dt, &SimpleQuadState::from_dvector(x.clone()), control);
+        DMatrix::<f64>::identity(7, 7) - j * dt
+    };
+    let (x, _) = newton(f, j, u_prev.clone(), NewtonOpts::default());
+    SimpleQuadState::from_dvector(x)
+}
+
+#[test]
+fn workspace_path_matches_allocating_path_with_fewer_allocations() {
+    let model = SimpleQuadcopter::new(0.3);
+    let control = model.input_to_control(&DroneInput {
+        roll_rad: 5f64.to_radians(),
+        pitch_rad: 15f64.to_radians(),
+        yaw_rate_rps: 0.6,
+        thrust_norm: 0.5,
+    });
+    let dt = 0.01;
+    let steps = 500;
+
+    let mut solver = BackwardEuler::default();
+    let mut state = SimpleQuadState::new(0.0, 0.0, 5.0, 1.0, 0.2);
+    // Warm up so the workspace is sized before counting
+    solver.step(&model, 0.0, &state, &control, dt);
+
+    let before = allocations();
+    for i in 0..steps {
+        state = solver.step(&model, i as f64 * dt, &state, &control, dt);
+    }
+    let workspace_allocs = allocations() - before;
+
+    let mut reference = SimpleQuadState::new(0.0, 0.0, 5.0, 1.0, 0.2);
+    let before = allocations();
+    for i in 0..steps {
+        reference = reference_step(&model, i as f64 * dt, &reference, &control, dt);
+    }
+    let reference_allocs = allocations() - before;
+
+    assert_eq!(state, reference);
+    assert!(
+        2 * workspace_allocs <= reference_allocs,
+        "workspace path allocated {workspace_allocs} times vs {reference_allocs}"
+    );
+}